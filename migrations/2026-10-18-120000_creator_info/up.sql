@@ -0,0 +1,12 @@
+-- Biographical data and external ids for creators
+
+create table creator_info (
+  creator_id integer primary key references creators (id) on delete cascade,
+  born smallint,
+  died smallint,
+  nationality varchar(100),
+  bio text,
+  wikidata varchar(20),
+  lambiek varchar(100),
+  phantomwiki varchar(200)
+);
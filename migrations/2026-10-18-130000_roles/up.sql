@@ -0,0 +1,78 @@
+-- Vocabulary of roles for episodes_by and articles_by
+
+create table roles (
+  code varchar(10) primary key,
+  label varchar(100) not null,
+  noun varchar(100) not null,
+  ord smallint not null unique,
+  main boolean not null,
+  weight smallint not null
+);
+
+comment on column roles.label is 'Prefix for credits, e.g. "Bild:"';
+comment on column roles.noun is 'What a creator with the role is called';
+comment on column roles.main is 'Main roles are listed as episodes on creator pages';
+comment on column roles.weight is 'Extra score per episode in creator_contributions';
+
+insert into roles (code, label, noun, ord, main, weight) values
+  ('by', 'Av', 'upphovsperson', 10, true, 9),
+  ('text', 'Text:', 'författare', 20, true, 9),
+  ('bild', 'Bild:', 'tecknare', 30, true, 9),
+  ('ink', 'Tush:', 'tuschare', 40, true, 5),
+  ('color', 'Färgläggning:', 'färgläggare', 50, false, 0),
+  ('orig', 'Efter en originalberättelse av:', 'originalförfattare', 60, true, 5),
+  ('redax', 'Redaktion:', 'redaktion', 70, false, 5),
+  ('xlat', 'Översättning:', 'översättare', 80, false, 0),
+  ('textning', 'Textsättning:', 'textsättare', 90, false, 0);
+
+-- Keep any other roles already in use, so the foreign keys can be added.
+insert into roles (code, label, noun, ord, main, weight)
+select code, code || ':', 'något annat', 100 + row_number() over (order by code), false, 0
+from (
+  select role from episodes_by union select role from articles_by
+) as used (code)
+where code not in (select code from roles);
+
+alter table episodes_by add foreign key (role) references roles (code);
+alter table articles_by add foreign key (role) references roles (code);
+
+drop materialized view creator_contributions;
+drop view creator_sum_episodes;
+
+create view creator_sum_episodes (id, n, weighted, earliest, latest)
+as select c.id, cast(count(distinct eb.episode_id) as integer),
+  cast(coalesce(sum(r.weight), 0) as integer),
+  min(magic), max(magic)
+from
+   creators c
+   left outer join (((
+       creator_aliases ca
+       left outer join (
+           episodes_by eb
+           join roles r on r.code = eb.role
+           left outer join episode_parts ep ON ep.episode_id = eb.episode_id
+         ) on eb.creator_alias_id = ca.id
+       )
+       left outer join publications p ON p.episode_part = ep.id
+     )
+     left outer join issues i ON i.id = p.issue_id
+   ) on ca.creator_id = c.id
+group by c.id
+order by c.name;
+
+create materialized view creator_contributions
+  (id, name, slug, score, n_episodes, n_covers, n_articles, first_issue, latest_issue)
+as select
+  cr.id, cr.name, cr.slug,
+  cast(e.n + e.weighted + 6*a.n + round(90*sqrt(c.n)) as integer),
+  e.n,
+  c.n,
+  a.n,
+  least(e.earliest, c.earliest, a.earliest),
+  greatest(e.latest, c.latest, a.latest)
+from
+  creators cr
+  left join creator_sum_episodes e on e.id = cr.id
+  left join creator_sum_articles a on a.id = cr.id
+  left join creator_sum_covers c on c.id = cr.id
+  order by cr.name;
@@ -0,0 +1,22 @@
+-- Metadata about titles, maintained in extra-titles.data
+
+create table title_info (
+  title_id integer primary key references titles (id) on delete cascade,
+  daystrip boolean not null default false,
+  sundays boolean not null default false,
+  syndicate varchar(200),
+  country varchar(100),
+  orig_creator varchar(200)
+);
+
+-- The strip kinds was previously hardcoded.
+insert into title_info (title_id, daystrip, sundays)
+select id,
+  slug in ('blixt-gordon', 'fantomen', 'johnny-hazard',
+           'king-vid-granspolisen', 'latigo', 'mandrake', 'rick-o-shay'),
+  slug in ('fantomen', 'johnny-hazard', 'ludvig', 'mandrake',
+           'mandrake-fantomen')
+from titles
+where slug in ('blixt-gordon', 'fantomen', 'johnny-hazard',
+               'king-vid-granspolisen', 'latigo', 'mandrake', 'rick-o-shay',
+               'ludvig', 'mandrake-fantomen');
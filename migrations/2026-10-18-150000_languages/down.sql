@@ -0,0 +1,2 @@
+alter table episodes drop constraint episodes_orig_lang_fkey;
+drop table languages;
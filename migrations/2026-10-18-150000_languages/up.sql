@@ -0,0 +1,29 @@
+-- Languages of original episodes
+
+create table languages (
+  code varchar(10) primary key,
+  name varchar(100) not null
+);
+
+comment on column languages.code is 'Language code, as in xml:lang';
+comment on column languages.name is 'Swedish name of the language, e.g. "Engelska"';
+
+insert into languages (code, name) values
+  ('da', 'Danska'),
+  ('de', 'Tyska'),
+  ('en', 'Engelska'),
+  ('es', 'Spanska'),
+  ('fi', 'Finska'),
+  ('fr', 'Franska'),
+  ('it', 'Italienska'),
+  ('nl', 'Nederländska'),
+  ('no', 'Norska'),
+  ('pt', 'Portugisiska'),
+  ('sv', 'Svenska');
+
+-- Keep any other languages already in use, so the foreign key can be added.
+insert into languages (code, name)
+select distinct orig_lang, orig_lang from episodes
+where orig_lang is not null and orig_lang not in (select code from languages);
+
+alter table episodes add foreign key (orig_lang) references languages (code);
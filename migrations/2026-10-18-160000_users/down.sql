@@ -0,0 +1,4 @@
+drop table read_episodes;
+drop table owned_issues;
+drop table sessions;
+drop table users;
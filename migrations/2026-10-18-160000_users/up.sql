@@ -0,0 +1,29 @@
+-- Local user accounts, for tracking owned issues and read episodes
+
+create table users (
+  id serial primary key,
+  username varchar(100) unique not null,
+  password_hash varchar not null,
+  created timestamp with time zone not null default now()
+);
+
+create table sessions (
+  token varchar(64) primary key,
+  user_id integer not null references users (id) on delete cascade,
+  created timestamp with time zone not null default now()
+);
+
+create table owned_issues (
+  user_id integer not null references users (id) on delete cascade,
+  issue_id integer not null references issues (id) on delete cascade,
+  condition varchar(100),
+  primary key (user_id, issue_id)
+);
+
+comment on column owned_issues.condition is 'Free text condition of the copy, e.g. "Fint"';
+
+create table read_episodes (
+  user_id integer not null references users (id) on delete cascade,
+  episode_id integer not null references episodes (id) on delete cascade,
+  primary key (user_id, episode_id)
+);
@@ -0,0 +1,2 @@
+-- Admin users can edit issue contents on the web.
+alter table users add column admin boolean not null default false;
@@ -0,0 +1,8 @@
+drop trigger refkeys_changes on refkeys;
+drop trigger episodes_changes on episodes;
+drop trigger titles_changes on titles;
+drop trigger issues_changes on issues;
+drop function record_change();
+drop table change_fields;
+drop table changes;
+drop table import_runs;
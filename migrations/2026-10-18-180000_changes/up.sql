@@ -0,0 +1,67 @@
+-- Audit log of changes to the indexed data.
+--
+-- Each read-files run (or web edit) is an import run.  Triggers on the
+-- main tables record every change, with the changed field values, and
+-- the current import run as set in the fanrs.import_run setting.
+
+create table import_runs (
+  id serial primary key,
+  started timestamp with time zone not null default now(),
+  source varchar(100) not null
+);
+
+create table changes (
+  id serial primary key,
+  run_id integer references import_runs on delete cascade,
+  at timestamp with time zone not null default now(),
+  table_name varchar(32) not null,
+  row_id integer not null,
+  action varchar(8) not null,
+  label text
+);
+
+create index changes_run on changes (run_id);
+
+create table change_fields (
+  change_id integer not null references changes on delete cascade,
+  field varchar(32) not null,
+  old_value text,
+  new_value text,
+  primary key (change_id, field)
+);
+
+-- The trigger arguments are the fields making up a label for the row.
+create function record_change() returns trigger language plpgsql as $$
+declare
+  run integer := nullif(current_setting('fanrs.import_run', true), '')::integer;
+  o jsonb := case when tg_op <> 'INSERT' then to_jsonb(old) end;
+  n jsonb := case when tg_op <> 'DELETE' then to_jsonb(new) end;
+  rec jsonb := coalesce(n, o);
+  cid integer;
+begin
+  if tg_op = 'UPDATE' and o = n then
+    return null;
+  end if;
+  insert into changes (run_id, table_name, row_id, action, label)
+  values (run, tg_table_name, (rec->>'id')::integer, lower(tg_op),
+          (select string_agg(rec->>a, ' / ' order by i)
+             from unnest(tg_argv) with ordinality as t(a, i)))
+  returning id into cid;
+  insert into change_fields (change_id, field, old_value, new_value)
+  select cid, key, o->>key, n->>key
+    from jsonb_object_keys(rec) key
+   where key <> 'id'
+     and (o->key) is distinct from (n->key)
+     and coalesce(o->>key, n->>key) is not null;
+  return null;
+end;
+$$;
+
+create trigger issues_changes after insert or update or delete on issues
+  for each row execute function record_change('number_str', 'year');
+create trigger titles_changes after insert or update or delete on titles
+  for each row execute function record_change('title');
+create trigger episodes_changes after insert or update or delete on episodes
+  for each row execute function record_change('name');
+create trigger refkeys_changes after insert or update or delete on refkeys
+  for each row execute function record_change('title');
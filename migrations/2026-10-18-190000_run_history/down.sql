@@ -0,0 +1,5 @@
+drop table import_run_counts;
+alter table import_runs drop column error;
+alter table import_runs drop column years;
+alter table import_runs drop column args;
+alter table import_runs drop column finished;
@@ -0,0 +1,15 @@
+-- History of import runs, including fetch-covers, with the outcome.
+alter table import_runs add column finished timestamp with time zone;
+alter table import_runs add column args text not null default '';
+alter table import_runs add column years smallint[] not null default '{}';
+alter table import_runs add column error text;
+
+-- Runs recorded before this migration did finish, when is unknown.
+update import_runs set finished = started;
+
+create table import_run_counts (
+  run_id integer not null references import_runs on delete cascade,
+  name varchar(50) not null,
+  n integer not null,
+  primary key (run_id, name)
+);
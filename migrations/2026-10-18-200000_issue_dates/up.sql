@@ -0,0 +1,4 @@
+-- Publication date of issues, when known.
+alter table issues add column date date;
+
+comment on column issues.date is 'Release date, may be in the future for announced issues';
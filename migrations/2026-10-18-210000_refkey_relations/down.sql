@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+drop table refkey_aliases;
+drop table refkey_see_also;
+drop table refkey_parents;
@@ -0,0 +1,49 @@
+-- Relations between refkeys, maintained in extra-refkeys.data
+
+create table refkey_parents (
+  refkey_id integer not null references refkeys (id) on delete cascade,
+  parent_id integer not null references refkeys (id) on delete cascade,
+  primary key (refkey_id, parent_id)
+);
+
+create table refkey_see_also (
+  refkey_id integer not null references refkeys (id) on delete cascade,
+  other_id integer not null references refkeys (id) on delete cascade,
+  primary key (refkey_id, other_id)
+);
+
+-- Other names and slugs for a refkey.  An alias with a name is a key
+-- in the data files that means the refkey.  The slug of an alias, of
+-- the given refkey kind, redirects to the refkey.
+create table refkey_aliases (
+  id serial primary key,
+  refkey_id integer not null references refkeys (id) on delete cascade,
+  kind smallint not null,
+  name varchar(200),
+  slug varchar(100) not null,
+  unique (kind, slug)
+);
+
+-- The aliases and redirects was previously hardcoded.
+insert into refkeys (kind, title, slug)
+select 2, title, slug
+from (values ('Julie', '17j'), ('Den 22:e Fantomen', '22'),
+             ('Kit', '22k'), ('Heloise', '22h')) as fa (title, slug)
+where not exists (select 1 from refkeys r where r.kind = 2 and r.slug = fa.slug);
+
+insert into refkey_aliases (refkey_id, kind, name, slug)
+select r.id, a.kind, a.name, a.slug
+from refkeys r
+join (values
+  (2, '17j', 1, 'Julie', 'julie'),
+  (2, '22', 1, 'Kit & Heloise', 'kit-heloise'),
+  (2, '22k', 1, 'Kit', 'kit'),
+  (2, '22h', 1, 'Heloise', 'heloise'),
+  (2, '17j', 2, null, '17.1'),
+  (2, '22k', 2, null, '22.1'),
+  (2, '22h', 2, null, '22.2'),
+  (1, 'christophe-d-errant', 1, null, 'christophe_derrant'),
+  (1, 'olango-folket', 1, null, 'olangofolket'),
+  (1, 'pirat-pete', 1, null, 'piratpete')
+) as a (target_kind, target, kind, name, slug)
+on r.kind = a.target_kind and r.slug = a.target;
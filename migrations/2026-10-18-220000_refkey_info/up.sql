@@ -0,0 +1,8 @@
+-- Encyclopedic data about refkeys, maintained in extra-refkeys.data
+
+create table refkey_info (
+  refkey_id integer primary key references refkeys (id) on delete cascade,
+  kind varchar(20)
+    check (kind in ('character', 'place', 'organisation', 'object')),
+  description text
+);
@@ -0,0 +1,56 @@
+use crate::DbOpt;
+use crate::models::{Change, ChangeSummary, ImportRun};
+use anyhow::{Result, anyhow};
+
+#[derive(clap::Parser)]
+pub struct Args {
+    #[clap(flatten)]
+    db: DbOpt,
+
+    /// Show all changes of this import run.
+    #[arg(long)]
+    run: Option<i32>,
+
+    /// Number of runs to list, or changes to show for a run.
+    #[arg(long, short = 'n', default_value = "10")]
+    limit: i64,
+}
+
+impl Args {
+    pub async fn run(self) -> Result<()> {
+        let mut db = self.db.get_db().await?;
+        if let Some(id) = self.run {
+            let run = ImportRun::get(id, &mut db)
+                .await?
+                .ok_or_else(|| anyhow!("No import run #{id}"))?;
+            let summary = ChangeSummary::for_run(&run, &mut db).await?;
+            println!(
+                "#{} {} {}: {summary}",
+                run.id,
+                run.started.format("%Y-%m-%d %H:%M:%S"),
+                run.source
+            );
+            let changes = Change::for_run(&run, self.limit, &mut db).await?;
+            for change in &changes {
+                println!("{} {}", change.action, change.what());
+                for field in &change.fields {
+                    println!("    {field}");
+                }
+            }
+            if summary.total() > self.limit {
+                println!("... and {} more.", summary.total() - self.limit);
+            }
+        } else {
+            for run in ImportRun::latest(self.limit, &mut db).await? {
+                let summary = ChangeSummary::for_run(&run, &mut db).await?;
+                println!(
+                    "#{} {} {}: {summary}",
+                    run.id,
+                    run.started.format("%Y-%m-%d %H:%M:%S"),
+                    run.source,
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use crate::models::{Language, RefKey, Title, TitleInfo};
+use crate::schema::creator_aliases::dsl as ca;
+use crate::schema::creators::dsl as c;
+use crate::schema::episodes::dsl as e;
+use crate::schema::languages::dsl as l;
+use crate::schema::refkeys::dsl as r;
+use crate::schema::title_info::dsl as ti;
+use crate::schema::titles::dsl as t;
+use anyhow::{Result, bail};
+use diesel::dsl::{exists, not, sql};
+use diesel::prelude::*;
+use diesel::sql_types::Bool;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+/// Check the database for inconsistencies.
+///
+/// All problems found are reported, and an error returned if there
+/// was any.
+pub async fn check(db: &mut AsyncPgConnection) -> Result<()> {
+    let mut problems = check_strips(db).await?;
+    problems.extend(check_titles(db).await?);
+    problems.extend(check_creators(db).await?);
+    problems.extend(check_refkeys(db).await?);
+    problems.extend(check_languages(db).await?);
+    for problem in &problems {
+        eprintln!("{problem}");
+    }
+    if !problems.is_empty() {
+        bail!("Found {} problems", problems.len());
+    }
+    eprintln!("Everything checks out.");
+    Ok(())
+}
+
+/// Check that the strip kinds in title metadata matches the data.
+async fn check_strips(db: &mut AsyncPgConnection) -> Result<Vec<Problem>> {
+    let info = ti::title_info
+        .select((ti::title_id, TitleInfo::as_select()))
+        .load::<(i32, TitleInfo)>(db)
+        .await?
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+    let data = t::titles
+        .left_join(e::episodes)
+        .select((
+            Title::as_select(),
+            sql::<Bool>("bool_or(orig_to_date is not null and orig_sundays)"),
+            sql::<Bool>(
+                "bool_or(orig_to_date is not null and not orig_sundays)",
+            ),
+        ))
+        .group_by(t::titles::all_columns())
+        .order(t::slug)
+        .load::<(Title, bool, bool)>(db)
+        .await?;
+    let no_info = TitleInfo::default();
+    let mut problems = Vec::new();
+    for (title, sundays, daystrip) in data {
+        let info = info.get(&title.id).unwrap_or(&no_info);
+        if info.daystrip && !daystrip {
+            problems.push(Problem::MissingDaystrips(title.clone()));
+        } else if !info.daystrip && daystrip {
+            problems.push(Problem::UnexpectedDaystrips(title.clone()));
+        }
+        if info.sundays && !sundays {
+            problems.push(Problem::MissingSundays(title));
+        } else if !info.sundays && sundays {
+            problems.push(Problem::UnexpectedSundays(title));
+        }
+    }
+    Ok(problems)
+}
+
+/// Check for titles without any episodes.
+///
+/// Such titles can be created from a misspelled title in the
+/// metadata or a reference.
+async fn check_titles(db: &mut AsyncPgConnection) -> Result<Vec<Problem>> {
+    Ok(t::titles
+        .select(Title::as_select())
+        .filter(not(exists(e::episodes.filter(e::title_id.eq(t::id)))))
+        .order(t::slug)
+        .load(db)
+        .await?
+        .into_iter()
+        .map(Problem::TitleWithoutEpisodes)
+        .collect())
+}
+
+/// Check for creators without aliases, which can never be credited.
+async fn check_creators(db: &mut AsyncPgConnection) -> Result<Vec<Problem>> {
+    Ok(c::creators
+        .select(c::slug)
+        .filter(not(exists(
+            ca::creator_aliases.filter(ca::creator_id.eq(c::id)),
+        )))
+        .order(c::slug)
+        .load(db)
+        .await?
+        .into_iter()
+        .map(Problem::CreatorWithoutAliases)
+        .collect())
+}
+
+/// Check that references to creators and titles refer to existing ones.
+async fn check_refkeys(db: &mut AsyncPgConnection) -> Result<Vec<Problem>> {
+    let who = r::refkeys
+        .select(r::slug)
+        .filter(r::kind.eq(RefKey::WHO_ID))
+        .filter(not(exists(c::creators.filter(c::slug.eq(r::slug)))))
+        .order(r::slug)
+        .load::<String>(db)
+        .await?;
+    let titles = r::refkeys
+        .select(r::slug)
+        .filter(r::kind.eq(RefKey::TITLE_ID))
+        .filter(not(exists(t::titles.filter(t::slug.eq(r::slug)))))
+        .order(r::slug)
+        .load::<String>(db)
+        .await?;
+    Ok(who
+        .into_iter()
+        .map(Problem::UnknownCreatorRef)
+        .chain(titles.into_iter().map(Problem::UnknownTitleRef))
+        .collect())
+}
+
+/// Check for languages created by `read-files` without a proper name.
+async fn check_languages(db: &mut AsyncPgConnection) -> Result<Vec<Problem>> {
+    Ok(l::languages
+        .select(Language::as_select())
+        .order(l::code)
+        .load::<Language>(db)
+        .await?
+        .into_iter()
+        .filter(Language::is_unnamed)
+        .map(|lang| Problem::UnnamedLanguage(lang.code))
+        .collect())
+}
+
+#[derive(Debug)]
+enum Problem {
+    MissingDaystrips(Title),
+    UnexpectedDaystrips(Title),
+    MissingSundays(Title),
+    UnexpectedSundays(Title),
+    TitleWithoutEpisodes(Title),
+    CreatorWithoutAliases(String),
+    UnknownCreatorRef(String),
+    UnknownTitleRef(String),
+    UnnamedLanguage(String),
+}
+
+impl Display for Problem {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::MissingDaystrips(title) => write!(
+                out,
+                "Expected daystrips for {} ({}) not found",
+                title.title, title.slug,
+            ),
+            Problem::UnexpectedDaystrips(title) => write!(
+                out,
+                "Unexpected daystrips for {} ({}) found",
+                title.title, title.slug,
+            ),
+            Problem::MissingSundays(title) => write!(
+                out,
+                "Expected sundays for {} ({}) not found",
+                title.title, title.slug,
+            ),
+            Problem::UnexpectedSundays(title) => write!(
+                out,
+                "Unexpected sundays for {} ({}) found",
+                title.title, title.slug,
+            ),
+            Problem::TitleWithoutEpisodes(title) => write!(
+                out,
+                "Title {} ({}) has no episodes",
+                title.title, title.slug,
+            ),
+            Problem::CreatorWithoutAliases(slug) => {
+                write!(out, "Creator {slug} has no aliases")
+            }
+            Problem::UnknownCreatorRef(slug) => {
+                write!(out, "Reference to unknown creator {slug}")
+            }
+            Problem::UnknownTitleRef(slug) => {
+                write!(out, "Reference to unknown title {slug}")
+            }
+            Problem::UnnamedLanguage(code) => {
+                write!(out, "Language {code} has no name")
+            }
+        }
+    }
+}
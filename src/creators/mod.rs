@@ -0,0 +1,774 @@
+mod suggest;
+
+use crate::DbOpt;
+use crate::models::{Creator, ImportRun, RefKey};
+use crate::schema::article_refkeys::dsl as ar;
+use crate::schema::articles_by::dsl as ab;
+use crate::schema::covers_by::dsl as cb;
+use crate::schema::creator_aliases::dsl as ca;
+use crate::schema::creators::dsl as c;
+use crate::schema::episode_refkeys::dsl as er;
+use crate::schema::episodes_by::dsl as eb;
+use crate::schema::refkeys::dsl as r;
+use crate::yearfile::xml_escape;
+use anyhow::{Context, Result, anyhow, bail};
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use roxmltree::{Document, Node};
+use slug::slugify;
+use std::fs::{read_to_string, write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Parser)]
+pub struct Args {
+    #[clap(flatten)]
+    db: DbOpt,
+
+    /// The directory containing the data files.
+    ///
+    /// Changes are written back to `extra-people.data` in this
+    /// directory, so they survive a reload of the data.
+    #[arg(long, short, env = "FANTOMEN_DATA")]
+    basedir: PathBuf,
+
+    #[clap(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(clap::Subcommand)]
+enum Cmd {
+    /// Merge one creator into another.
+    ///
+    /// All aliases of the FROM creator, and thereby all episodes,
+    /// articles and covers, are moved to the INTO creator.  The FROM
+    /// creator is then removed.
+    Merge {
+        /// Slug of the creator to remove.
+        from: String,
+        /// Slug of the creator to keep.
+        into: String,
+    },
+
+    /// Split an alias, with its contributions, to a separate creator.
+    Split {
+        /// Slug of the creator currently owning the alias.
+        creator: String,
+        /// The alias to split off.
+        alias: String,
+        /// Slug for the new creator (default based on the alias).
+        #[arg(long)]
+        slug: Option<String>,
+    },
+
+    /// Add or remove aliases for a creator.
+    #[clap(subcommand)]
+    Alias(AliasCmd),
+
+    /// Change the name and/or slug of a creator.
+    ///
+    /// The old name is kept as an alias.
+    Rename {
+        /// Current slug of the creator.
+        creator: String,
+        /// New name for the creator.
+        #[arg(long)]
+        name: Option<String>,
+        /// New slug for the creator.
+        #[arg(long)]
+        slug: Option<String>,
+    },
+
+    /// List creators that are probably the same person.
+    ///
+    /// Creators are compared by name (ignoring accents, word order
+    /// and initials) and by working on the same episodes and titles.
+    /// Nothing is changed, use merge to act on the suggestions.
+    SuggestMerges(suggest::SuggestMerges),
+}
+
+#[derive(clap::Subcommand)]
+enum AliasCmd {
+    /// Add an alias for a creator.
+    Add {
+        /// Slug of the creator.
+        creator: String,
+        /// The alias name.
+        alias: String,
+    },
+    /// Remove an unused alias from a creator.
+    Remove {
+        /// Slug of the creator.
+        creator: String,
+        /// The alias name.
+        alias: String,
+    },
+}
+
+impl Args {
+    pub async fn run(self) -> Result<()> {
+        if let Cmd::SuggestMerges(args) = &self.cmd {
+            return args.run(&mut self.db.get_db().await?).await;
+        }
+        let mut people = PeopleFile::load(&self.basedir)?;
+        let mut db = self.db.get_db().await?;
+        let cmd = &self.cmd;
+        let run =
+            ImportRun::start("creators", &cmd.options(), &mut db).await?;
+        let result = db
+            .transaction::<_, anyhow::Error, _>(|db| {
+                async move { cmd.run(&mut people, db).await }.scope_boxed()
+            })
+            .await;
+        run.finish(result.as_ref().err().map(|e| format!("{e:#}")), &mut db)
+            .await?;
+        result?;
+        sql_query("refresh materialized view creator_contributions;")
+            .execute(&mut db)
+            .await?;
+        Ok(())
+    }
+}
+
+impl Cmd {
+    /// The subcommand and its arguments, as recorded in the run history.
+    fn options(&self) -> String {
+        let opt = |name: &str, value: &Option<String>| {
+            value
+                .as_ref()
+                .map(|v| format!(" --{name} {v:?}"))
+                .unwrap_or_default()
+        };
+        match self {
+            Cmd::Merge { from, into } => format!("merge {from} {into}"),
+            Cmd::Split {
+                creator,
+                alias,
+                slug,
+            } => format!("split {creator} {alias:?}{}", opt("slug", slug)),
+            Cmd::Alias(AliasCmd::Add { creator, alias }) => {
+                format!("alias add {creator} {alias:?}")
+            }
+            Cmd::Alias(AliasCmd::Remove { creator, alias }) => {
+                format!("alias remove {creator} {alias:?}")
+            }
+            Cmd::Rename {
+                creator,
+                name,
+                slug,
+            } => format!(
+                "rename {creator}{}{}",
+                opt("name", name),
+                opt("slug", slug),
+            ),
+            Cmd::SuggestMerges(_) => "suggest-merges".into(),
+        }
+    }
+
+    /// Do the database changes and prepare the changes to the
+    /// people data file, and save it if everything went well.
+    async fn run(
+        &self,
+        people: &mut PeopleFile,
+        db: &mut AsyncPgConnection,
+    ) -> Result<()> {
+        match self {
+            Cmd::Merge { from, into } => {
+                merge(from, into, people, db).await?
+            }
+            Cmd::Split {
+                creator,
+                alias,
+                slug,
+            } => split(creator, alias, slug.as_deref(), people, db).await?,
+            Cmd::Alias(AliasCmd::Add { creator, alias }) => {
+                let creator = get_creator(creator, db).await?;
+                add_alias(&creator, alias, db).await?;
+                people.add_alias(&creator, alias)?;
+                println!("Added alias {alias:?} for {}.", creator.name);
+            }
+            Cmd::Alias(AliasCmd::Remove { creator, alias }) => {
+                let creator = get_creator(creator, db).await?;
+                remove_alias(&creator, alias, db).await?;
+                people.remove_alias(&creator, alias)?;
+                println!("Removed alias {alias:?} from {}.", creator.name);
+            }
+            Cmd::Rename {
+                creator,
+                name,
+                slug,
+            } => {
+                if name.is_none() && slug.is_none() {
+                    bail!("Nothing to do, give a new name and/or slug.");
+                }
+                rename(creator, name.as_deref(), slug.as_deref(), people, db)
+                    .await?;
+            }
+            Cmd::SuggestMerges(_) => unreachable!("Handled in Args::run"),
+        }
+        people.save()
+    }
+}
+
+async fn merge(
+    from: &str,
+    into: &str,
+    people: &mut PeopleFile,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    let from = get_creator(from, db).await?;
+    let into = get_creator(into, db).await?;
+    if from.id == into.id {
+        bail!("Can't merge {} into itself.", from.slug);
+    }
+    let aliases = get_aliases(&from, db).await?;
+    diesel::update(ca::creator_aliases)
+        .filter(ca::creator_id.eq(from.id))
+        .set(ca::creator_id.eq(into.id))
+        .execute(db)
+        .await?;
+    move_who_refs(&from, &into, db).await?;
+    diesel::delete(c::creators.filter(c::id.eq(from.id)))
+        .execute(db)
+        .await?;
+
+    people.remove_person(&from)?;
+    for alias in &aliases {
+        people.add_alias(&into, alias)?;
+    }
+    println!(
+        "Merged {} ({}) into {} ({}), moving {} aliases.",
+        from.name,
+        from.slug,
+        into.name,
+        into.slug,
+        aliases.len(),
+    );
+    Ok(())
+}
+
+async fn split(
+    creator: &str,
+    alias: &str,
+    slug: Option<&str>,
+    people: &mut PeopleFile,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    let creator = get_creator(creator, db).await?;
+    if alias == creator.name {
+        bail!("Can't split the main name from {}.", creator.slug);
+    }
+    let alias_id = ca::creator_aliases
+        .select(ca::id)
+        .filter(ca::creator_id.eq(creator.id))
+        .filter(ca::name.eq(alias))
+        .first::<i32>(db)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            anyhow!("{alias:?} is not an alias of {}", creator.name)
+        })?;
+    let slug = slug.map_or_else(|| slugify(alias), String::from);
+    let new = diesel::insert_into(c::creators)
+        .values((c::name.eq(alias), c::slug.eq(&slug)))
+        .returning((c::id, c::name, c::slug))
+        .get_result::<Creator>(db)
+        .await
+        .with_context(|| format!("Failed to create {alias:?} ({slug})"))?;
+    diesel::update(ca::creator_aliases)
+        .filter(ca::id.eq(alias_id))
+        .set(ca::creator_id.eq(new.id))
+        .execute(db)
+        .await?;
+
+    people.remove_alias(&creator, alias)?;
+    people.person(&new)?;
+    println!("Split {alias:?} ({slug}) from {}.", creator.name);
+    Ok(())
+}
+
+async fn add_alias(
+    creator: &Creator,
+    alias: &str,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    let existing = c::creators
+        .inner_join(ca::creator_aliases)
+        .select((c::id, c::name, c::slug))
+        .filter(ca::name.eq(alias))
+        .first::<Creator>(db)
+        .await
+        .optional()?;
+    match existing {
+        Some(other) if other.id == creator.id => {
+            println!("{alias:?} is already an alias of {}.", creator.name);
+        }
+        Some(other) => bail!(
+            "{alias:?} is an alias of {} ({}), maybe merge?",
+            other.name,
+            other.slug,
+        ),
+        None => {
+            diesel::insert_into(ca::creator_aliases)
+                .values((ca::creator_id.eq(creator.id), ca::name.eq(alias)))
+                .execute(db)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn remove_alias(
+    creator: &Creator,
+    alias: &str,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    if alias == creator.name {
+        bail!("Can't remove the main name of {}.", creator.slug);
+    }
+    let alias_id = ca::creator_aliases
+        .select(ca::id)
+        .filter(ca::creator_id.eq(creator.id))
+        .filter(ca::name.eq(alias))
+        .first::<i32>(db)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            anyhow!("{alias:?} is not an alias of {}", creator.name)
+        })?;
+    let uses = eb::episodes_by
+        .select(count_star())
+        .filter(eb::creator_alias_id.eq(alias_id))
+        .first::<i64>(db)
+        .await?
+        + ab::articles_by
+            .select(count_star())
+            .filter(ab::creator_alias_id.eq(alias_id))
+            .first::<i64>(db)
+            .await?
+        + cb::covers_by
+            .select(count_star())
+            .filter(cb::creator_alias_id.eq(alias_id))
+            .first::<i64>(db)
+            .await?;
+    if uses > 0 {
+        bail!("{alias:?} is used in {uses} contributions, maybe split?");
+    }
+    diesel::delete(ca::creator_aliases.filter(ca::id.eq(alias_id)))
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+async fn rename(
+    creator: &str,
+    name: Option<&str>,
+    slug: Option<&str>,
+    people: &mut PeopleFile,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    let old = get_creator(creator, db).await?;
+    let new = Creator {
+        id: old.id,
+        name: name.unwrap_or(&old.name).into(),
+        slug: slug.unwrap_or(&old.slug).into(),
+    };
+    if name.is_some() {
+        add_alias(&old, &new.name, db).await?;
+    }
+    diesel::update(c::creators)
+        .filter(c::id.eq(old.id))
+        .set((c::name.eq(&new.name), c::slug.eq(&new.slug)))
+        .execute(db)
+        .await
+        .with_context(|| format!("Failed to rename to {new:?}"))?;
+    diesel::update(r::refkeys)
+        .filter(r::kind.eq(RefKey::WHO_ID))
+        .filter(r::slug.eq(&old.slug))
+        .set((r::title.eq(&new.name), r::slug.eq(&new.slug)))
+        .execute(db)
+        .await?;
+
+    people.rename(&old, &new)?;
+    println!(
+        "Renamed {} ({}) to {} ({}).",
+        old.name, old.slug, new.name, new.slug,
+    );
+    Ok(())
+}
+
+async fn get_creator(
+    slug: &str,
+    db: &mut AsyncPgConnection,
+) -> Result<Creator> {
+    Creator::from_slug(slug, db)
+        .await
+        .optional()?
+        .ok_or_else(|| anyhow!("No creator with slug {slug:?}"))
+}
+
+async fn get_aliases(
+    creator: &Creator,
+    db: &mut AsyncPgConnection,
+) -> Result<Vec<String>, diesel::result::Error> {
+    ca::creator_aliases
+        .select(ca::name)
+        .filter(ca::creator_id.eq(creator.id))
+        .order(ca::name)
+        .load(db)
+        .await
+}
+
+/// Move any who-references to `from` so they refer to `to` instead.
+async fn move_who_refs(
+    from: &Creator,
+    to: &Creator,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    let Some(old) = r::refkeys
+        .select(r::id)
+        .filter(r::kind.eq(RefKey::WHO_ID))
+        .filter(r::slug.eq(&from.slug))
+        .first::<i32>(db)
+        .await
+        .optional()?
+    else {
+        return Ok(());
+    };
+    let new = if let Some(id) = r::refkeys
+        .select(r::id)
+        .filter(r::kind.eq(RefKey::WHO_ID))
+        .filter(r::slug.eq(&to.slug))
+        .first::<i32>(db)
+        .await
+        .optional()?
+    {
+        id
+    } else {
+        diesel::insert_into(r::refkeys)
+            .values((
+                r::kind.eq(RefKey::WHO_ID),
+                r::title.eq(&to.name),
+                r::slug.eq(&to.slug),
+            ))
+            .returning(r::id)
+            .get_result(db)
+            .await?
+    };
+
+    let has_new = er::episode_refkeys
+        .select(er::episode_id)
+        .filter(er::refkey_id.eq(new))
+        .load::<i32>(db)
+        .await?;
+    diesel::update(er::episode_refkeys)
+        .filter(er::refkey_id.eq(old))
+        .filter(er::episode_id.ne_all(has_new))
+        .set(er::refkey_id.eq(new))
+        .execute(db)
+        .await?;
+    diesel::delete(er::episode_refkeys.filter(er::refkey_id.eq(old)))
+        .execute(db)
+        .await?;
+
+    let has_new = ar::article_refkeys
+        .select(ar::article_id)
+        .filter(ar::refkey_id.eq(new))
+        .load::<i32>(db)
+        .await?;
+    diesel::update(ar::article_refkeys)
+        .filter(ar::refkey_id.eq(old))
+        .filter(ar::article_id.ne_all(has_new))
+        .set(ar::refkey_id.eq(new))
+        .execute(db)
+        .await?;
+    diesel::delete(ar::article_refkeys.filter(ar::refkey_id.eq(old)))
+        .execute(db)
+        .await?;
+
+    diesel::delete(r::refkeys.filter(r::id.eq(old)))
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// The `extra-people.data` file.
+///
+/// Changes are made as text edits of the relevant elements only, so
+/// any other formatting and comments in the file are preserved.
+struct PeopleFile {
+    path: PathBuf,
+    data: String,
+}
+
+impl PeopleFile {
+    fn load(base: &Path) -> Result<Self> {
+        let path = base.join("extra-people.data");
+        let data = read_to_string(&path)
+            .with_context(|| format!("Failed to read {path:?}"))?;
+        Document::parse(&data)
+            .with_context(|| format!("Failed to parse {path:?}"))?;
+        Ok(PeopleFile { path, data })
+    }
+
+    fn save(&self) -> Result<()> {
+        write(&self.path, &self.data)
+            .with_context(|| format!("Failed to write {:?}", self.path))
+    }
+
+    /// Find the person element for a creator, as read by read-files.
+    fn find_person(&self, creator: &Creator) -> Result<Option<Range<usize>>> {
+        let doc = Document::parse(&self.data)?;
+        Ok(doc
+            .root_element()
+            .children()
+            .filter(|e| e.has_tag_name("person"))
+            .find(|e| person_slug(*e).as_deref() == Some(&creator.slug))
+            .map(|e| e.range()))
+    }
+
+    /// Get the person element for a creator, creating it if needed.
+    fn person(&mut self, creator: &Creator) -> Result<Range<usize>> {
+        if let Some(range) = self.find_person(creator)? {
+            return Ok(range);
+        }
+        let end = {
+            let doc = Document::parse(&self.data)?;
+            let root = doc.root_element().range();
+            root.start
+                + self.data[root].rfind("</").ok_or_else(|| {
+                    anyhow!("Bad root element in {:?}", self.path)
+                })?
+        };
+        let slug = if creator.slug == slugify(&creator.name) {
+            String::new()
+        } else {
+            format!(" slug=\"{}\"", xml_escape(&creator.slug))
+        };
+        let elem = format!(
+            "<person{slug}><name>{}</name></person>",
+            xml_escape(&creator.name),
+        );
+        self.data.insert_str(end, &format!("  {elem}\n"));
+        Ok(end + 2..end + 2 + elem.len())
+    }
+
+    fn remove_person(&mut self, creator: &Creator) -> Result<()> {
+        if let Some(range) = self.find_person(creator)? {
+            let start = self.line_start(range.start);
+            self.data.replace_range(start..range.end, "");
+        }
+        Ok(())
+    }
+
+    fn add_alias(&mut self, creator: &Creator, alias: &str) -> Result<()> {
+        if alias == creator.name {
+            return Ok(());
+        }
+        let person = self.person(creator)?;
+        let (at, indent) = {
+            let doc = Document::parse(&self.data)?;
+            let person = node_at(&doc, person.start)?;
+            if person_names(person).any(|n| n == alias) {
+                return Ok(());
+            }
+            let last = person
+                .children()
+                .rfind(Node::is_element)
+                .ok_or_else(|| anyhow!("Empty person in {:?}", self.path))?
+                .range();
+            let indent = &self.data[self.line_start(last.start)..last.start];
+            (last.end, indent.to_string())
+        };
+        let indent = if indent.is_empty() { " " } else { &indent };
+        let elem = format!("{indent}<alias>{}</alias>", xml_escape(alias));
+        self.data.insert_str(at, &elem);
+        Ok(())
+    }
+
+    fn remove_alias(&mut self, creator: &Creator, alias: &str) -> Result<()> {
+        let Some(person) = self.find_person(creator)? else {
+            return Ok(());
+        };
+        let range = {
+            let doc = Document::parse(&self.data)?;
+            node_at(&doc, person.start)?
+                .children()
+                .filter(|e| e.has_tag_name("alias"))
+                .find(|e| e.text() == Some(alias))
+                .map(|e| e.range())
+        };
+        if let Some(range) = range {
+            let start = self.line_start(range.start);
+            self.data.replace_range(start..range.end, "");
+        }
+        Ok(())
+    }
+
+    fn rename(&mut self, old: &Creator, new: &Creator) -> Result<()> {
+        let person = self.person(old)?;
+        let (name, slug) = {
+            let doc = Document::parse(&self.data)?;
+            let person = node_at(&doc, person.start)?;
+            let name = person
+                .children()
+                .find(|e| e.has_tag_name("name"))
+                .ok_or_else(|| anyhow!("Person without name"))?
+                .range();
+            (name, person.attribute_node("slug").map(|a| a.range()))
+        };
+        // Edit from the end, so the earlier ranges stays valid.
+        self.data.replace_range(
+            name,
+            &format!("<name>{}</name>", xml_escape(&new.name)),
+        );
+        let slug_attr = if new.slug == slugify(&new.name) {
+            String::new()
+        } else {
+            format!("slug=\"{}\"", xml_escape(&new.slug))
+        };
+        match slug {
+            Some(range) => {
+                let range = if slug_attr.is_empty() {
+                    self.ws_before(range.start)..range.end
+                } else {
+                    range
+                };
+                self.data.replace_range(range, &slug_attr);
+            }
+            None if !slug_attr.is_empty() => {
+                let at = person.start + "<person".len();
+                self.data.insert_str(at, &format!(" {slug_attr}"));
+            }
+            None => (),
+        }
+        self.add_alias(new, &old.name)
+    }
+
+    /// The start of whitespace before `pos`, back to and including
+    /// a preceding newline.
+    fn line_start(&self, pos: usize) -> usize {
+        let ws = self.ws_before(pos);
+        if self.data[..ws].ends_with('\n') && ws < pos {
+            ws - 1
+        } else {
+            ws
+        }
+    }
+
+    /// The start of non-newline whitespace before `pos`.
+    fn ws_before(&self, pos: usize) -> usize {
+        self.data[..pos]
+            .trim_end_matches(|c: char| c.is_whitespace() && c != '\n')
+            .len()
+    }
+}
+
+fn node_at<'a, 'b>(
+    doc: &'a Document<'b>,
+    pos: usize,
+) -> Result<Node<'a, 'b>> {
+    doc.descendants()
+        .find(|n| n.is_element() && n.range().start == pos)
+        .ok_or_else(|| anyhow!("No element at {pos}"))
+}
+
+/// The slug of a person element, as used by read-files.
+fn person_slug(person: Node) -> Option<String> {
+    let name = person.children().find(|e| e.has_tag_name("name"))?.text()?;
+    Some(
+        person
+            .attribute("slug")
+            .map_or_else(|| slugify(name), String::from),
+    )
+}
+
+fn person_names<'a, 'b: 'a>(
+    person: Node<'a, 'b>,
+) -> impl Iterator<Item = &'a str> {
+    person
+        .children()
+        .filter(|e| e.has_tag_name("name") || e.has_tag_name("alias"))
+        .filter_map(|e| e.text())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Creator, PeopleFile};
+
+    fn file(data: &str) -> PeopleFile {
+        PeopleFile {
+            path: "test".into(),
+            data: data.into(),
+        }
+    }
+    fn creator(name: &str, slug: &str) -> Creator {
+        Creator {
+            id: 1,
+            name: name.into(),
+            slug: slug.into(),
+        }
+    }
+
+    #[test]
+    fn add_alias_existing() {
+        let mut f = file(
+            "<people>\n  <person><name>Jaime Vallvé</name>\n    \
+             <alias>J. Vallvé</alias>\n  </person>\n</people>\n",
+        );
+        let c = creator("Jaime Vallvé", "jaime-vallve");
+        f.add_alias(&c, "Jaime Vallve").unwrap();
+        f.add_alias(&c, "J. Vallvé").unwrap();
+        assert_eq!(
+            f.data,
+            "<people>\n  <person><name>Jaime Vallvé</name>\n    \
+             <alias>J. Vallvé</alias>\n    <alias>Jaime Vallve</alias>\n  \
+             </person>\n</people>\n",
+        );
+    }
+
+    #[test]
+    fn add_alias_new_person() {
+        let mut f = file("<people>\n  <!-- comment -->\n</people>\n");
+        let c = creator("Tony DePaul", "tony-depaul");
+        f.add_alias(&c, "T. DePaul").unwrap();
+        assert_eq!(
+            f.data,
+            "<people>\n  <!-- comment -->\n  <person><name>Tony DePaul\
+             </name> <alias>T. DePaul</alias></person>\n</people>\n",
+        );
+    }
+
+    #[test]
+    fn remove_person_and_alias() {
+        let mut f = file(
+            "<people>\n  <person><name>A</name></person>\n  \
+             <person><name>B</name>\n    <alias>C</alias>\n  </person>\n\
+             </people>\n",
+        );
+        f.remove_person(&creator("A", "a")).unwrap();
+        f.remove_alias(&creator("B", "b"), "C").unwrap();
+        assert_eq!(
+            f.data,
+            "<people>\n  <person><name>B</name>\n  </person>\n</people>\n",
+        );
+    }
+
+    #[test]
+    fn rename() {
+        let mut f = file(
+            "<people>\n  <person slug=\"lf\"><name>Falk</name></person>\n\
+             </people>\n",
+        );
+        f.rename(&creator("Falk", "lf"), &creator("Lee Falk", "lee-falk"))
+            .unwrap();
+        assert_eq!(
+            f.data,
+            "<people>\n  <person><name>Lee Falk</name> \
+             <alias>Falk</alias></person>\n</people>\n",
+        );
+    }
+}
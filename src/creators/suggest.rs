@@ -0,0 +1,219 @@
+//! Find creators that are probably the same person.
+//!
+//! Since creators are matched by exact alias text when reading the
+//! data files, small differences in spelling gives separate creators.
+use crate::schema::creator_aliases::dsl as ca;
+use crate::schema::creators::dsl as c;
+use crate::schema::episodes::dsl as e;
+use crate::schema::episodes_by::dsl as eb;
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use slug::slugify;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(clap::Parser)]
+pub struct SuggestMerges {
+    /// Minimum confidence (in percent) for a suggestion to be shown.
+    #[arg(long, default_value_t = 60)]
+    min: u8,
+
+    /// Show at most this many suggestions.
+    #[arg(long)]
+    limit: Option<usize>,
+}
+
+impl SuggestMerges {
+    pub async fn run(&self, db: &mut AsyncPgConnection) -> Result<()> {
+        let creators = Candidate::load_all(db).await?;
+        let mut found = Vec::new();
+        for (i, a) in creators.iter().enumerate() {
+            for b in &creators[i + 1..] {
+                if let Some(s) = Suggestion::check(a, b)
+                    && s.confidence >= f64::from(self.min) / 100.
+                {
+                    found.push(s);
+                }
+            }
+        }
+        found.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        if let Some(limit) = self.limit {
+            found.truncate(limit);
+        }
+        for s in &found {
+            println!(
+                "{:3.0}% {} ({}) ~ {} ({}): {}",
+                s.confidence * 100.,
+                s.a.slug,
+                s.a.name,
+                s.b.slug,
+                s.b.name,
+                s.reasons.join(", "),
+            );
+        }
+        if found.is_empty() {
+            println!("No probable duplicates found.");
+        }
+        Ok(())
+    }
+}
+
+/// A creator, with the data used to compare it to others.
+struct Candidate {
+    name: String,
+    slug: String,
+    aliases: Vec<String>,
+    /// Episodes and roles credited to this creator.
+    credits: BTreeSet<(i32, String)>,
+    titles: BTreeSet<i32>,
+}
+
+impl Candidate {
+    async fn load_all(
+        db: &mut AsyncPgConnection,
+    ) -> Result<Vec<Candidate>, diesel::result::Error> {
+        let mut creators = c::creators
+            .select((c::id, c::name, c::slug))
+            .load::<(i32, String, String)>(db)
+            .await?
+            .into_iter()
+            .map(|(id, name, slug)| {
+                let c = Candidate {
+                    name,
+                    slug,
+                    aliases: vec![],
+                    credits: BTreeSet::new(),
+                    titles: BTreeSet::new(),
+                };
+                (id, c)
+            })
+            .collect::<BTreeMap<_, _>>();
+        for (id, alias) in ca::creator_aliases
+            .select((ca::creator_id, ca::name))
+            .load::<(i32, String)>(db)
+            .await?
+        {
+            if let Some(c) = creators.get_mut(&id) {
+                c.aliases.push(alias);
+            }
+        }
+        for (id, episode, role, title) in eb::episodes_by
+            .inner_join(ca::creator_aliases)
+            .inner_join(e::episodes)
+            .select((ca::creator_id, eb::episode_id, eb::role, e::title_id))
+            .load::<(i32, i32, String, i32)>(db)
+            .await?
+        {
+            if let Some(c) = creators.get_mut(&id) {
+                c.credits.insert((episode, role));
+                c.titles.insert(title);
+            }
+        }
+        let mut creators = creators.into_values().collect::<Vec<_>>();
+        creators.sort_by(|a, b| a.slug.cmp(&b.slug));
+        Ok(creators)
+    }
+}
+
+struct Suggestion<'a> {
+    a: &'a Candidate,
+    b: &'a Candidate,
+    confidence: f64,
+    reasons: Vec<String>,
+}
+
+impl<'a> Suggestion<'a> {
+    fn check(a: &'a Candidate, b: &'a Candidate) -> Option<Self> {
+        let (similarity, reason) = a
+            .aliases
+            .iter()
+            .flat_map(|x| b.aliases.iter().map(move |y| (x, y)))
+            .map(|(x, y)| name_similarity(x, y))
+            .max_by(|x, y| x.0.total_cmp(&y.0))?;
+        if similarity < 0.5 {
+            return None;
+        }
+        let mut reasons = vec![reason.to_string()];
+        let shared = a.credits.intersection(&b.credits).count();
+        let titles = a.titles.intersection(&b.titles).count();
+        let all_titles = a.titles.union(&b.titles).count();
+        let mut support = 0.;
+        if shared > 0 {
+            support += 0.5;
+            reasons.push(format!("{shared} episodes with the same role"));
+        }
+        if titles > 0 {
+            #[allow(clippy::cast_precision_loss)] // Small numbers
+            let part = titles as f64 / all_titles as f64;
+            support += 0.5 * part;
+            reasons.push(format!("{titles} of {all_titles} titles shared"));
+        }
+        Some(Suggestion {
+            a,
+            b,
+            confidence: similarity + (1. - similarity) * support,
+            reasons,
+        })
+    }
+}
+
+/// How similar two names are, from 0 to 1, and why.
+fn name_similarity(a: &str, b: &str) -> (f64, &'static str) {
+    let (a, b) = (slugify(a), slugify(b));
+    if a == b {
+        return (1., "same name except accents or punctuation");
+    }
+    let mut aw = a.split('-').collect::<Vec<_>>();
+    let mut bw = b.split('-').collect::<Vec<_>>();
+    if aw.last() == bw.last() && aw.len() == bw.len() {
+        let initials = aw.iter().zip(&bw).all(|(x, y)| {
+            x.starts_with(y) && y.len() == 1
+                || y.starts_with(x) && x.len() == 1
+                || x == y
+        });
+        if initials {
+            return (0.85, "same name with initials");
+        }
+    }
+    aw.sort_unstable();
+    bw.sort_unstable();
+    if aw == bw {
+        return (0.95, "same name in another order");
+    }
+    let dist = edit_distance(&a, &b);
+    let len = a.chars().count().max(b.chars().count()).max(1);
+    #[allow(clippy::cast_precision_loss)] // Short strings
+    let similarity = 1. - dist as f64 / len as f64;
+    (similarity, "similar spelling")
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let subst = prev[j] + usize::from(ca != *cb);
+            cur[j + 1] = subst.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("falk", "falk"), 0);
+}
+
+#[test]
+fn test_name_similarity() {
+    assert_eq!(name_similarity("Jaime Vallvé", "Jaime Vallve").0, 1.);
+    assert_eq!(name_similarity("Vallvé, Jaime", "Jaime Vallve").0, 0.95);
+    assert_eq!(name_similarity("J. Vallvé", "Jaime Vallve").0, 0.85);
+    assert!(name_similarity("Tony DePaul", "Tony De Paul").0 > 0.9);
+    assert!(name_similarity("Lee Falk", "Sy Barry").0 < 0.5);
+}
@@ -0,0 +1,673 @@
+//! Export the index as csv files or an ods spreadsheet.
+use crate::DbOpt;
+use crate::models::{Price, RefKey};
+use crate::schema::article_refkeys::dsl as ar;
+use crate::schema::articles::dsl as a;
+use crate::schema::creator_aliases::dsl as ca;
+use crate::schema::creators::dsl as c;
+use crate::schema::episode_parts::dsl as ep;
+use crate::schema::episode_refkeys::dsl as er;
+use crate::schema::episodes::dsl as e;
+use crate::schema::issues::dsl as i;
+use crate::schema::other_mags::dsl as om;
+use crate::schema::publications::dsl as p;
+use crate::schema::refkeys::dsl as r;
+use crate::schema::titles::dsl as t;
+use crate::templates::ToHtml;
+use anyhow::Result;
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::BTreeMap;
+use std::fs::{File, create_dir_all};
+use std::io::{self, BufWriter, Cursor, Seek, Write};
+use std::path::PathBuf;
+use tracing::info;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, result::ZipResult};
+
+#[derive(clap::Parser)]
+pub struct Args {
+    #[clap(flatten)]
+    db: DbOpt,
+
+    /// Write a single ods spreadsheet, with a sheet per table.
+    #[clap(long)]
+    ods: bool,
+
+    /// Write a zip archive of the csv files.
+    #[clap(long, conflicts_with = "ods")]
+    zip: bool,
+
+    /// Where to write the export.
+    ///
+    /// A directory for the csv files, or a file for `--ods` or
+    /// `--zip`.  Defaults to the current directory or
+    /// `fantomenindex.ods` / `fantomenindex.zip`.
+    output: Option<PathBuf>,
+}
+
+impl Args {
+    pub async fn run(self) -> Result<()> {
+        let mut db = self.db.get_db().await?;
+        let export = Export::load(&mut db).await?;
+        if self.ods || self.zip {
+            let ext = if self.ods { "ods" } else { "zip" };
+            let path = self
+                .output
+                .unwrap_or_else(|| format!("fantomenindex.{ext}").into());
+            let mut out = BufWriter::new(File::create(&path)?);
+            if self.ods {
+                export.write_ods(&mut out)?;
+            } else {
+                export.write_zip(&mut out)?;
+            }
+            out.flush()?;
+            info!("Wrote {}", path.display());
+        } else {
+            let dir = self.output.unwrap_or_else(|| ".".into());
+            create_dir_all(&dir)?;
+            for table in &export.tables {
+                let path = dir.join(table.filename());
+                let mut out = BufWriter::new(File::create(&path)?);
+                table.write_csv(&mut out)?;
+                out.flush()?;
+                info!("Wrote {} ({} rows)", path.display(), table.rows.len());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The full index, as a set of tables.
+pub struct Export {
+    tables: Vec<Table>,
+}
+
+impl Export {
+    pub async fn load(db: &mut AsyncPgConnection) -> Result<Export, Error> {
+        Ok(Export {
+            tables: vec![
+                issues(db).await?,
+                publications(db).await?,
+                episodes(db).await?,
+                creators(db).await?,
+                refkeys(db).await?,
+            ],
+        })
+    }
+
+    /// Write all tables as csv files in a zip archive.
+    pub fn write_zip<W: Write + Seek>(&self, out: W) -> ZipResult<()> {
+        let mut zip = ZipWriter::new(out);
+        for table in &self.tables {
+            zip.start_file(table.filename(), SimpleFileOptions::default())?;
+            table.write_csv(&mut zip)?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// Write all tables as an OpenDocument spreadsheet.
+    ///
+    /// An ods file is a zip archive with the mimetype first and
+    /// uncompressed, a manifest, and the actual data in `content.xml`.
+    pub fn write_ods<W: Write + Seek>(&self, out: W) -> ZipResult<()> {
+        let mut zip = ZipWriter::new(out);
+        let stored = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored);
+        zip.start_file("mimetype", stored)?;
+        zip.write_all(ODS_MIME.as_bytes())?;
+        zip.start_file(
+            "META-INF/manifest.xml",
+            SimpleFileOptions::default(),
+        )?;
+        write!(
+            zip,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <manifest:manifest xmlns:manifest=\"urn:oasis:names:tc:\
+             opendocument:xmlns:manifest:1.0\" manifest:version=\"1.2\">\n\
+             <manifest:file-entry manifest:full-path=\"/\" \
+             manifest:media-type=\"{ODS_MIME}\"/>\n\
+             <manifest:file-entry manifest:full-path=\"content.xml\" \
+             manifest:media-type=\"text/xml\"/>\n\
+             </manifest:manifest>\n"
+        )?;
+        zip.start_file("content.xml", SimpleFileOptions::default())?;
+        writeln!(
+            zip,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <office:document-content \
+             xmlns:office=\"{ns}office:1.0\" \
+             xmlns:table=\"{ns}table:1.0\" \
+             xmlns:text=\"{ns}text:1.0\" \
+             office:version=\"1.2\"><office:body><office:spreadsheet>",
+            ns = "urn:oasis:names:tc:opendocument:xmlns:",
+        )?;
+        for table in &self.tables {
+            table.write_ods_table(&mut zip)?;
+        }
+        writeln!(zip, "</office:spreadsheet></office:body>")?;
+        writeln!(zip, "</office:document-content>")?;
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// The csv files in a zip archive, in memory.
+    pub fn to_zip(&self) -> ZipResult<Vec<u8>> {
+        let mut out = Cursor::new(Vec::new());
+        self.write_zip(&mut out)?;
+        Ok(out.into_inner())
+    }
+}
+
+const ODS_MIME: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+/// A table of the export, written as a csv file or a spreadsheet.
+struct Table {
+    name: &'static str,
+    columns: &'static [&'static str],
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    fn filename(&self) -> String {
+        format!("{}.csv", self.name)
+    }
+
+    fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{}", self.columns.join(","))?;
+        for row in &self.rows {
+            for (n, value) in row.iter().enumerate() {
+                if n > 0 {
+                    out.write_all(b",")?;
+                }
+                value.write_csv(out)?;
+            }
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    fn write_ods_table(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "<table:table table:name=\"{}\">", self.name)?;
+        write!(out, "<table:table-row>")?;
+        for column in self.columns {
+            Value::from(*column).write_ods(out)?;
+        }
+        writeln!(out, "</table:table-row>")?;
+        for row in &self.rows {
+            write!(out, "<table:table-row>")?;
+            for value in row {
+                value.write_ods(out)?;
+            }
+            writeln!(out, "</table:table-row>")?;
+        }
+        writeln!(out, "</table:table>")
+    }
+}
+
+/// A single cell value in the export.
+#[derive(Debug, PartialEq)]
+enum Value {
+    Null,
+    Int(i64),
+    /// A price, in kronor.
+    Price(Price),
+    Bool(bool),
+    Date(NaiveDate),
+    Text(String),
+}
+
+impl Value {
+    fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Value::Null => Ok(()),
+            Value::Int(v) => write!(out, "{v}"),
+            Value::Price(v) => write!(out, "{:.2}", v.as_kr()),
+            Value::Bool(v) => write!(out, "{v}"),
+            Value::Date(v) => write!(out, "{v}"),
+            Value::Text(v) => {
+                if v.contains([',', '"', '\n', '\r']) {
+                    write!(out, "\"{}\"", v.replace('"', "\"\""))
+                } else {
+                    out.write_all(v.as_bytes())
+                }
+            }
+        }
+    }
+
+    fn write_ods(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Value::Null => write!(out, "<table:table-cell/>"),
+            Value::Int(v) => write!(
+                out,
+                "<table:table-cell office:value-type=\"float\" \
+                 office:value=\"{v}\"><text:p>{v}</text:p></table:table-cell>"
+            ),
+            Value::Price(v) => write!(
+                out,
+                "<table:table-cell office:value-type=\"float\" \
+                 office:value=\"{v:.2}\"><text:p>{v:.2}</text:p>\
+                 </table:table-cell>",
+                v = v.as_kr(),
+            ),
+            Value::Bool(v) => write!(
+                out,
+                "<table:table-cell office:value-type=\"boolean\" \
+                 office:boolean-value=\"{v}\"><text:p>{v}</text:p>\
+                 </table:table-cell>"
+            ),
+            Value::Date(v) => write!(
+                out,
+                "<table:table-cell office:value-type=\"date\" \
+                 office:date-value=\"{v}\"><text:p>{v}</text:p>\
+                 </table:table-cell>"
+            ),
+            Value::Text(v) => {
+                write!(
+                    out,
+                    "<table:table-cell office:value-type=\"string\"><text:p>"
+                )?;
+                v.to_html(out)?;
+                write!(out, "</text:p></table:table-cell>")
+            }
+        }
+    }
+}
+
+impl From<i16> for Value {
+    fn from(v: i16) -> Value {
+        Value::Int(v.into())
+    }
+}
+impl From<i32> for Value {
+    fn from(v: i32) -> Value {
+        Value::Int(v.into())
+    }
+}
+impl From<Price> for Value {
+    fn from(v: Price) -> Value {
+        Value::Price(v)
+    }
+}
+impl From<bool> for Value {
+    fn from(v: bool) -> Value {
+        Value::Bool(v)
+    }
+}
+impl From<NaiveDate> for Value {
+    fn from(v: NaiveDate) -> Value {
+        Value::Date(v)
+    }
+}
+impl From<String> for Value {
+    fn from(v: String) -> Value {
+        Value::Text(v)
+    }
+}
+impl From<&str> for Value {
+    fn from(v: &str) -> Value {
+        Value::Text(v.into())
+    }
+}
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(v: Option<T>) -> Value {
+        v.map_or(Value::Null, Into::into)
+    }
+}
+
+async fn issues(db: &mut AsyncPgConnection) -> Result<Table, Error> {
+    let rows = i::issues
+        .select((
+            i::id,
+            i::year,
+            i::number,
+            i::number_str,
+            i::date,
+            i::pages,
+            i::price,
+            i::ord,
+            i::cover_best,
+        ))
+        .order(i::magic)
+        .load::<(
+            i32,
+            i16,
+            i16,
+            String,
+            Option<NaiveDate>,
+            Option<i16>,
+            Option<Price>,
+            Option<i32>,
+            Option<i16>,
+        )>(db)
+        .await?
+        .into_iter()
+        .map(
+            |(
+                id,
+                year,
+                number,
+                number_str,
+                date,
+                pages,
+                price,
+                ord,
+                best,
+            )| {
+                vec![
+                    id.into(),
+                    year.into(),
+                    number.into(),
+                    number_str.into(),
+                    date.into(),
+                    pages.into(),
+                    price.into(),
+                    ord.into(),
+                    best.into(),
+                ]
+            },
+        )
+        .collect();
+    Ok(Table {
+        name: "issues",
+        columns: &[
+            "issue_id",
+            "year",
+            "number",
+            "number_str",
+            "date",
+            "pages",
+            "price",
+            "ord",
+            "cover_best",
+        ],
+        rows,
+    })
+}
+
+async fn publications(db: &mut AsyncPgConnection) -> Result<Table, Error> {
+    let rows = p::publications
+        .inner_join(i::issues)
+        .left_join(
+            ep::episode_parts.inner_join(e::episodes.inner_join(t::titles)),
+        )
+        .left_join(a::articles)
+        .select((
+            p::id,
+            p::issue_id,
+            i::year,
+            i::number_str,
+            p::seqno,
+            p::label,
+            p::best_plac,
+            ep::episode_id.nullable(),
+            ep::part_no.nullable(),
+            ep::part_name.nullable(),
+            t::title.nullable(),
+            p::article_id,
+            a::title.nullable(),
+        ))
+        .order((i::magic, p::seqno))
+        .load::<(
+            i32,
+            i32,
+            i16,
+            String,
+            Option<i16>,
+            String,
+            Option<i16>,
+            Option<i32>,
+            Option<i16>,
+            Option<String>,
+            Option<String>,
+            Option<i32>,
+            Option<String>,
+        )>(db)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let (id, issue, year, number, seqno, label, best) =
+                (row.0, row.1, row.2, row.3, row.4, row.5, row.6);
+            let (episode, part_no, part_name, title) =
+                (row.7, row.8, row.9, row.10);
+            let (article, article_title) = (row.11, row.12);
+            vec![
+                id.into(),
+                issue.into(),
+                year.into(),
+                number.into(),
+                seqno.into(),
+                (!label.is_empty()).then_some(label).into(),
+                best.into(),
+                episode.into(),
+                part_no.into(),
+                part_name.into(),
+                article.into(),
+                title.or(article_title).into(),
+            ]
+        })
+        .collect();
+    Ok(Table {
+        name: "publications",
+        columns: &[
+            "publication_id",
+            "issue_id",
+            "year",
+            "number_str",
+            "seqno",
+            "label",
+            "best_plac",
+            "episode_id",
+            "part_no",
+            "part_name",
+            "article_id",
+            "title",
+        ],
+        rows,
+    })
+}
+
+async fn episodes(db: &mut AsyncPgConnection) -> Result<Table, Error> {
+    type Row = (
+        (
+            i32,
+            i32,
+            String,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+        ),
+        (
+            Option<String>,
+            Option<String>,
+            Option<NaiveDate>,
+            Option<NaiveDate>,
+            bool,
+            Option<i32>,
+            Option<i32>,
+        ),
+        Option<(String, Option<i16>, Option<i16>)>,
+    );
+    let rows = e::episodes
+        .inner_join(t::titles)
+        .left_join(om::other_mags)
+        .select((
+            (e::id, e::title_id, t::title, e::name, e::teaser, e::note),
+            (
+                e::orig_lang,
+                e::orig_episode,
+                e::orig_date,
+                e::orig_to_date,
+                e::orig_sundays,
+                e::strip_from,
+                e::strip_to,
+            ),
+            (om::name, om::issue, om::year).nullable(),
+        ))
+        .order((t::title, e::id))
+        .load::<Row>(db)
+        .await?
+        .into_iter()
+        .map(|((id, title_id, title, name, teaser, note), orig, mag)| {
+            let (lang, episode, date, to_date, sundays, from, to) = orig;
+            let (mag, mag_issue, mag_year) = match mag {
+                Some((name, issue, year)) => (Some(name), issue, year),
+                None => (None, None, None),
+            };
+            vec![
+                id.into(),
+                title_id.into(),
+                title.into(),
+                name.into(),
+                teaser.into(),
+                note.into(),
+                lang.into(),
+                episode.into(),
+                date.into(),
+                to_date.into(),
+                sundays.into(),
+                from.into(),
+                to.into(),
+                mag.into(),
+                mag_issue.into(),
+                mag_year.into(),
+            ]
+        })
+        .collect();
+    Ok(Table {
+        name: "episodes",
+        columns: &[
+            "episode_id",
+            "title_id",
+            "title",
+            "name",
+            "teaser",
+            "note",
+            "orig_lang",
+            "orig_episode",
+            "orig_date",
+            "orig_to_date",
+            "orig_sundays",
+            "strip_from",
+            "strip_to",
+            "orig_mag",
+            "orig_mag_issue",
+            "orig_mag_year",
+        ],
+        rows,
+    })
+}
+
+async fn creators(db: &mut AsyncPgConnection) -> Result<Table, Error> {
+    let mut aliases = BTreeMap::<i32, Vec<String>>::new();
+    for (creator, alias) in ca::creator_aliases
+        .inner_join(c::creators)
+        .select((ca::creator_id, ca::name))
+        .filter(ca::name.ne(c::name))
+        .order(ca::name)
+        .load::<(i32, String)>(db)
+        .await?
+    {
+        aliases.entry(creator).or_default().push(alias);
+    }
+    let rows = c::creators
+        .select((c::id, c::name, c::slug))
+        .order(c::name)
+        .load::<(i32, String, String)>(db)
+        .await?
+        .into_iter()
+        .map(|(id, name, slug)| {
+            let aliases = aliases.remove(&id).unwrap_or_default();
+            vec![
+                id.into(),
+                name.into(),
+                slug.into(),
+                (!aliases.is_empty()).then(|| aliases.join("; ")).into(),
+            ]
+        })
+        .collect();
+    Ok(Table {
+        name: "creators",
+        columns: &["creator_id", "name", "slug", "aliases"],
+        rows,
+    })
+}
+
+/// Which refkeys are assigned to which episodes and articles.
+async fn refkeys(db: &mut AsyncPgConnection) -> Result<Table, Error> {
+    let on_episodes = er::episode_refkeys
+        .inner_join(r::refkeys)
+        .select((r::id, r::kind, r::title, r::slug, er::episode_id))
+        .order((r::kind, r::slug, er::episode_id))
+        .load::<(i32, i16, String, String, i32)>(db)
+        .await?;
+    let on_articles = ar::article_refkeys
+        .inner_join(r::refkeys)
+        .select((r::id, r::kind, r::title, r::slug, ar::article_id))
+        .order((r::kind, r::slug, ar::article_id))
+        .load::<(i32, i16, String, String, i32)>(db)
+        .await?;
+    let kind = |kind| match kind {
+        RefKey::FA_ID => "fa",
+        RefKey::KEY_ID => "key",
+        RefKey::WHO_ID => "who",
+        RefKey::TITLE_ID => "title",
+        _ => "unknown",
+    };
+    let rows = on_episodes
+        .into_iter()
+        .map(|(id, k, title, slug, episode)| {
+            (id, k, title, slug, Some(episode), None)
+        })
+        .chain(on_articles.into_iter().map(
+            |(id, k, title, slug, article)| {
+                (id, k, title, slug, None, Some(article))
+            },
+        ))
+        .map(|(id, k, title, slug, episode, article)| {
+            vec![
+                id.into(),
+                kind(k).into(),
+                title.into(),
+                slug.into(),
+                episode.into(),
+                article.into(),
+            ]
+        })
+        .collect();
+    Ok(Table {
+        name: "refkeys",
+        columns: &[
+            "refkey_id",
+            "kind",
+            "name",
+            "slug",
+            "episode_id",
+            "article_id",
+        ],
+        rows,
+    })
+}
+
+#[test]
+fn csv_quoting() {
+    let table = Table {
+        name: "test",
+        columns: &["a", "b", "c"],
+        rows: vec![vec![
+            Value::from(17i16),
+            Value::from("Ett \"citat\", med komma"),
+            Value::from(None::<String>),
+        ]],
+    };
+    let mut out = Vec::new();
+    table.write_csv(&mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "a,b,c\n17,\"Ett \"\"citat\"\", med komma\",\n",
+    );
+}
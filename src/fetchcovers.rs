@@ -1,5 +1,5 @@
 use crate::DbOpt;
-use crate::models::IssueRef;
+use crate::models::{ImportRun, IssueRef};
 use crate::schema::covers::dsl as c;
 use crate::schema::issues::dsl as i;
 use anyhow::{Context, Result, anyhow};
@@ -48,6 +48,24 @@ impl Args {
     }
 
     async fn do_fetch(self, mut db: AsyncPgConnection) -> Result<()> {
+        if self.no_op {
+            return self.fetch(None, &mut db).await;
+        }
+        // Only record the options, the database url may contain a password.
+        let args = if self.update_old { "--update-old" } else { "" };
+        let run = ImportRun::start("fetch-covers", args, &mut db).await?;
+        let result = self.fetch(Some(&run), &mut db).await;
+        run.finish(result.as_ref().err().map(|e| format!("{e:#}")), &mut db)
+            .await?;
+        result
+    }
+
+    /// Fetch covers, counting fetched and failed covers in `run`.
+    async fn fetch(
+        &self,
+        run: Option<&ImportRun>,
+        db: &mut AsyncPgConnection,
+    ) -> Result<()> {
         let mut client = WikiClient::new();
         let query = i::issues
             .select((i::id, i::year, i::number_str))
@@ -61,13 +79,20 @@ impl Args {
                 .into_boxed()
         };
         for (id, year, number_str) in
-            query.load::<(i32, i16, String)>(&mut db).await?
+            query.load::<(i32, i16, String)>(db).await?
         {
-            if self.no_op {
-                println!("Would load cover {number_str:>2}/{year}.");
+            if let Some(run) = run {
+                let fetched =
+                    load_cover(&mut client, db, id, year, &number_str)
+                        .await?;
+                let name = if fetched {
+                    "covers fetched"
+                } else {
+                    "covers failed"
+                };
+                run.add_count(name, 1, db).await?;
             } else {
-                load_cover(&mut client, &mut db, id, year, &number_str)
-                    .await?;
+                println!("Would load cover {number_str:>2}/{year}.");
             }
         }
         Ok(())
@@ -101,13 +126,14 @@ impl LoadLocal {
     }
 }
 
+/// Fetch and save a cover, returning true if it was found.
 async fn load_cover(
     client: &mut WikiClient,
     db: &mut AsyncPgConnection,
     id: i32,
     year: i16,
     number_str: &str,
-) -> Result<()> {
+) -> Result<bool> {
     match client.fetchcover(year, number_str).await {
         Ok(imgdata) => {
             save_cover(id, imgdata.as_ref(), db).await?;
@@ -117,12 +143,13 @@ async fn load_cover(
                 number_str,
                 year,
             );
+            Ok(true)
         }
         Err(err) => {
             eprintln!("Failed to fetch cover for {number_str}/{year}: {err}");
+            Ok(false)
         }
     }
-    Ok(())
 }
 
 async fn save_cover(
@@ -133,7 +160,7 @@ async fn save_cover(
     diesel::insert_into(c::covers)
         .values((
             c::issue.eq(id),
-            c::image.eq(imgdata.as_ref()),
+            c::image.eq(imgdata),
             c::fetch_time.eq(now),
         ))
         .on_conflict(c::issue)
@@ -0,0 +1,915 @@
+//! English translations, keyed by the Swedish text.
+//!
+//! Keep the `{}` placeholders in the same order as in the Swedish
+//! text, that is checked by a test.
+
+pub static CATALOG: &[(&str, &str)] = &[
+    // Page layout and front page.
+    ("Rasmus Fantomenindex", "Rasmus Phantom index"),
+    ("Serier", "Comics"),
+    ("Taggar", "Tags"),
+    ("Serieskapare", "Creators"),
+    ("Sök", "Search"),
+    (
+        "Den svenska Fantomentidningen ges ut av Egmont Publishing, \
+         redaktör är Andreas Eriksson. \
+         Fantomen © King Features Syndicate Inc. \
+         Dessa sidor är gjorda av Rasmus Kaj med \
+         <a href=\"https://github.com/kaj/fanrs\">fanrs {}</a> \
+         och fritt tillgängliga. \
+         Omslagsbilder ägs av sina tecknare / förlag, \
+         och presenteras här genom \
+         <a href=\"https://www.phantomwiki.org/\">Phantom wiki</a>.",
+        "The Swedish Fantomen magazine is published by Egmont Publishing, \
+         editor is Andreas Eriksson. \
+         The Phantom © King Features Syndicate Inc. \
+         These pages are made by Rasmus Kaj with \
+         <a href=\"https://github.com/kaj/fanrs\">fanrs {}</a> \
+         and freely available. \
+         Cover images are owned by their artists / publishers, \
+         and presented here through \
+         <a href=\"https://www.phantomwiki.org/\">Phantom wiki</a>.",
+    ),
+    (
+        "Index över {} av de minst {} svenska Fantomentidningar som \
+         kommit ut.  Serier, upphovspersoner, företeelser.",
+        "Index of {} of the at least {} Swedish Fantomen magazines \
+         published.  Comics, creators, phenomena.",
+    ),
+    (
+        "Här listas innehållet i {} av de minst {} svenska \
+         Fantomentidningar som kommit ut. \
+         Du kan välja att titta på en årgång, någon företeelse i \
+         Fantomenserien, eller någon serieskapare i listorna nedan.",
+        "This is the contents of {} of the at least {} Swedish Fantomen \
+         magazines published. \
+         You can choose to look at a year, at something in the Phantom \
+         comic, or at a creator in the lists below.",
+    ),
+    ("Fantomenätten", "The Phantom lineage"),
+    ("Årgångar", "Years"),
+    (
+        "Se även <a href=\"/prices\">prisutvecklingen</a> och \
+         <a href=\"/reprints\">repriserna</a>. \
+         Serierna finns även listade efter \
+         <a href=\"/orig\">originalpublicering</a>. \
+         De senaste <a href=\"/changes\">ändringarna</a> i indexet finns \
+         också listade. \
+         Medlemmar kan <a href=\"/my\">logga in</a> för att hålla reda på \
+         sin samling.",
+        "See also the <a href=\"/prices\">price history</a> and the \
+         <a href=\"/reprints\">reprints</a>. \
+         The comics are also listed by \
+         <a href=\"/orig\">original publication</a>. \
+         The latest <a href=\"/changes\">changes</a> to the index are \
+         also listed. \
+         Members can <a href=\"/my\">log in</a> to keep track of their \
+         collection.",
+    ),
+    (
+        "Kända utgivningsdagar, även för kommande nummer, finns som \
+         <a href=\"/calendar.ics\">kalender</a>.",
+        "Known release dates, also for upcoming issues, are available as \
+         a <a href=\"/calendar.ics\">calendar</a>.",
+    ),
+    ("Se även", "See also"),
+    (
+        "Den andra stora Fantomensiten.",
+        "The other big Phantom site.",
+    ),
+    (
+        "om du vill prenumerera.",
+        "if you want to subscribe (in Swedish).",
+    ),
+    ("för samlare och läsare.", "for collectors and readers."),
+    ("jag som gör det här indexet.", "I make this index."),
+    ("Den här veckan för …", "This week …"),
+    ("{} år sedan", "{} years ago"),
+    ("Alla förekommande serier", "All comics"),
+    (
+        "Personer och företeelser i Fantomens värld",
+        "People and phenomena in the world of the Phantom",
+    ),
+    ("Alla taggar", "All tags"),
+    ("Alla serieskapare", "All creators"),
+    ("Kapten Walker", "Captain Walker"),
+    // Search.
+    ("Sök i Fantomenindexet", "Search the Phantom index"),
+    (
+        "Du kan fritextsöka i de texter som beskriver serier och \
+         artiklar, noveller, etc.",
+        "You can search the texts that describe comics and articles, \
+         short stories, etc.  Note that the texts are mostly in Swedish.",
+    ),
+    (
+        "Du verkar ha javascript avstängt. Det går bra att söka ändå, \
+         men med javascript kan du filtrera sökningen smidigare.",
+        "You seem to have javascript turned off. Searching works anyway, \
+         but with javascript you can filter the search more easily.",
+    ),
+    (
+        "Här kan du söka i allt data som finns i Fantomenindexet.",
+        "Here you can search all data in the Phantom index.",
+    ),
+    ("Referenser", "References"),
+    ("Episoder och artiklar", "Episodes and articles"),
+    // Years and issues.
+    ("Årgång {}", "Year {}"),
+    (
+        "Den svenska Fantomentidningen, årgång {}.",
+        "The Swedish Fantomen magazine, year {}.",
+    ),
+    (
+        "Översikt över Fantomentidningar publicerade år {}.",
+        "Overview of Fantomen magazines published in {}.",
+    ),
+    ("Fantomentidningens {} årgång.", "The {} year of Fantomen."),
+    (
+        "Tidningar nr {} - {} sedan starten.",
+        "Issues {} - {} since the start.",
+    ),
+    ("Innehållet i Fantomen {}.", "Contents of Fantomen {}."),
+    ("Kommer ut {}.", "To be released {}."),
+    ("Utkom {}.", "Released {}."),
+    ("Nr {} från starten.", "Issue {} since the start."),
+    ("{} sidor", "{} pages"),
+    ("Pris", "Price"),
+    ("pris {}", "price {}"),
+    (
+        "Du har denna tidning, i skick {}.",
+        "You have this issue, in condition {}.",
+    ),
+    ("Du har denna tidning.", "You have this issue."),
+    ("Du har inte denna tidning.", "You don't have this issue."),
+    ("Skick", "Condition"),
+    ("Ändra skick", "Change condition"),
+    ("Markera som ägd", "Mark as owned"),
+    ("Ta bort", "Remove"),
+    ("Markera episoderna som lästa", "Mark the episodes as read"),
+    ("min samling", "my collection"),
+    ("redigera", "edit"),
+    ("Nr", "Nr"),
+    ("Omslag av", "Cover by"),
+    (
+        "Nummer {} i omröstningen om bästa äventyr {}.",
+        "Number {} in the vote for best adventure {}.",
+    ),
+    ("Alla nummer {}", "All issues {}"),
+    ("Föregående och nästa nummer", "Previous and next issue"),
+    ("Referenser:", "References:"),
+    ("del {}", "part {}"),
+    ("Även publicerad i", "Also published in"),
+    ("Publicerad i", "Published in"),
+    ("Publicerad i:", "Published in:"),
+    ("Tidigare publicerad i", "Previously published in"),
+    ("originlet", "original"),
+    ("Stripp nr", "Strip nr"),
+    ("Först publicerad", "First published"),
+    ("Dagstrippar", "Daily strips"),
+    ("Söndagssidor", "Sunday pages"),
+    // Roles and languages, from the database.
+    ("Av", "By"),
+    ("Text:", "Text:"),
+    ("Bild:", "Art:"),
+    ("Tush:", "Ink:"),
+    ("Färgläggning:", "Colors:"),
+    (
+        "Efter en originalberättelse av:",
+        "Based on an original story by:",
+    ),
+    ("Redaktion:", "Editor:"),
+    ("Översättning:", "Translation:"),
+    ("Textsättning:", "Lettering:"),
+    ("och", "and"),
+    ("upphovsperson", "creator"),
+    ("författare", "writer"),
+    ("tecknare", "artist"),
+    ("tuschare", "inker"),
+    ("färgläggare", "colorist"),
+    ("originalförfattare", "original writer"),
+    ("redaktion", "editor"),
+    ("översättare", "translator"),
+    ("textsättare", "letterer"),
+    ("något annat", "something else"),
+    ("Danska", "Danish"),
+    ("Tyska", "German"),
+    ("Engelska", "English"),
+    ("Spanska", "Spanish"),
+    ("Finska", "Finnish"),
+    ("Franska", "French"),
+    ("Italienska", "Italian"),
+    ("Nederländska", "Dutch"),
+    ("Norska", "Norwegian"),
+    ("Portugisiska", "Portuguese"),
+    ("Svenska", "Swedish"),
+    // Titles.
+    ("Serier i Fantomentidningen", "Comics in Fantomen"),
+    (
+        "Återkommade och engångs serier som publicerats i den svenska \
+         Fantomentidningen.",
+        "Recurring and one-off comics published in the Swedish Fantomen \
+         magazine.",
+    ),
+    (
+        "Antal episoder syftar på separata episoder. \
+         Om ett äventyr är publicerat i flera delar eller repriserat \
+         flera gånger så är det fortfarande ett äventyr. \
+         Några tidningar saknas fortfarande i indexet, serier och \
+         episoder publicerade där ingår naturligtvis inte i den här \
+         listan.",
+        "The number of episodes counts separate episodes. \
+         An adventure published in several parts or reprinted several \
+         times is still one adventure. \
+         Some issues are still missing from the index, comics and \
+         episodes published there are of course not included in this \
+         list.",
+    ),
+    (
+        "De gånger serien {} publicerats i svenska Fantomentidningen.",
+        "The times the comic {} has been published in the Swedish \
+         Fantomen magazine.",
+    ),
+    (
+        "Här listas de gånger serien <strong>{}</strong> \
+         (<a href=\"https://seriewikin.serieframjandet.se/index.php/Special:Search?search={}&amp;go=go\" \
+         title=\"Sök &quot;{}&quot; i Seriewikin\">Seriewikin</a>, \
+         <a href=\"https://sv.wikipedia.org/wiki/Special:Search?search={}&amp;go=go\" \
+         title=\"Sök &quot;{}&quot; i Svenska Wikipedia\">wikipedia</a>) \
+         förekommer i mina indexerade fantomentidningar.",
+        "These are the times the comic <strong>{}</strong> \
+         (<a href=\"https://seriewikin.serieframjandet.se/index.php/Special:Search?search={}&amp;go=go\" \
+         title=\"Search &quot;{}&quot; in Seriewikin\">Seriewikin</a>, \
+         <a href=\"https://sv.wikipedia.org/wiki/Special:Search?search={}&amp;go=go\" \
+         title=\"Search &quot;{}&quot; in Swedish Wikipedia\">wikipedia</a>) \
+         appears in my indexed Fantomen magazines.",
+    ),
+    ("skapades av", "was created by"),
+    ("distribueras av", "is distributed by"),
+    ("kommer från", "comes from"),
+    (
+        "Du kan lista serierna efter \
+         <a href=\"/titles/{}\">publikation i Fantomentidningen</a>",
+        "You can list the comics by \
+         <a href=\"/titles/{}\">publication in Fantomen</a>",
+    ),
+    (
+        "eller efter originalpublicerig som \
+         <a href=\"/titles/weekdays-{}\">dagstripp</a>",
+        "or by original publication as \
+         <a href=\"/titles/weekdays-{}\">daily strips</a>",
+    ),
+    (
+        "eller <a href=\"/titles/sundays-{}\">söndagssidor</a>",
+        "or <a href=\"/titles/sundays-{}\">Sunday pages</a>",
+    ),
+    (
+        "(så långt information om ursprungspublicering finns med i \
+         indexet).",
+        "(as far as information about the original publication is \
+         included in the index).",
+    ),
+    (
+        "Se även <a href=\"/titles/{}/chronology\">kronologin</a> över \
+         originalpubliceringen.",
+        "See also the <a href=\"/titles/{}/chronology\">chronology</a> of \
+         the original publication.",
+    ),
+    (
+        "Visar bara episoder som publicerats mer än en gång.",
+        "Showing only episodes published more than once.",
+    ),
+    ("Visa alla episoder", "Show all episodes"),
+    (
+        "<a href=\"/titles/{}?reprinted=true\">Visa bara repriser</a>, \
+         dvs episoder som publicerats mer än en gång, eller \
+         <a href=\"/my/unread/{}\">dina olästa episoder</a>.",
+        "<a href=\"/titles/{}?reprinted=true\">Show only reprints</a>, \
+         i.e. episodes published more than once, or \
+         <a href=\"/my/unread/{}\">your unread episodes</a>.",
+    ),
+    (
+        "Episoderna kräver flera sidor: {}.",
+        "The episodes need several pages: {}.",
+    ),
+    // Chronology.
+    ("Kronologi för {}", "Chronology of {}"),
+    (
+        "Originalpubliceringen av {} och vilka delar som publicerats i \
+         svenska Fantomentidningen.",
+        "The original publication of {} and which parts have been \
+         published in the Swedish Fantomen magazine.",
+    ),
+    (
+        "Här visas originalpubliceringen av \
+         <a href=\"/titles/{}\">{}</a> i datumordning, så långt \
+         information om ursprungspublicering finns med i indexet. \
+         Luckor mellan episoderna är sådant som inte publicerats i de \
+         indexerade tidningarna, överlapp är delar som publicerats i mer \
+         än en episod.",
+        "This is the original publication of \
+         <a href=\"/titles/{}\">{}</a> in date order, as far as \
+         information about the original publication is included in the \
+         index. \
+         Gaps between the episodes are parts not published in the \
+         indexed magazines, overlaps are parts published in more than \
+         one episode.",
+    ),
+    (
+        "Under perioden {} har {} {} publicerats.",
+        "During the period {}, {} {} have been published.",
+    ),
+    ("{} {} saknas.", "{} {} are missing."),
+    (
+        "{} {} har publicerats i mer än en episod.",
+        "{} {} have been published in more than one episode.",
+    ),
+    ("söndagssidor", "Sunday pages"),
+    ("strippar", "strips"),
+    ("Tidslinje för {}", "Timeline of {}"),
+    ("Datum", "Date"),
+    ("Strippnr", "Strip nr"),
+    ("Episod", "Episode"),
+    ("Publicerad", "Published"),
+    ("Saknas, {} {}.", "Missing, {} {}."),
+    (
+        "Överlappar {} {} med tidigare episod.",
+        "Overlaps {} {} with a previous episode.",
+    ),
+    // Creators.
+    ("Serieskapare i Fantomentidningen", "Creators in Fantomen"),
+    (
+        "Lista över personer som medverkat i den svenska \
+         Fantomentidningen.",
+        "List of people who have contributed to the Swedish Fantomen \
+         magazine.",
+    ),
+    (
+        "Antal episoder syftar på separata episoder. \
+         Om ett äventyr är publicerat i flera delar eller repriserat \
+         flera gånger så är det fortfarande ett äventyr. \
+         Några tidningar saknas fortfarande i indexet, serier och \
+         episoder publicerade där räknas naturligtvis inte i den här \
+         listan.",
+        "The number of episodes counts separate episodes. \
+         An adventure published in several parts or reprinted several \
+         times is still one adventure. \
+         Some issues are still missing from the index, comics and \
+         episodes published there are of course not counted in this \
+         list.",
+    ),
+    ("Namn", "Name"),
+    (
+        "<span class=\"ifwide\">Antal</span> episoder",
+        "<span class=\"ifwide\">Number of</span> episodes",
+    ),
+    (
+        "<span class=\"ifwide\">Antal</span> omslag",
+        "<span class=\"ifwide\">Number of</span> covers",
+    ),
+    (
+        "<span class=\"ifwide\">Antal</span> artiklar",
+        "<span class=\"ifwide\">Number of</span> articles",
+    ),
+    ("Poäng", "Score"),
+    ("Först", "First"),
+    ("Senast", "Latest"),
+    (
+        "Index över hur {} medverkat i den svenska Fantomentidningen",
+        "Index of how {} has contributed to the Swedish Fantomen magazine",
+    ),
+    (
+        "Här listas vad <strong>{}</strong> \
+         (<a href=\"https://seriewikin.serieframjandet.se/index.php/Special:Search?search={}&amp;go=go\" \
+         title=\"Sök &quot;{}&quot; i Seriewikin\">Seriewikin</a>, \
+         <a href=\"https://sv.wikipedia.org/wiki/Special:Search?search={}&amp;go=go\" \
+         title=\"Sök &quot;{}&quot; i Svenska Wikipedia\">wikipedia</a>) \
+         varit med att skapa som förekommer i mina indexerade \
+         fantomentidningar.",
+        "This is what <strong>{}</strong> \
+         (<a href=\"https://seriewikin.serieframjandet.se/index.php/Special:Search?search={}&amp;go=go\" \
+         title=\"Search &quot;{}&quot; in Seriewikin\">Seriewikin</a>, \
+         <a href=\"https://sv.wikipedia.org/wiki/Special:Search?search={}&amp;go=go\" \
+         title=\"Search &quot;{}&quot; in Swedish Wikipedia\">wikipedia</a>) \
+         has helped create that appears in my indexed Fantomen \
+         magazines.",
+    ),
+    ("född {}", "born {}"),
+    ("död {}", "died {}"),
+    ("Mer om {} på", "More about {} on"),
+    ("Artiklar", "Articles"),
+    ("Omslagsbilder", "Cover images"),
+    (
+        "{} har skapat minst {} omslag till Fantomentidningen. \
+         Här visas några av de bästa, en så komplett lista som indexet \
+         förmår följer nedan.",
+        "{} has created at least {} covers for Fantomen. \
+         Some of the best are shown here, followed by a list as complete \
+         as the index can make it.",
+    ),
+    ("Nr {} i bästa omslag.", "Nr {} in best covers."),
+    ("nr {} i bästa omslag", "nr {} in best covers"),
+    ("Episoder", "Episodes"),
+    ("Medverkat som {}", "Contributed as {}"),
+    // Refkeys.
+    (
+        "Om {} i den svenska Fantomentidnigen.",
+        "About {} in the Swedish Fantomen magazine.",
+    ),
+    (
+        "Personer, platser och företeelser",
+        "People, places and phenomena",
+    ),
+    (
+        "Personer, platser och företeelser i Fantomens värld enligt den \
+         svenska Fantomentidningen.",
+        "People, places and phenomena in the world of the Phantom \
+         according to the Swedish Fantomen magazine.",
+    ),
+    (
+        "Antal episoder syftar på separata episoder. \
+         Om ett äventyr är publicerat i flera delar eller repriserat \
+         flera gånger så är det fortfarande ett äventyr. \
+         Några tidningar saknas fortfarande i indexet, äventyr \
+         publicerade där ingår naturligtvis inte i den här listan.",
+        "The number of episodes counts separate episodes. \
+         An adventure published in several parts or reprinted several \
+         times is still one adventure. \
+         Some issues are still missing from the index, adventures \
+         published there are of course not included in this list.",
+    ),
+    ("Titel", "Title"),
+    (
+        "Antal <span class=\"ifwide\">episoder</span>",
+        "<span class=\"ifwide\">Number of</span> episodes",
+    ),
+    // Origins.
+    ("Originalpublicering", "Original publication"),
+    (
+        "Serier i svenska Fantomentidningen efter originalspråk och \
+         tidigare publicering.",
+        "Comics in the Swedish Fantomen magazine by original language and \
+         previous publication.",
+    ),
+    (
+        "Här listas serierna i de indexerade fantomentidningarna efter \
+         originalspråk och efter tidningar där de tidigare publicerats, \
+         så långt det finns med i indexet.",
+        "The comics in the indexed Fantomen magazines are listed here by \
+         original language and by magazines where they were previously \
+         published, as far as it is included in the index.",
+    ),
+    ("Efter originalspråk", "By original language"),
+    ("Språk", "Language"),
+    ("Tidning", "Magazine"),
+    ("År", "Year"),
+    ("{} original", "{} originals"),
+    (
+        "Serier i svenska Fantomentidningen med original på {}.",
+        "Comics in the Swedish Fantomen magazine with originals in {}.",
+    ),
+    (
+        "Serier i svenska Fantomentidningen som tidigare publicerats i {}.",
+        "Comics in the Swedish Fantomen magazine previously published in \
+         {}.",
+    ),
+    (
+        "Se även <a href=\"/orig\">alla originalspråk och tidningar</a>.",
+        "See also <a href=\"/orig\">all original languages and \
+         magazines</a>.",
+    ),
+    ("Original", "Original"),
+    ("Övriga episoder", "Other episodes"),
+    // Prices and reprints.
+    ("Prisutveckling", "Price history"),
+    (
+        "Omslagspriset för den svenska Fantomentidningen genom åren, även \
+         i dagens penningvärde.",
+        "The cover price of the Swedish Fantomen magazine through the \
+         years, also in today's money.",
+    ),
+    (
+        "Omslagspriset för de tidningar i indexet där priset är känt. \
+         Priserna räknas även om till {} års penningvärde enligt \
+         konsumentprisindex (KPI) från SCB. \
+         Datat finns även som <a href=\"/prices.csv\">csv</a> och \
+         <a href=\"/prices.json\">json</a>.",
+        "The cover price of the issues in the index where the price is \
+         known. \
+         The prices are also converted to {} money according to the \
+         consumer price index (KPI) from Statistics Sweden. \
+         The data is also available as <a href=\"/prices.csv\">csv</a> \
+         and <a href=\"/prices.json\">json</a>.",
+    ),
+    (
+        "<span class=\"nominal\">Pris</span> och \
+         <span class=\"real\">pris i {} års penningvärde</span> per \
+         nummer, i kronor.",
+        "<span class=\"nominal\">Price</span> and \
+         <span class=\"real\">price in {} money</span> per issue, in \
+         Swedish kronor.",
+    ),
+    ("Pris per nummer", "Price per issue"),
+    ("Per årgång", "Per year"),
+    ("I {} års pengar", "In {} money"),
+    (
+        "Per sida<span class=\"ifwide\">, i {} års pengar</span>",
+        "Per page<span class=\"ifwide\">, in {} money</span>",
+    ),
+    ("Repriser", "Reprints"),
+    (
+        "Serieepisoder som publicerats mer än en gång i svenska \
+         Fantomentidningen.",
+        "Comic episodes published more than once in the Swedish Fantomen \
+         magazine.",
+    ),
+    (
+        "Här listas de {} episoder som publicerats mer än en gång i de \
+         indexerade tidningarna, med tiden mellan publiceringarna och om \
+         en publicering bara innehöll en del av episoden. \
+         En publicering som bara är känd genom en notis om tidigare \
+         publicering har okänt omfång.",
+        "These are the {} episodes published more than once in the \
+         indexed magazines, with the time between the publications and \
+         whether a publication contained only a part of the episode. \
+         A publication known only from a note about previous \
+         publication has unknown extent.",
+    ),
+    ("{} år senare", "{} years later"),
+    ("omfång okänt", "extent unknown"),
+    ("delvis ({} av {} delar)", "partial ({} of {} parts)"),
+    // Members.
+    ("Logga in", "Log in"),
+    (
+        "Logga in för att hålla reda på dina tidningar och lästa episoder.",
+        "Log in to keep track of your issues and read episodes.",
+    ),
+    (
+        "Medlemmar med konto kan logga in för att hålla reda på vilka \
+         tidningar de har och vilka episoder de har läst. \
+         Konton skapas av den som driver indexet.",
+        "Members with an account can log in to keep track of which issues \
+         they have and which episodes they have read. \
+         Accounts are created by whoever runs the index.",
+    ),
+    ("Användarnamn", "User name"),
+    ("Lösenord", "Password"),
+    (
+        "Fel användarnamn eller lösenord.",
+        "Wrong user name or password.",
+    ),
+    ("Inloggad som {}.", "Logged in as {}."),
+    ("Logga ut", "Log out"),
+    ("Min samling", "My collection"),
+    (
+        "Dina tidningar och lästa episoder.",
+        "Your issues and read episodes.",
+    ),
+    (
+        "Du har {} av {} indexerade tidningar. \
+         Se <a href=\"/my/missing\">tidningar som saknas</a>. \
+         Markera tidningar som ägda eller lästa på respektive tidnings \
+         sida.",
+        "You have {} of {} indexed issues. \
+         See <a href=\"/my/missing\">missing issues</a>. \
+         Mark issues as owned or read on the page of each issue.",
+    ),
+    ("Export och import", "Export and import"),
+    (
+        "Du kan <a href=\"/my/owned.csv\">ladda ner din samling som \
+         csv</a>, med kolumnerna år, nummer och skick.  \
+         Dubbelnummer skrivs som t.ex. 25-26.  \
+         Samma format kan importeras, tidningar som redan finns i \
+         samlingen får skicket uppdaterat.",
+        "You can <a href=\"/my/owned.csv\">download your collection as \
+         csv</a>, with the columns year, number and condition.  \
+         Double issues are written as e.g. 25-26.  \
+         The same format can be imported, issues already in the \
+         collection get their condition updated.",
+    ),
+    ("Importera", "Import"),
+    ("Tidningar per årgång", "Issues per year"),
+    ("Ägda", "Owned"),
+    ("Lästa episoder", "Read episodes"),
+    ("Serie", "Comic"),
+    ("Lästa", "Read"),
+    ("Import av samling", "Collection import"),
+    (
+        "Resultat av import av ägda tidningar.",
+        "Result of importing owned issues.",
+    ),
+    (
+        "{} tidningar markerades som ägda. \
+         Tillbaka till <a href=\"/my\">din samling</a>.",
+        "{} issues were marked as owned. \
+         Back to <a href=\"/my\">your collection</a>.",
+    ),
+    (
+        "Rader som inte kunde importeras",
+        "Rows that could not be imported",
+    ),
+    ("Saknade tidningar", "Missing issues"),
+    (
+        "Indexerade tidningar som saknas i din samling.",
+        "Indexed issues missing from your collection.",
+    ),
+    (
+        "Här listas de indexerade tidningar som du inte har markerat som \
+         ägda.  Tillbaka till <a href=\"/my\">din samling</a>.",
+        "These are the indexed issues you have not marked as owned.  \
+         Back to <a href=\"/my\">your collection</a>.",
+    ),
+    ("Olästa episoder av {}", "Unread episodes of {}"),
+    (
+        "Episoder av {} som du inte har läst.",
+        "Episodes of {} that you have not read.",
+    ),
+    (
+        "Här listas de {} episoder av <a href=\"/titles/{}\">{}</a> som du \
+         inte har markerat som lästa.  \
+         Tillbaka till <a href=\"/my\">din samling</a>.",
+        "These are the {} episodes of <a href=\"/titles/{}\">{}</a> that \
+         you have not marked as read.  \
+         Back to <a href=\"/my\">your collection</a>.",
+    ),
+    ("Markera som läst", "Mark as read"),
+    // Changes and admin.
+    ("Ändringar", "Changes"),
+    (
+        "Ändringar i indexet vid de senaste inläsningarna av data.",
+        "Changes to the index in the latest data imports.",
+    ),
+    (
+        "Här listas de senaste inläsningarna av data till indexet, med \
+         antalet nya, ändrade och borttagna tidningar, serier, episoder \
+         och referenser.  Välj en inläsning för att se alla ändringar.",
+        "These are the latest data imports to the index, with the number \
+         of new, changed and removed issues, comics, episodes and \
+         references.  Choose an import to see all changes.",
+    ),
+    ("Inläsning", "Import"),
+    ("Inläsningar", "Imports"),
+    ("Källa", "Source"),
+    ("Inga ändringar.", "No changes."),
+    ("Ändringar #{}", "Changes #{}"),
+    (
+        "Ändringar i indexet vid en inläsning av data.",
+        "Changes to the index in a data import.",
+    ),
+    (
+        "Inläsning från {} {}, med {} ändringar.",
+        "Import from {} {}, with {} changes.",
+    ),
+    ("Alla inläsningar", "All imports"),
+    ("Typ", "Type"),
+    ("Vad", "What"),
+    ("Ändring", "Change"),
+    ("Fält", "Fields"),
+    ("… och {} ändringar till.", "… and {} more changes."),
+    ("tidningar", "issues"),
+    ("serier", "comics"),
+    ("episoder", "episodes"),
+    ("referenser", "references"),
+    ("ny", "new"),
+    ("ändrad", "changed"),
+    ("borttagen", "removed"),
+    ("nya", "new"),
+    ("ändrade", "changed"),
+    ("borttagna", "removed"),
+    ("Redigera Fantomen {}/{}", "Edit Fantomen {}/{}"),
+    (
+        "Redigera innehållet i ett nummer.",
+        "Edit the contents of an issue.",
+    ),
+    (
+        "Varje publicering i numret (serie, artikel eller omslag) har en \
+         egen rad.  Fyll i den tomma raden sist för att lägga till en \
+         publicering, eller välj ingen typ för att ta bort en.  När du \
+         sparar läses numret in i databasen och skrivs tillbaka till \
+         <code>{}.data</code>, där resten av filen lämnas orörd.",
+        "Each publication in the issue (comic, article or cover) has a \
+         row of its own.  Fill in the empty last row to add a \
+         publication, or choose no kind to remove one.  When you save, \
+         the issue is read into the database and written back to \
+         <code>{}.data</code>, where the rest of the file is left \
+         untouched.",
+    ),
+    (
+        "Det går också att <a href=\"/admin/{}/{}/xml\">redigera \
+         numret som xml</a>.",
+        "You can also <a href=\"/admin/{}/{}/xml\">edit the issue as \
+         xml</a>.",
+    ),
+    (
+        "Här redigeras numret som xml, i samma format som i \
+         <code>{}.data</code>.  För vanliga ändringar finns \
+         <a href=\"/admin/{}/{}\">formuläret</a>.  När du sparar läses \
+         numret in i databasen och skrivs tillbaka till datafilen, där \
+         resten av filen lämnas orörd.",
+        "Here the issue is edited as xml, in the same format as in \
+         <code>{}.data</code>.  For ordinary changes, there is \
+         <a href=\"/admin/{}/{}\">the form</a>.  When you save, the \
+         issue is read into the database and written back to the data \
+         file, where the rest of the file is left untouched.",
+    ),
+    ("Artikel", "Article"),
+    ("Omslag", "Cover"),
+    ("Xml", "Xml"),
+    ("(ingen)", "(none)"),
+    ("Företeelse", "Thing"),
+    ("Fantom", "Phantom"),
+    ("Sidor", "Pages"),
+    ("Ordning", "Order"),
+    ("Episod / underrubrik", "Episode / subtitle"),
+    ("Del", "Part"),
+    ("Delens namn", "Name of part"),
+    ("Bästa placering", "Best placement"),
+    ("Upphovspersoner", "Creators"),
+    ("Övrig xml", "Other xml"),
+    (
+        "Numret finns inte i datafilen och läggs till.",
+        "The issue is not in the data file and will be added.",
+    ),
+    (
+        "Tillbaka till <a href=\"/{}/{}\">numret</a>.",
+        "Back to <a href=\"/{}/{}\">the issue</a>.",
+    ),
+    ("Spara", "Save"),
+    (
+        "Numret har ändrats i datafilen sedan du började redigera. \
+         Kontrollera dina ändringar och spara igen.",
+        "The issue has changed in the data file since you started \
+         editing. Check your changes and save again.",
+    ),
+    (
+        "Historik över inläsningar av data och omslagsbilder.",
+        "History of imports of data and cover images.",
+    ),
+    (
+        "De senaste körningarna av <code>read-files</code>, \
+         <code>fetch-covers</code> och <code>creators</code> samt \
+         redigeringar på webben, med \
+         inlästa årgångar, antal borttagna poster, hämtade omslag och \
+         eventuella fel.  Se även <a href=\"/changes\">ändringarna</a> i \
+         indexet.",
+        "The latest runs of <code>read-files</code>, \
+         <code>fetch-covers</code> and <code>creators</code> and edits \
+         on the web, with the years \
+         read, number of removed records, fetched covers and any \
+         errors.  See also the <a href=\"/changes\">changes</a> to the \
+         index.",
+    ),
+    ("Start", "Start"),
+    ("Status", "Status"),
+    ("Detaljer", "Details"),
+    ("Klar", "Done"),
+    ("på {} s", "in {} s"),
+    ("Misslyckades", "Failed"),
+    ("Pågår eller avbruten", "Running or aborted"),
+    // Errors.
+    ("(Tekniskt fel.)", "(Technical error.)"),
+    ("Du hittar inte Fantomen …", "You don't find the Phantom …"),
+    ("… han hittar dig!", "… he finds you!"),
+    (
+        "Antingen kan du gå tillbaks till \
+         <a href=\"/\">fantomenindexets förstasida</a> och leta, \
+         eller så kan du helt enkelt gå ut i djungeln och ropa efter \
+         <a href=\"rasmus@krats.se\">rasmus@krats.se</a>.",
+        "Either you can go back to \
+         <a href=\"/\">the Phantom index front page</a> and search, \
+         or you can simply go out in the jungle and call for \
+         <a href=\"rasmus@krats.se\">rasmus@krats.se</a>.",
+    ),
+    ("Servern är överlastad", "The server is overloaded"),
+    (
+        "Fantomen vilar först då fred råder i världen. \
+         Den här webbservern verkar dock behöva lite vila just nu. \
+         Försök gärna igen om ett litet tag.",
+        "The Phantom rests only when there is peace in the world. \
+         This web server seems to need some rest right now, though. \
+         Please try again in a little while.",
+    ),
+    ("Något gick snett", "Something went wrong"),
+    (
+        "Något gick snett. \
+         Detta missöde är noterat i webserverns krönika. \
+         Du kanske kan försöka igen om en stund? \
+         Eller gå tillbaks till \
+         <a href='/'>fantomenindexets förstasida</a>? \
+         Om det fortfarande verkar trasigt så får du gärna rapportera \
+         felet till \
+         <a href='mailto:rasmus@krats.se'>rasmus@krats.se</a>.",
+        "Something went wrong. \
+         This mishap is noted in the chronicles of the web server. \
+         Maybe you can try again in a while? \
+         Or go back to \
+         <a href='/'>the Phantom index front page</a>? \
+         If it still seems broken, please report the error to \
+         <a href='mailto:rasmus@krats.se'>rasmus@krats.se</a>.",
+    ),
+    ("Renderingsfel", "Rendering error"),
+    ("Databasfel", "Database error"),
+    ("Fel i datafilen", "Error in the data file"),
+    (
+        "Rad {}: Okänd tidning {}/{}",
+        "Line {}: Unknown issue {}/{}",
+    ),
+    (
+        "Rad {}: Fel antal fält i \"{}\"",
+        "Line {}: Wrong number of fields in \"{}\"",
+    ),
+    (
+        "Rad {}: Oavslutat citat i \"{}\"",
+        "Line {}: Unterminated quote in \"{}\"",
+    ),
+    (
+        "Rad {}: Felaktigt år i \"{}\"",
+        "Line {}: Bad year in \"{}\"",
+    ),
+    (
+        "Rad {}: Felaktigt nummer i \"{}\"",
+        "Line {}: Bad issue number in \"{}\"",
+    ),
+    ("Samarbeten för {}", "Collaborations of {}"),
+    (
+        "Serieskapare som har medverkat i samma episoder som {} i den \
+         svenska Fantomentidningen.",
+        "Creators who worked on the same episodes as {} in the Swedish \
+         Phantom magazine.",
+    ),
+    (
+        "Serieskapare som har medverkat i samma episoder som \
+         <a href=\"/who/{}\">{}</a>, av totalt {} episoder. Hela nätverket \
+         av samarbeten finns även som \
+         <a href=\"/who/collaborations.graphml\">GraphML</a> och \
+         <a href=\"/who/collaborations.json\">JSON</a>.",
+        "Creators who worked on the same episodes as \
+         <a href=\"/who/{}\">{}</a>, out of {} episodes in total. The \
+         full network of collaborations is also available as \
+         <a href=\"/who/collaborations.graphml\">GraphML</a> and \
+         <a href=\"/who/collaborations.json\">JSON</a>.",
+    ),
+    (
+        "{} har inte delat någon episod med någon annan.",
+        "{} has not shared any episode with anyone else.",
+    ),
+    ("Roller", "Roles"),
+    ("{} med {} ({})", "{} with {} ({})"),
+    (
+        "Se även <a href=\"/who/{}/collaborators\">vilka {} har \
+         samarbetat med</a>.",
+        "See also <a href=\"/who/{}/collaborators\">who {} has worked \
+         with</a>.",
+    ),
+    ("Del av:", "Part of:"),
+    ("Omfattar:", "Includes:"),
+    ("Se även:", "See also:"),
+    ("Även kallad:", "Also known as:"),
+    ("Första förekomst:", "First appearance:"),
+    ("Person", "Character"),
+    ("Plats", "Place"),
+    ("Organisation", "Organisation"),
+    ("Föremål", "Object"),
+    ("Jämför", "Compare"),
+    (
+        "Jämför två serier, serieskapare eller företeelser i den svenska \
+         Fantomentidningen.",
+        "Compare two comics, creators or things in the Swedish Phantom \
+         magazine.",
+    ),
+    (
+        "Jämför två serier, serieskapare, fantomer eller andra företeelser. \
+         Ange dem som <code>t:</code>, <code>p:</code>, <code>f:</code> \
+         eller <code>k:</code> följt av adressnamnet, till exempel \
+         <code>f:21</code> eller <code>p:lee-falk</code>.",
+        "Compare two comics, creators, phantoms or other things. Give them \
+         as <code>t:</code>, <code>p:</code>, <code>f:</code> or \
+         <code>k:</code> followed by the name in the address, such as \
+         <code>f:21</code> or <code>p:lee-falk</code>.",
+    ),
+    ("Den ena", "One"),
+    ("Den andra", "The other"),
+    (
+        "{} har {} episoder och {} har {}. {} episoder är gemensamma.",
+        "{} has {} episodes and {} has {}. {} episodes are shared.",
+    ),
+    ("Gemensamma", "Shared"),
+    ("Gemensamma episoder", "Shared episodes"),
+    ("Bara {}", "Only {}"),
+    ("först i", "first in"),
+    ("Episoder per år", "Episodes per year"),
+    (
+        "Du kan också <a href=\"/compare\">jämföra</a> två serier, \
+         serieskapare eller företeelser.",
+        "You can also <a href=\"/compare\">compare</a> two comics, \
+         creators or things.",
+    ),
+    ("Tidslinje", "Timeline"),
+    ("omslag", "covers"),
+    (
+        "Hela indexet kan <a href=\"/export.zip\">laddas ner</a> som \
+         csv-filer, för den som vill göra egna sammanställningar.",
+        "The whole index can be <a href=\"/export.zip\">downloaded</a> \
+         as csv files, for anyone who wants to make their own \
+         compilations.",
+    ),
+];
@@ -0,0 +1,351 @@
+//! Translation of the web ui.
+//!
+//! Texts are written in Swedish in the templates and code, and that
+//! Swedish text is used as the key when looking up a translation in
+//! a catalog (like gettext does).  A text missing in the catalog is
+//! shown in Swedish.  This also makes it possible to translate texts
+//! from the database, such as role and language names.
+use crate::templates::ToHtml;
+use chrono::{Datelike, NaiveDate};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::LazyLock;
+
+mod en;
+
+/// A language for the web ui.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    Sv,
+    En,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::Sv, Lang::En];
+
+    /// The language code, as used in `lang` attributes and urls.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Sv => "sv",
+            Lang::En => "en",
+        }
+    }
+
+    /// The locale, as used in Open Graph metadata.
+    pub fn locale(self) -> &'static str {
+        match self {
+            Lang::Sv => "sv_SE",
+            Lang::En => "en_GB",
+        }
+    }
+
+    /// Get a language from a code, ignoring any region part.
+    pub fn from_code(code: &str) -> Option<Lang> {
+        let primary = code.split(['-', '_']).next()?.trim();
+        Lang::ALL
+            .into_iter()
+            .find(|lang| primary.eq_ignore_ascii_case(lang.code()))
+    }
+
+    /// The preferred supported language of an `Accept-Language` header.
+    pub fn from_accept(header: &str) -> Option<Lang> {
+        let mut best: Option<(Lang, f32)> = None;
+        for item in header.split(',') {
+            let mut parts = item.split(';');
+            let Some(lang) = parts.next().and_then(Lang::from_code) else {
+                continue;
+            };
+            let q = parts
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if q > 0.0 && best.is_none_or(|(_, b)| q > b) {
+                best = Some((lang, q));
+            }
+        }
+        best.map(|(lang, _)| lang)
+    }
+
+    /// The other language, for linking to it.
+    pub fn other(self) -> Lang {
+        match self {
+            Lang::Sv => Lang::En,
+            Lang::En => Lang::Sv,
+        }
+    }
+
+    /// The name of this language, in the language itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lang::Sv => "Svenska",
+            Lang::En => "English",
+        }
+    }
+
+    /// Translate a (Swedish) text to this language.
+    pub fn t(self, sv: &str) -> &str {
+        match self {
+            Lang::Sv => sv,
+            Lang::En => EN.get(sv).copied().unwrap_or(sv),
+        }
+    }
+
+    /// A translated message with html markup, to be filled with `arg`.
+    ///
+    /// Each `{}` in the message is replaced by an argument.  The
+    /// message itself is written as is, but the arguments are escaped.
+    pub fn msg<'a>(self, sv: &'static str) -> Msg<'a> {
+        Msg {
+            text: self.t(sv),
+            args: Vec::new(),
+        }
+    }
+
+    /// A translated plain text, with each `{}` replaced by an argument.
+    pub fn text(
+        self,
+        sv: &'static str,
+        args: &[&dyn fmt::Display],
+    ) -> String {
+        let mut result = String::new();
+        let mut args = args.iter();
+        for (i, part) in self.t(sv).split("{}").enumerate() {
+            if i > 0
+                && let Some(arg) = args.next()
+            {
+                result.push_str(&arg.to_string());
+            }
+            result.push_str(part);
+        }
+        result
+    }
+
+    /// Render something that has a language-dependent html form.
+    pub fn html<T: ToHtmlIn + ?Sized>(self, item: &T) -> InLang<'_, T> {
+        InLang(self, item)
+    }
+
+    /// An ordinal number, such as "2:a" or "2nd".
+    pub fn ordinal(self, n: i64) -> Ordinal {
+        Ordinal(self, n)
+    }
+
+    /// A date with weekday and month names, such as "måndag den 8
+    /// januari 1968" or "Monday, 8 January 1968".
+    pub fn date(self, date: NaiveDate) -> LongDate {
+        LongDate(self, date)
+    }
+}
+
+static EN: LazyLock<HashMap<&str, &str>> =
+    LazyLock::new(|| en::CATALOG.iter().copied().collect());
+
+/// Something that is rendered differently depending on language.
+pub trait ToHtmlIn {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Something with a language-dependent form, in a given language.
+pub struct InLang<'a, T: ?Sized>(Lang, &'a T);
+
+impl<T: ToHtmlIn + ?Sized> ToHtml for InLang<'_, T> {
+    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.1.to_html_in(self.0, out)
+    }
+}
+
+/// A translated message with html markup and escaped arguments.
+pub struct Msg<'a> {
+    text: &'a str,
+    args: Vec<&'a dyn ToHtml>,
+}
+
+impl<'a> Msg<'a> {
+    #[must_use]
+    pub fn arg(mut self, arg: &'a dyn ToHtml) -> Self {
+        self.args.push(arg);
+        self
+    }
+}
+
+impl ToHtml for Msg<'_> {
+    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+        let mut args = self.args.iter();
+        for (i, part) in self.text.split("{}").enumerate() {
+            if i > 0
+                && let Some(arg) = args.next()
+            {
+                arg.to_html(out)?;
+            }
+            out.write_all(part.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Ordinal(Lang, i64);
+
+impl fmt::Display for Ordinal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Ordinal(lang, n) = *self;
+        let suffix = match lang {
+            Lang::Sv => match (n % 10, n % 100) {
+                (1 | 2, 11 | 12) => ":e",
+                (1 | 2, _) => ":a",
+                _ => ":e",
+            },
+            Lang::En => match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            },
+        };
+        write!(f, "{n}{suffix}")
+    }
+}
+
+pub struct LongDate(Lang, NaiveDate);
+
+impl fmt::Display for LongDate {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        let LongDate(lang, date) = self;
+        let weekday = date.weekday().num_days_from_monday() as usize;
+        let month = date.month0() as usize;
+        match lang {
+            Lang::Sv => write!(
+                out,
+                "{} den {} {} {}",
+                SV_WEEKDAYS[weekday],
+                date.day(),
+                SV_MONTHS[month],
+                date.year(),
+            ),
+            Lang::En => write!(
+                out,
+                "{}, {} {} {}",
+                EN_WEEKDAYS[weekday],
+                date.day(),
+                EN_MONTHS[month],
+                date.year(),
+            ),
+        }
+    }
+}
+
+static SV_WEEKDAYS: [&str; 7] = [
+    "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag", "söndag",
+];
+static SV_MONTHS: [&str; 12] = [
+    "januari",
+    "februari",
+    "mars",
+    "april",
+    "maj",
+    "juni",
+    "juli",
+    "augusti",
+    "september",
+    "oktober",
+    "november",
+    "december",
+];
+static EN_WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+static EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+#[cfg(test)]
+mod test {
+    use super::{Lang, en};
+    use chrono::NaiveDate;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn catalog_keys_unique() {
+        let mut seen = BTreeSet::new();
+        for (sv, _) in en::CATALOG {
+            assert!(seen.insert(sv), "Duplicate catalog key {sv:?}");
+        }
+    }
+
+    #[test]
+    fn catalog_placeholders_match() {
+        for (sv, en) in en::CATALOG {
+            assert_eq!(
+                sv.matches("{}").count(),
+                en.matches("{}").count(),
+                "Placeholders differ for {sv:?}",
+            );
+        }
+    }
+
+    #[test]
+    fn accept_language() {
+        assert_eq!(Lang::from_accept("en-US,en;q=0.9"), Some(Lang::En));
+        assert_eq!(
+            Lang::from_accept("de, en;q=0.5, sv;q=0.8"),
+            Some(Lang::Sv)
+        );
+        assert_eq!(Lang::from_accept("sv-SE"), Some(Lang::Sv));
+        assert_eq!(Lang::from_accept("fi, de;q=0.3"), None);
+        assert_eq!(Lang::from_accept("en;q=0"), None);
+    }
+
+    #[test]
+    fn ordinals() {
+        let ords = |lang: Lang| {
+            [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 101, 111]
+                .map(|n| lang.ordinal(n).to_string())
+                .join(" ")
+        };
+        assert_eq!(
+            ords(Lang::Sv),
+            "1:a 2:a 3:e 4:e 11:e 12:e 13:e 21:a 22:a 23:e 101:a 111:e",
+        );
+        assert_eq!(
+            ords(Lang::En),
+            "1st 2nd 3rd 4th 11th 12th 13th 21st 22nd 23rd 101st 111th",
+        );
+    }
+
+    #[test]
+    fn dates() {
+        let date = NaiveDate::from_ymd_opt(1968, 1, 8).unwrap();
+        assert_eq!(
+            Lang::Sv.date(date).to_string(),
+            "måndag den 8 januari 1968"
+        );
+        assert_eq!(Lang::En.date(date).to_string(), "Monday, 8 January 1968");
+    }
+
+    #[test]
+    fn text_args() {
+        assert_eq!(
+            Lang::En.text("Innehållet i Fantomen {}.", &[&"3/1970"]),
+            "Contents of Fantomen 3/1970.",
+        );
+    }
+}
@@ -1,17 +1,24 @@
 #![recursion_limit = "128"]
 #![forbid(unsafe_code)]
 
-mod checkstrips;
+mod changes;
+mod check;
 mod count_pages;
+mod creators;
 mod dbopt;
+mod export;
 mod fetchcovers;
+mod i18n;
 mod listissues;
 mod models;
 mod readfiles;
+mod runs;
 mod schema;
 mod server;
+mod users;
+mod yearfile;
 
-use crate::checkstrips::check_strips;
+use crate::check::check;
 use crate::listissues::list_issues;
 use anyhow::Result;
 use clap::Parser;
@@ -35,15 +42,50 @@ enum Fanrs {
     /// Fetch missing cover images from phantomwiki.
     FetchCovers(fetchcovers::Args),
 
-    /// Check assumptions about which titles has daystrips and/or sunday pages.
+    /// Check the consistency of the data.
     ///
-    /// The code contains hardcoded lists of which comics has
-    /// daystrips or sunday pages, this routine checks that those
-    /// assumptions are correct with the database values.
-    CheckStrips(DbOpt),
+    /// Checks that the daystrip and sunday pages flags in the title
+    /// metadata are correct with the episode data, that there are no
+    /// titles without episodes or creators without aliases, and that
+    /// references to creators and titles are valid.
+    #[command(alias = "check-strips")]
+    Check(DbOpt),
 
     /// Calculate number of pages from a yearbook toc.
     CountPages(count_pages::CountPages),
+
+    /// Manage creators and their aliases.
+    ///
+    /// Changes are made both in the database and in the
+    /// `extra-people.data` file.
+    Creators(creators::Args),
+
+    /// Show changes to the data made by recent imports.
+    ///
+    /// Without `--run`, list the latest import runs with the number
+    /// of changes per table.  With `--run`, show each change of that
+    /// run with the old and new values.
+    Changes(changes::Args),
+
+    /// Show the history of import runs.
+    ///
+    /// Each run of `read-files` and `fetch-covers`, and each edit on
+    /// the web, is recorded with its arguments, the years read, counts
+    /// of cleared rows and fetched covers, and any error.
+    Runs(runs::Args),
+
+    /// Manage local user accounts.
+    ///
+    /// Users can log in to the web server to keep track of the
+    /// issues they own and the episodes they have read.
+    Users(users::Args),
+
+    /// Export the index as csv files or an ods spreadsheet.
+    ///
+    /// The export has tables of issues, publications, episodes,
+    /// creators with their aliases, and refkeys with the episodes and
+    /// articles they are assigned to.
+    Export(export::Args),
 }
 
 impl Fanrs {
@@ -55,10 +97,13 @@ impl Fanrs {
             }
             Fanrs::RunServer(args) => args.run().await,
             Fanrs::FetchCovers(args) => args.run().await,
-            Fanrs::CheckStrips(db) => {
-                check_strips(&mut db.get_db().await?).await
-            }
+            Fanrs::Check(db) => check(&mut db.get_db().await?).await,
             Fanrs::CountPages(args) => args.run(),
+            Fanrs::Creators(args) => args.run().await,
+            Fanrs::Changes(args) => args.run().await,
+            Fanrs::Runs(args) => args.run().await,
+            Fanrs::Users(args) => args.run().await,
+            Fanrs::Export(args) => args.run().await,
         }
     }
 }
@@ -0,0 +1,160 @@
+//! The audit log of changes to the indexed data.
+//!
+//! Changes are recorded by triggers in the database, see the
+//! `changes` migration, so this module only reads them.
+use super::ImportRun;
+use crate::schema::change_fields::dsl as cf;
+use crate::schema::changes::dsl as c;
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A recorded change to one row.
+#[derive(Debug)]
+pub struct Change {
+    pub table_name: String,
+    pub row_id: i32,
+    /// One of `insert`, `update` or `delete`.
+    pub action: String,
+    pub label: Option<String>,
+    pub fields: Vec<ChangedField>,
+}
+
+/// The old and new value of a field in a change.
+///
+/// For an insert, `old` is `None`, for a delete `new` is `None`.
+#[derive(Debug, Queryable)]
+pub struct ChangedField {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl Change {
+    /// Load the changes of an import run, with the changed fields.
+    ///
+    /// At most `limit` changes are loaded, in the order they were made.
+    pub async fn for_run(
+        run: &ImportRun,
+        limit: i64,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Vec<Change>, Error> {
+        let changes = c::changes
+            .select((c::id, c::table_name, c::row_id, c::action, c::label))
+            .filter(c::run_id.eq(run.id))
+            .order(c::id)
+            .limit(limit)
+            .load::<(i32, String, i32, String, Option<String>)>(db)
+            .await?;
+        let ids = changes.iter().map(|c| c.0).collect::<Vec<_>>();
+        let mut fields = BTreeMap::<i32, Vec<ChangedField>>::new();
+        for (id, field) in cf::change_fields
+            .select((
+                cf::change_id,
+                (cf::field, cf::old_value, cf::new_value),
+            ))
+            .filter(cf::change_id.eq_any(&ids))
+            .order((cf::change_id, cf::field))
+            .load::<(i32, ChangedField)>(db)
+            .await?
+        {
+            fields.entry(id).or_default().push(field);
+        }
+        Ok(changes
+            .into_iter()
+            .map(|(id, table_name, row_id, action, label)| Change {
+                table_name,
+                row_id,
+                action,
+                label,
+                fields: fields.remove(&id).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// A description of the changed row, such as `episodes #17 Name`.
+    pub fn what(&self) -> String {
+        match &self.label {
+            Some(label) => {
+                format!("{} #{} {}", self.table_name, self.row_id, label)
+            }
+            None => format!("{} #{}", self.table_name, self.row_id),
+        }
+    }
+}
+
+impl fmt::Display for ChangedField {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => {
+                write!(out, "{}: {old:?} → {new:?}", self.field)
+            }
+            (None, Some(new)) => write!(out, "{}: {new:?}", self.field),
+            (Some(old), None) => write!(out, "{}: {old:?} → –", self.field),
+            (None, None) => write!(out, "{}", self.field),
+        }
+    }
+}
+
+/// Number of changes per table and action in an import run.
+#[derive(Debug, Default)]
+pub struct ChangeSummary {
+    /// (table, action) → count, e.g. `("episodes", "update") → 3`.
+    pub counts: BTreeMap<(String, String), i64>,
+}
+
+impl ChangeSummary {
+    pub async fn for_run(
+        run: &ImportRun,
+        db: &mut AsyncPgConnection,
+    ) -> Result<ChangeSummary, Error> {
+        let counts = c::changes
+            .group_by((c::table_name, c::action))
+            .select((c::table_name, c::action, count_star()))
+            .filter(c::run_id.eq(run.id))
+            .load::<(String, String, i64)>(db)
+            .await?
+            .into_iter()
+            .map(|(table, action, n)| ((table, action), n))
+            .collect();
+        Ok(ChangeSummary { counts })
+    }
+
+    pub fn total(&self) -> i64 {
+        self.counts.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+impl fmt::Display for ChangeSummary {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return out.write_str("no changes");
+        }
+        for (i, ((table, action), n)) in self.counts.iter().enumerate() {
+            if i > 0 {
+                out.write_str(", ")?;
+            }
+            write!(out, "{n} {table} {action}")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn summary_display() {
+    let mut summary = ChangeSummary::default();
+    assert_eq!(summary.to_string(), "no changes");
+    summary.counts.insert(("titles".into(), "insert".into()), 2);
+    summary
+        .counts
+        .insert(("episodes".into(), "delete".into()), 1);
+    assert_eq!(summary.to_string(), "1 episodes delete, 2 titles insert");
+    assert_eq!(summary.total(), 3);
+}
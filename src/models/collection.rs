@@ -0,0 +1,295 @@
+//! Import and export of owned issues as csv.
+//!
+//! The format is one issue per row, with the columns `year`, `nr`
+//! and an optional `condition`.  Double issues are written as in the
+//! index, e.g. `25-26`.  A header row is optional on import.
+use super::issue::ParseError;
+use super::{Issue, IssueRef, User};
+use crate::i18n::{Lang, ToHtmlIn};
+use crate::schema::issues::dsl as i;
+use crate::schema::owned_issues::dsl as oi;
+use crate::templates::ToHtml;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+
+/// The csv header row.
+const HEADER: &str = "year,nr,condition";
+
+/// Export the issues owned by a user as csv, in publication order.
+pub async fn export_owned(
+    user: &User,
+    db: &mut AsyncPgConnection,
+) -> Result<String, Error> {
+    let owned = oi::owned_issues
+        .inner_join(i::issues)
+        .select(((i::year, (i::number, i::number_str)), oi::condition))
+        .filter(oi::user_id.eq(user.id))
+        .order(i::magic)
+        .load::<(IssueRef, Option<String>)>(db)
+        .await?;
+    let mut csv = format!("{HEADER}\n");
+    for (issue, condition) in owned {
+        let _ = writeln!(
+            csv,
+            "{},{},{}",
+            issue.year,
+            issue.number,
+            Quoted(condition.as_deref().unwrap_or_default()),
+        );
+    }
+    Ok(csv)
+}
+
+/// Import owned issues from csv, adding or updating the condition.
+///
+/// Rows that cannot be parsed or do not match any known issue are
+/// returned in the report rather than aborting the import.
+pub async fn import_owned(
+    user: &User,
+    csv: &str,
+    db: &mut AsyncPgConnection,
+) -> Result<ImportReport, Error> {
+    let mut report = ImportReport::default();
+    for (line, text, row) in parse_rows(csv) {
+        match row {
+            Ok((issue, condition)) => {
+                match Issue::load(issue.year, &issue.number, db).await? {
+                    Some(found) => {
+                        user.set_owned(found.id, condition.as_deref(), db)
+                            .await?;
+                        report.imported += 1;
+                    }
+                    None => report.bad.push(BadRow {
+                        line,
+                        text: text.into(),
+                        reason: BadReason::UnknownIssue(issue),
+                    }),
+                }
+            }
+            Err(reason) => report.bad.push(BadRow {
+                line,
+                text: text.into(),
+                reason,
+            }),
+        }
+    }
+    Ok(report)
+}
+
+/// The result of a csv import.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub bad: Vec<BadRow>,
+}
+
+/// A row that could not be imported.
+#[derive(Debug)]
+pub struct BadRow {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// The row as given.
+    pub text: String,
+    pub reason: BadReason,
+}
+
+/// Why a row could not be imported.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BadReason {
+    /// The row does not match any known issue.
+    UnknownIssue(IssueRef),
+    /// The row does not have two or three fields.
+    FieldCount,
+    /// A quoted field has no end quote.
+    OpenQuote,
+    BadYear,
+    BadNumber,
+}
+
+impl BadRow {
+    /// The report of this row, as a message in the catalog and its
+    /// arguments.
+    fn message(&self) -> (&'static str, Vec<String>) {
+        let line = self.line.to_string();
+        let text = self.text.clone();
+        match &self.reason {
+            BadReason::UnknownIssue(issue) => (
+                "Rad {}: Okänd tidning {}/{}",
+                vec![line, issue.number.to_string(), issue.year.to_string()],
+            ),
+            BadReason::FieldCount => {
+                ("Rad {}: Fel antal fält i \"{}\"", vec![line, text])
+            }
+            BadReason::OpenQuote => {
+                ("Rad {}: Oavslutat citat i \"{}\"", vec![line, text])
+            }
+            BadReason::BadYear => {
+                ("Rad {}: Felaktigt år i \"{}\"", vec![line, text])
+            }
+            BadReason::BadNumber => {
+                ("Rad {}: Felaktigt nummer i \"{}\"", vec![line, text])
+            }
+        }
+    }
+}
+
+impl ToHtmlIn for BadRow {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
+        let (message, args) = self.message();
+        args.iter()
+            .fold(lang.msg(message), |msg, arg| msg.arg(arg))
+            .to_html(out)
+    }
+}
+
+/// The report in English, for the command line.
+impl fmt::Display for BadRow {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        let (message, args) = self.message();
+        let args = args.iter().map(|a| a as &dyn fmt::Display);
+        out.write_str(&Lang::En.text(message, &args.collect::<Vec<_>>()))
+    }
+}
+
+type Row = Result<(IssueRef, Option<String>), BadReason>;
+
+/// Parse csv rows, skipping a header row and empty lines.
+///
+/// Each row is given with its line number and text.
+fn parse_rows(csv: &str) -> Vec<(usize, &str, Row)> {
+    csv.lines()
+        .enumerate()
+        .map(|(n, line)| (n + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .filter(|(n, line)| !(*n == 1 && line.starts_with("year")))
+        .map(|(n, line)| (n, line, parse_row(line)))
+        .collect()
+}
+
+fn parse_row(line: &str) -> Row {
+    let fields = split_fields(line)?;
+    let (year, nr, condition) = match fields.as_slice() {
+        [year, nr] => (year, nr, None),
+        [year, nr, condition] => {
+            let condition = condition.trim();
+            (year, nr, (!condition.is_empty()).then(|| condition.into()))
+        }
+        _ => return Err(BadReason::FieldCount),
+    };
+    let issue = format!("{} {}", year.trim(), nr.trim())
+        .parse::<IssueRef>()
+        .map_err(|e| match e {
+            ParseError::BadYear => BadReason::BadYear,
+            _ => BadReason::BadNumber,
+        })?;
+    Ok((issue, condition))
+}
+
+/// Split a csv line into fields, handling double-quoted fields.
+fn split_fields(line: &str) -> Result<Vec<String>, BadReason> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(BadReason::OpenQuote);
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+/// A csv field, quoted if needed.
+struct Quoted<'a>(&'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.contains([',', '"', '\n']) {
+            write!(out, "\"{}\"", self.0.replace('"', "\"\""))
+        } else {
+            out.write_str(self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BadReason, BadRow, Quoted, parse_rows};
+
+    #[test]
+    fn parse_with_header_and_double_issue() {
+        let rows = parse_rows(
+            "year,nr,condition\n1970,3,Fint\n\n1970,4-5,\"Bra, men sliten\"\n",
+        );
+        let rows = rows
+            .into_iter()
+            .map(|(n, _, row)| {
+                let (issue, condition) = row.unwrap();
+                (n, format!("{}/{}", issue.number, issue.year), condition)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                (2, "3/1970".into(), Some("Fint".into())),
+                (4, "4-5/1970".into(), Some("Bra, men sliten".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_bad_rows() {
+        let rows = parse_rows("1970,x\n19x0,3,\n1970\n1995,1,\"open\n");
+        let reasons = rows
+            .into_iter()
+            .map(|(_, _, row)| row.unwrap_err())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            [
+                BadReason::BadNumber,
+                BadReason::BadYear,
+                BadReason::FieldCount,
+                BadReason::OpenQuote,
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_row_in_english() {
+        let bad = BadRow {
+            line: 3,
+            text: "1970".into(),
+            reason: BadReason::FieldCount,
+        };
+        assert_eq!(
+            bad.to_string(),
+            "Line 3: Wrong number of fields in \"1970\"",
+        );
+    }
+
+    #[test]
+    fn quote_roundtrip() {
+        let text = "Sliten, \"ok\"";
+        let line = format!("1970,3,{}", Quoted(text));
+        let (_, _, row) = parse_rows(&line).pop().unwrap();
+        assert_eq!(row.unwrap().1.as_deref(), Some(text));
+    }
+}
@@ -11,7 +11,7 @@ use std::io::{self, Write};
 
 /// In most cases, this struct will hold the id and name from
 /// `creator_aliases` together with the slug from creators.
-#[derive(Debug, Queryable, Eq, PartialEq)]
+#[derive(Clone, Debug, Queryable, Eq, PartialEq)]
 pub struct Creator {
     pub id: i32,
     pub name: String,
@@ -0,0 +1,138 @@
+use crate::i18n::Lang;
+use crate::schema::creator_info::dsl as ci;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Biographical data and external ids for a creator.
+///
+/// All fields are optional, and stored only for creators where some
+/// data is known.
+#[derive(Debug, Default, Queryable, PartialEq, Eq)]
+pub struct CreatorInfo {
+    pub born: Option<i16>,
+    pub died: Option<i16>,
+    pub nationality: Option<String>,
+    pub bio: Option<String>,
+    /// A Wikidata item id, such as `Q1236574`.
+    pub wikidata: Option<String>,
+    /// Path of a Lambiek Comiclopedia page, such as `f/falk_lee`.
+    pub lambiek: Option<String>,
+    /// Page name in the phantomwiki, such as `Lee_Falk`.
+    pub phantomwiki: Option<String>,
+}
+
+impl CreatorInfo {
+    pub async fn load(
+        creator_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<CreatorInfo, Error> {
+        Ok(ci::creator_info
+            .select((
+                ci::born,
+                ci::died,
+                ci::nationality,
+                ci::bio,
+                ci::wikidata,
+                ci::lambiek,
+                ci::phantomwiki,
+            ))
+            .filter(ci::creator_id.eq(creator_id))
+            .first(db)
+            .await
+            .optional()?
+            .unwrap_or_default())
+    }
+
+    /// Store this info for a creator, replacing any existing info.
+    pub async fn save(
+        &self,
+        creator_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        if self.is_empty() {
+            diesel::delete(ci::creator_info)
+                .filter(ci::creator_id.eq(creator_id))
+                .execute(db)
+                .await?;
+            return Ok(());
+        }
+        diesel::insert_into(ci::creator_info)
+            .values((
+                ci::creator_id.eq(creator_id),
+                ci::born.eq(self.born),
+                ci::died.eq(self.died),
+                ci::nationality.eq(&self.nationality),
+                ci::bio.eq(&self.bio),
+                ci::wikidata.eq(&self.wikidata),
+                ci::lambiek.eq(&self.lambiek),
+                ci::phantomwiki.eq(&self.phantomwiki),
+            ))
+            .on_conflict(ci::creator_id)
+            .do_update()
+            .set((
+                ci::born.eq(excluded(ci::born)),
+                ci::died.eq(excluded(ci::died)),
+                ci::nationality.eq(excluded(ci::nationality)),
+                ci::bio.eq(excluded(ci::bio)),
+                ci::wikidata.eq(excluded(ci::wikidata)),
+                ci::lambiek.eq(excluded(ci::lambiek)),
+                ci::phantomwiki.eq(excluded(ci::phantomwiki)),
+            ))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == CreatorInfo::default()
+    }
+
+    /// Life span, such as "1911–1999" or "född 1948".
+    pub fn years(&self, lang: Lang) -> Option<String> {
+        match (self.born, self.died) {
+            (Some(born), Some(died)) => Some(format!("{born}–{died}")),
+            (Some(born), None) => Some(lang.text("född {}", &[&born])),
+            (None, Some(died)) => Some(lang.text("död {}", &[&died])),
+            (None, None) => None,
+        }
+    }
+
+    /// External pages about the creator, as (name, url) pairs.
+    pub fn links(&self) -> Vec<(&'static str, String)> {
+        let mut links = Vec::new();
+        if let Some(id) = &self.wikidata {
+            links.push((
+                "Wikidata",
+                format!("https://www.wikidata.org/wiki/{id}"),
+            ));
+        }
+        if let Some(id) = &self.lambiek {
+            links.push((
+                "Lambiek Comiclopedia",
+                format!("https://www.lambiek.net/artists/{id}.htm"),
+            ));
+        }
+        if let Some(id) = &self.phantomwiki {
+            links.push((
+                "Phantom wiki",
+                format!("https://www.phantomwiki.org/{id}"),
+            ));
+        }
+        links
+    }
+}
+
+#[test]
+fn test_years() {
+    let info = |born, died| CreatorInfo {
+        born,
+        died,
+        ..CreatorInfo::default()
+    };
+    let years = |info: CreatorInfo| info.years(Lang::Sv);
+    assert_eq!(years(info(Some(1911), Some(1999))).unwrap(), "1911–1999");
+    assert_eq!(years(info(Some(1948), None)).unwrap(), "född 1948");
+    assert_eq!(years(info(None, None)), None);
+}
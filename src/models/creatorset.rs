@@ -1,4 +1,5 @@
 use super::{Article, Creator, Episode};
+use crate::i18n::{Lang, ToHtmlIn};
 use crate::templates::ToHtml;
 use diesel::prelude::*;
 use diesel::result::Error;
@@ -6,13 +7,21 @@ use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use std::collections::BTreeMap;
 use std::io::{self, Write};
 
+/// Creators of an episode or article, grouped by role.
+///
+/// The groups are kept in role order, each with the code and label of
+/// the role.
 #[derive(Debug)]
-pub struct CreatorSet(BTreeMap<String, Vec<Creator>>);
+pub struct CreatorSet(Vec<RoleGroup>);
 
-impl CreatorSet {
-    pub const MAIN_ROLES: &'static [&'static str] =
-        &["by", "bild", "text", "orig", "ink"];
+#[derive(Debug)]
+struct RoleGroup {
+    role: String,
+    label: String,
+    creators: Vec<Creator>,
+}
 
+impl CreatorSet {
     pub async fn for_episode(
         episode: &Episode,
         db: &mut AsyncPgConnection,
@@ -20,16 +29,53 @@ impl CreatorSet {
         use crate::schema::creator_aliases::dsl as ca;
         use crate::schema::creators::dsl as c;
         use crate::schema::episodes_by::dsl as cp;
+        use crate::schema::roles::dsl as r;
         let c_columns = (c::id, ca::name, c::slug);
         let data = cp::episodes_by
             .inner_join(ca::creator_aliases.inner_join(c::creators))
-            .select((cp::role, c_columns))
+            .inner_join(r::roles)
+            .select((r::code, r::label, c_columns))
             .filter(cp::episode_id.eq(episode.id))
-            .load::<(String, Creator)>(db)
+            .order((r::ord, cp::id))
+            .load::<(String, String, Creator)>(db)
             .await?;
         Ok(CreatorSet::from_data(data))
     }
 
+    /// Creators for many episodes at once, by episode id.
+    ///
+    /// Creators are given by their main name rather than the alias
+    /// used in each episode.
+    pub async fn for_episodes(
+        episodes: &[i32],
+        db: &mut AsyncPgConnection,
+    ) -> Result<BTreeMap<i32, CreatorSet>, Error> {
+        use crate::schema::creator_aliases::dsl as ca;
+        use crate::schema::creators::dsl as c;
+        use crate::schema::episodes_by::dsl as cp;
+        use crate::schema::roles::dsl as r;
+        let c_columns = (c::id, c::name, c::slug);
+        let data = cp::episodes_by
+            .inner_join(ca::creator_aliases.inner_join(c::creators))
+            .inner_join(r::roles)
+            .select((cp::episode_id, r::code, r::label, c_columns))
+            .filter(cp::episode_id.eq_any(episodes))
+            .order((cp::episode_id, r::ord, cp::id))
+            .load::<(i32, String, String, Creator)>(db)
+            .await?;
+        let mut result = BTreeMap::<i32, Vec<_>>::new();
+        for (episode, role, label, creator) in data {
+            result
+                .entry(episode)
+                .or_default()
+                .push((role, label, creator));
+        }
+        Ok(result
+            .into_iter()
+            .map(|(episode, data)| (episode, CreatorSet::from_data(data)))
+            .collect())
+    }
+
     pub async fn for_article(
         article: &Article,
         db: &mut AsyncPgConnection,
@@ -37,52 +83,60 @@ impl CreatorSet {
         use crate::schema::articles_by::dsl as ab;
         use crate::schema::creator_aliases::dsl as ca;
         use crate::schema::creators::dsl as c;
+        use crate::schema::roles::dsl as r;
         let c_columns = (c::id, ca::name, c::slug);
         let data = ab::articles_by
             .inner_join(ca::creator_aliases.inner_join(c::creators))
-            .select((ab::role, c_columns))
+            .inner_join(r::roles)
+            .select((r::code, r::label, c_columns))
             .filter(ab::article_id.eq(article.id))
-            .load::<(String, Creator)>(db)
+            .order((r::ord, ab::id))
+            .load::<(String, String, Creator)>(db)
             .await?;
         Ok(CreatorSet::from_data(data))
     }
 
-    fn from_data(data: Vec<(String, Creator)>) -> CreatorSet {
-        let mut result: BTreeMap<String, Vec<Creator>> = BTreeMap::new();
-        for (role, creator) in data {
-            result.entry(role).or_default().push(creator);
+    /// Group data that is ordered by role.
+    fn from_data(data: Vec<(String, String, Creator)>) -> CreatorSet {
+        let mut result: Vec<RoleGroup> = Vec::new();
+        for (role, label, creator) in data {
+            match result.last_mut() {
+                Some(last) if last.role == role => {
+                    last.creators.push(creator);
+                }
+                _ => result.push(RoleGroup {
+                    role,
+                    label,
+                    creators: vec![creator],
+                }),
+            }
         }
         CreatorSet(result)
     }
+
+    /// The creators for each role, by role code.
+    pub fn by_role(&self) -> impl Iterator<Item = (&str, &[Creator])> {
+        self.0
+            .iter()
+            .map(|g| (g.role.as_str(), g.creators.as_slice()))
+    }
 }
 
-impl ToHtml for CreatorSet {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for CreatorSet {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         if !self.0.is_empty() {
             write!(out, "<p class='info creators'>")?;
-            let roles = [
-                ("by", "Av"),
-                ("text", "Text:"),
-                ("bild", "Bild:"),
-                ("ink", "Tush:"),
-                ("color", "Färgläggning:"),
-                ("orig", "Efter en originalberättelse av:"),
-                ("redax", "Redaktion:"),
-                ("xlat", "Översättning:"),
-                ("textning", "Textsättning:"),
-            ];
-            for (code, desc) in roles {
-                if let Some((last, rest)) =
-                    self.0.get(code).and_then(|s| s.split_last())
-                {
-                    write!(out, "{desc} ")?;
+            for group in &self.0 {
+                if let Some((last, rest)) = group.creators.split_last() {
+                    lang.t(&group.label).to_html(out)?;
+                    write!(out, " ")?;
                     if let Some((first, rest)) = rest.split_first() {
                         first.to_html(out)?;
                         for creator in rest {
                             write!(out, ", ")?;
                             creator.to_html(out)?;
                         }
-                        write!(out, " och ")?;
+                        write!(out, " {} ", lang.t("och"))?;
                     }
                     last.to_html(out)?;
                     write!(out, ". ")?;
@@ -1,11 +1,11 @@
-use super::{OtherMag, RefKey, Title};
+use super::{Language, OtherMag, RefKey, Title};
+use crate::i18n::{Lang, ToHtmlIn};
 use crate::schema::episodes;
 use crate::templates::ToHtml;
-use chrono::{Datelike, NaiveDate};
+use chrono::NaiveDate;
 use diesel::prelude::*;
 use diesel::result::Error;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
-use std::fmt;
 use std::io::{self, Write};
 use tracing::warn;
 
@@ -159,6 +159,16 @@ impl Episode {
             }
         }
     }
+    pub async fn load_orig_lang(
+        &self,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Option<Language>, Error> {
+        if let Some(code) = &self.orig_lang {
+            Language::get(code, db).await
+        } else {
+            Ok(None)
+        }
+    }
     pub async fn load_orig_mag(
         &self,
         db: &mut AsyncPgConnection,
@@ -176,16 +186,6 @@ pub struct OrigEpisode<'a> {
     episode: &'a str,
 }
 
-impl<'a> OrigEpisode<'a> {
-    pub fn langname(&self) -> &'a str {
-        match self.lang {
-            "fr" => "Franska",
-            "en" => "Engelska",
-            l => l,
-        }
-    }
-}
-
 impl ToHtml for OrigEpisode<'_> {
     fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
         write!(out, "<q lang='{}'>", self.lang)?;
@@ -208,57 +208,50 @@ impl OrigDates {
             sun: false,
         }
     }
-    pub fn kind(&self) -> &'static str {
+    pub fn range(from: NaiveDate, to: NaiveDate, sun: bool) -> Self {
+        OrigDates {
+            from,
+            to: Some(to),
+            sun,
+        }
+    }
+    pub fn kind(&self) -> OrigKind {
         if self.to.is_none() {
-            "Först publicerad"
+            OrigKind::First
         } else if self.sun {
-            "Söndagssidor"
+            OrigKind::Sundays
         } else {
-            "Dagstrippar"
+            OrigKind::Daily
         }
     }
 }
 
-impl ToHtml for OrigDates {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
-        match (self.from, self.to) {
-            (from, Some(to)) if from != to => {
-                write!(out, "{} - {}", SvDate(&from), SvDate(&to))
-            }
-            (date, _) => write!(out, "{}", SvDate(&date)),
+/// What kind of original publication some [`OrigDates`] refer to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrigKind {
+    First,
+    Daily,
+    Sundays,
+}
+
+impl OrigKind {
+    /// The (Swedish) label, to be translated.
+    pub fn label(self) -> &'static str {
+        match self {
+            OrigKind::First => "Först publicerad",
+            OrigKind::Daily => "Dagstrippar",
+            OrigKind::Sundays => "Söndagssidor",
         }
     }
 }
 
-struct SvDate<'a>(&'a NaiveDate);
-
-impl fmt::Display for SvDate<'_> {
-    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            out,
-            "{} den {} {} {}",
-            LONG_WEEKDAYS[self.0.weekday().num_days_from_monday() as usize],
-            self.0.day(),
-            LONG_MONTHS[(self.0.month() - 1) as usize],
-            self.0.year(),
-        )
+impl ToHtmlIn for OrigDates {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
+        match (self.from, self.to) {
+            (from, Some(to)) if from != to => {
+                write!(out, "{} - {}", lang.date(from), lang.date(to))
+            }
+            (date, _) => write!(out, "{}", lang.date(date)),
+        }
     }
 }
-
-static LONG_WEEKDAYS: [&str; 7] = [
-    "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag", "söndag",
-];
-static LONG_MONTHS: [&str; 12] = [
-    "januari",
-    "februari",
-    "mars",
-    "april",
-    "maj",
-    "juni",
-    "juli",
-    "augusti",
-    "september",
-    "oktober",
-    "november",
-    "december",
-];
@@ -0,0 +1,236 @@
+use crate::schema::import_run_counts::dsl as irc;
+use crate::schema::import_runs::dsl as ir;
+use chrono::{DateTime, TimeDelta, Utc};
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::sql_query;
+use diesel::sql_types::Text;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::fmt::{self, Write};
+
+/// A run of importing data, from `read-files`, `fetch-covers` or a
+/// web edit.
+///
+/// While a run is active on a connection, all changes to issues,
+/// titles, episodes and refkeys are recorded in the `changes` table
+/// (by database triggers) with the id of the run.  The years read,
+/// named counts (such as cleared rows or fetched covers) and any error
+/// are stored with the run as it progresses.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::import_runs)]
+pub struct ImportRun {
+    pub id: i32,
+    pub started: DateTime<Utc>,
+    /// What started the run, e.g. `read-files` or a web user.
+    pub source: String,
+    /// When the run finished, `None` while running or if aborted.
+    pub finished: Option<DateTime<Utc>>,
+    /// The command line arguments of the run.
+    pub args: String,
+    /// The years read so far.
+    pub years: Vec<i16>,
+    pub error: Option<String>,
+}
+
+impl ImportRun {
+    /// Create a new run and make it active on `db`.
+    pub async fn start(
+        source: &str,
+        args: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<ImportRun, Error> {
+        let run = diesel::insert_into(ir::import_runs)
+            .values((ir::source.eq(source), ir::args.eq(args)))
+            .returning(ImportRun::as_returning())
+            .get_result(db)
+            .await?;
+        set_active(&run.id.to_string(), db).await?;
+        Ok(run)
+    }
+
+    /// Record that the data for `year` has been read.
+    pub async fn add_year(
+        &self,
+        year: i16,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        diesel::update(ir::import_runs.filter(ir::id.eq(self.id)))
+            .set(ir::years.eq(ir::years.concat(vec![year])))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Add `n` to the count called `name` for this run.
+    ///
+    /// Zero counts are not stored.
+    pub async fn add_count(
+        &self,
+        name: &str,
+        n: usize,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        if n == 0 {
+            return Ok(());
+        }
+        diesel::insert_into(irc::import_run_counts)
+            .values((
+                irc::run_id.eq(self.id),
+                irc::name.eq(name),
+                irc::n.eq(i32::try_from(n).unwrap_or(i32::MAX)),
+            ))
+            .on_conflict((irc::run_id, irc::name))
+            .do_update()
+            .set(irc::n.eq(irc::n + excluded(irc::n)))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Mark the run as finished, with an error if it failed, and stop
+    /// recording changes for it on `db`.
+    pub async fn finish(
+        self,
+        error: Option<String>,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        diesel::update(ir::import_runs.filter(ir::id.eq(self.id)))
+            .set((ir::finished.eq(Utc::now()), ir::error.eq(error)))
+            .execute(db)
+            .await?;
+        set_active("", db).await
+    }
+
+    pub async fn get(
+        id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Option<ImportRun>, Error> {
+        ir::import_runs
+            .select(ImportRun::as_select())
+            .filter(ir::id.eq(id))
+            .first(db)
+            .await
+            .optional()
+    }
+
+    /// The latest runs, most recent first.
+    pub async fn latest(
+        limit: i64,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Vec<ImportRun>, Error> {
+        ir::import_runs
+            .select(ImportRun::as_select())
+            .order(ir::id.desc())
+            .limit(limit)
+            .load(db)
+            .await
+    }
+
+    /// The named counts of this run, in name order.
+    pub async fn counts(
+        &self,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Vec<(String, i32)>, Error> {
+        irc::import_run_counts
+            .select((irc::name, irc::n))
+            .filter(irc::run_id.eq(self.id))
+            .order(irc::name)
+            .load(db)
+            .await
+    }
+
+    pub fn duration(&self) -> Option<TimeDelta> {
+        self.finished.map(|finished| finished - self.started)
+    }
+
+    /// The source, without the username of web edits.
+    ///
+    /// This is what is shown on public pages, to not tell who the
+    /// admins are.
+    pub fn public_source(&self) -> &str {
+        if self.source.starts_with("web:") {
+            "web"
+        } else {
+            &self.source
+        }
+    }
+
+    /// The years read, with consecutive years as ranges.
+    pub fn years_text(&self) -> String {
+        let mut result = String::new();
+        let mut years = self.years.clone();
+        years.sort_unstable();
+        years.dedup();
+        let mut rest = &years[..];
+        while let [first, tail @ ..] = rest {
+            let n = tail
+                .iter()
+                .zip(1..)
+                .take_while(|(y, i)| **y == first + i)
+                .count();
+            if !result.is_empty() {
+                result.push_str(", ");
+            }
+            let _ = match n {
+                0 => write!(result, "{first}"),
+                n => write!(result, "{first}–{}", tail[n - 1]),
+            };
+            rest = &tail[n..];
+        }
+        result
+    }
+
+    pub fn status(&self) -> RunStatus {
+        match (&self.finished, &self.error) {
+            (_, Some(_)) => RunStatus::Failed,
+            (Some(_), None) => RunStatus::Ok,
+            (None, None) => RunStatus::Unfinished,
+        }
+    }
+}
+
+/// The outcome of an import run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    Ok,
+    Failed,
+    /// Still running, or aborted without recording an error.
+    Unfinished,
+}
+
+impl fmt::Display for RunStatus {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.write_str(match self {
+            RunStatus::Ok => "ok",
+            RunStatus::Failed => "failed",
+            RunStatus::Unfinished => "unfinished",
+        })
+    }
+}
+
+async fn set_active(
+    value: &str,
+    db: &mut AsyncPgConnection,
+) -> Result<(), Error> {
+    sql_query("select set_config('fanrs.import_run', $1, false)")
+        .bind::<Text, _>(value)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+#[test]
+fn years_as_ranges() {
+    let run = ImportRun {
+        id: 1,
+        started: Utc::now(),
+        source: "read-files".into(),
+        finished: None,
+        args: String::new(),
+        years: vec![1995, 1950, 1951, 1952, 1970, 1971],
+        error: None,
+    };
+    assert_eq!(run.years_text(), "1950–1952, 1970–1971, 1995");
+    assert_eq!(run.public_source(), "read-files");
+}
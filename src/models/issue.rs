@@ -1,5 +1,6 @@
 use super::price::Price;
 use crate::templates::ToHtml;
+use chrono::{Local, NaiveDate};
 use diesel::deserialize::{self, FromSql};
 use diesel::pg::Pg;
 use diesel::prelude::*;
@@ -22,6 +23,8 @@ pub struct Issue {
     pub cover_best: Option<i16>,
     pub magic: i16,
     pub ord: Option<i32>,
+    /// The release date, if known.
+    pub date: Option<NaiveDate>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -30,6 +33,18 @@ pub struct IssueRef {
     pub number: Nr,
 }
 
+/// The data of an issue in the data files, besides year and number.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IssueData {
+    pub ord: Option<i32>,
+    pub pages: Option<i16>,
+    pub price: Option<Price>,
+    /// Placement of the cover in the best covers list, if any.
+    pub cover_best: Option<i16>,
+    /// The release date, if known.
+    pub date: Option<NaiveDate>,
+}
+
 impl Issue {
     pub async fn get_or_create_ref(
         year: i16,
@@ -39,48 +54,42 @@ impl Issue {
         match Issue::load(year, &number, db).await? {
             Some(t) => Ok(t),
             None => {
-                Issue::create(year, number, None, None, None, None, db).await
+                Issue::create(year, number, &IssueData::default(), db).await
             }
         }
     }
     pub async fn get_or_create(
         year: i16,
         number: Nr,
-        ord: Option<i32>,
-        pages: Option<i16>,
-        price: Option<Price>,
-        cover_best: Option<i16>,
+        data: &IssueData,
         db: &mut AsyncPgConnection,
     ) -> Result<Issue, Error> {
         use crate::schema::issues::dsl;
         if let Some(mut t) = Issue::load(year, &number, db).await? {
-            if (t.cover_best != cover_best)
-                || (t.pages != pages)
-                || (t.price != price)
-                || (t.ord != ord)
-            {
-                t.cover_best = cover_best;
-                t.pages = pages;
-                t.price = price;
-                t.ord = ord;
+            if t.data() != *data {
+                t.cover_best = data.cover_best;
+                t.pages = data.pages;
+                t.price = data.price;
+                t.ord = data.ord;
+                t.date = data.date;
                 diesel::update(dsl::issues)
                     .filter(dsl::id.eq(t.id))
                     .set((
-                        dsl::cover_best.eq(cover_best),
-                        dsl::pages.eq(pages),
+                        dsl::cover_best.eq(t.cover_best),
+                        dsl::pages.eq(t.pages),
                         dsl::price.eq(&t.price),
                         dsl::ord.eq(t.ord),
+                        dsl::date.eq(t.date),
                     ))
                     .execute(db)
                     .await?;
             }
             Ok(t)
         } else {
-            Issue::create(year, number, ord, pages, price, cover_best, db)
-                .await
+            Issue::create(year, number, data, db).await
         }
     }
-    async fn load(
+    pub async fn load(
         year: i16,
         number: &Nr,
         db: &mut AsyncPgConnection,
@@ -97,10 +106,7 @@ impl Issue {
     async fn create(
         year: i16,
         number: Nr,
-        ord: Option<i32>,
-        pages: Option<i16>,
-        price: Option<Price>,
-        cover_best: Option<i16>,
+        data: &IssueData,
         db: &mut AsyncPgConnection,
     ) -> Result<Issue, Error> {
         use crate::schema::issues::dsl as i;
@@ -111,15 +117,26 @@ impl Issue {
                 i::year.eq(year),
                 i::number.eq(number.number),
                 i::number_str.eq(number.nr_str),
-                i::pages.eq(pages),
-                i::price.eq(price),
-                i::cover_best.eq(cover_best),
+                i::pages.eq(data.pages),
+                i::price.eq(data.price),
+                i::cover_best.eq(data.cover_best),
                 i::magic.eq(magic),
-                i::ord.eq(ord),
+                i::ord.eq(data.ord),
+                i::date.eq(data.date),
             ))
             .get_result(db)
             .await
     }
+    /// The data of this issue, as given in the data files.
+    pub fn data(&self) -> IssueData {
+        IssueData {
+            ord: self.ord,
+            pages: self.pages,
+            price: self.price,
+            cover_best: self.cover_best,
+            date: self.date,
+        }
+    }
     pub async fn clear(
         &self,
         db: &mut AsyncPgConnection,
@@ -134,6 +151,11 @@ impl Issue {
     pub fn cover_url(&self) -> String {
         format!("/c/f{}-{}.jpg", self.year, self.number)
     }
+    /// True for an announced issue that is not yet released.
+    pub fn is_upcoming(&self) -> bool {
+        self.date
+            .is_some_and(|date| date > Local::now().date_naive())
+    }
 }
 
 impl fmt::Display for Issue {
@@ -0,0 +1,54 @@
+use crate::schema::languages::dsl as l;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// A language of original episodes.
+///
+/// The known languages are kept in the `languages` table, and used in
+/// the `orig_lang` column of `episodes`.
+#[derive(Debug, Clone, Queryable, Selectable, PartialEq, Eq)]
+#[diesel(table_name = crate::schema::languages)]
+pub struct Language {
+    pub code: String,
+    /// The Swedish name of the language, e.g. "Engelska".
+    pub name: String,
+}
+
+impl Language {
+    pub async fn get(
+        code: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Option<Language>, Error> {
+        l::languages
+            .select(Language::as_select())
+            .filter(l::code.eq(code))
+            .first(db)
+            .await
+            .optional()
+    }
+
+    /// Get a language, creating it if it is not known.
+    ///
+    /// A created language has the code as name until a proper name
+    /// is added to the database.
+    pub async fn get_or_create(
+        code: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Language, Error> {
+        if let Some(lang) = Language::get(code, db).await? {
+            Ok(lang)
+        } else {
+            diesel::insert_into(l::languages)
+                .values((l::code.eq(code), l::name.eq(code)))
+                .returning(Language::as_returning())
+                .get_result(db)
+                .await
+        }
+    }
+
+    /// True if the language has no proper name.
+    pub fn is_unnamed(&self) -> bool {
+        self.name == self.code
+    }
+}
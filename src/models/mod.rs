@@ -1,31 +1,51 @@
 #![allow(proc_macro_derive_resolution_fallback)]
+use crate::i18n::{Lang, ToHtmlIn};
 use crate::templates::ToHtml;
-use std::fmt;
 use std::io::{self, Write};
 
 mod article;
+mod change;
+pub mod collection;
 mod creator;
 pub mod creator_contributions;
+mod creator_info;
 mod creatorset;
 mod episode;
+mod import_run;
 mod issue;
+mod language;
 mod other_mag;
 mod part;
 mod price;
 mod refkey;
+mod refkey_info;
+mod refkey_relations;
 mod refkeyset;
+mod role;
 mod title;
+mod title_info;
+mod user;
 
 pub use self::article::Article;
+pub use self::change::{Change, ChangeSummary};
 pub use self::creator::Creator;
+pub use self::creator_info::CreatorInfo;
 pub use self::creatorset::CreatorSet;
-pub use self::episode::Episode;
-pub use self::issue::{Issue, IssueRef, Nr};
+pub use self::episode::{Episode, OrigDates, OrigKind};
+pub use self::import_run::{ImportRun, RunStatus};
+pub use self::issue::{Issue, IssueData, IssueRef, Nr};
+pub use self::language::Language;
 pub use self::other_mag::OtherMag;
 pub use self::part::{Part, PartInIssue};
+pub use self::price::{Price, kpi_year};
 pub use self::refkey::{IdRefKey, RefKey};
+pub use self::refkey_info::RefKeyInfo;
+pub use self::refkey_relations::RefKeyRelations;
 pub use self::refkeyset::RefKeySet;
+pub use self::role::Role;
 pub use self::title::Title;
+pub use self::title_info::TitleInfo;
+pub use self::user::User;
 
 pub trait CloudItem: Ord {
     fn write_item(
@@ -75,15 +95,8 @@ impl YearNo {
         Self(year + 1 - 1950)
     }
 }
-impl fmt::Display for YearNo {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let n = self.0;
-        n.fmt(f)?;
-        if n == 1 || n == 2 || (n > 20 && (n % 10 == 1 || n % 10 == 2)) {
-            f.write_str(":a")?;
-        } else {
-            f.write_str(":e")?;
-        }
-        Ok(())
+impl ToHtmlIn for YearNo {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
+        write!(out, "{}", lang.ordinal(self.0.into()))
     }
 }
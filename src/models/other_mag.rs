@@ -2,6 +2,7 @@ use crate::schema::other_mags::dsl as om;
 use diesel::prelude::*;
 use diesel::result::Error;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use slug::slugify;
 
 #[derive(Debug, Queryable, PartialOrd, Ord, PartialEq, Eq)]
 pub struct OtherMag {
@@ -49,6 +50,11 @@ impl OtherMag {
                 .await
         }
     }
+
+    /// The slug of the magazine name, the same for all issues.
+    pub fn slug(&self) -> String {
+        slugify(&self.name)
+    }
 }
 
 use std::fmt::{self, Display};
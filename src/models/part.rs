@@ -1,4 +1,5 @@
 use super::{Episode, Issue, IssueRef};
+use crate::i18n::{Lang, ToHtmlIn};
 use crate::schema::episode_parts::dsl as ep;
 use crate::schema::publications::dsl as p;
 use crate::templates::ToHtml;
@@ -147,14 +148,14 @@ impl Part {
     }
 }
 
-impl ToHtml for Part {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for Part {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         if !self.is_part() {
             return Ok(());
         }
         write!(out, "<span class='part'>")?;
         if let Some(no) = self.no {
-            write!(out, "del {no}")?;
+            write!(out, "{}", lang.text("del {}", &[&no]))?;
             if self.name.is_some() {
                 write!(out, ": ")?;
             }
@@ -169,12 +170,12 @@ impl ToHtml for Part {
 #[derive(Debug, Queryable)]
 pub struct PartInIssue(pub IssueRef, pub Part, pub Option<i16>);
 
-impl ToHtml for PartInIssue {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for PartInIssue {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         self.0.to_html(out)?;
         if self.1.is_part() {
             write!(out, " (")?;
-            self.1.to_html(out)?;
+            self.1.to_html_in(lang, out)?;
             write!(out, ")")?;
         }
         Ok(())
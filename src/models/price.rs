@@ -7,12 +7,56 @@ use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-#[derive(Debug, FromSqlRow, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, FromSqlRow, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Price {
     // Internal representation is a number of öre.
     price: i32,
 }
 
+impl Price {
+    pub fn from_ore(price: i32) -> Price {
+        Price { price }
+    }
+    pub fn as_ore(self) -> i32 {
+        self.price
+    }
+    /// The price in kronor, as a float.
+    pub fn as_kr(self) -> f64 {
+        f64::from(self.price) / 100.
+    }
+
+    /// This price, paid in `year`, expressed in the money value of
+    /// `to_year` according to the consumer price index.
+    ///
+    /// Years after the end of the table are treated as the last
+    /// year in the table.  Years before the table gives `None`.
+    pub fn adjusted(self, year: i16, to_year: i16) -> Option<Price> {
+        let price = f64::from(self.price) * kpi(to_year)? / kpi(year)?;
+        Some(Price {
+            price: price.round() as i32,
+        })
+    }
+
+    /// The price per page, for a magazine with `pages` pages.
+    pub fn per_page(self, pages: i16) -> Option<Price> {
+        (pages > 0).then(|| Price {
+            price: (f64::from(self.price) / f64::from(pages)).round() as i32,
+        })
+    }
+}
+
+/// The latest year of the bundled consumer price index.
+pub fn kpi_year() -> i16 {
+    KPI[KPI.len() - 1].0
+}
+
+fn kpi(year: i16) -> Option<f64> {
+    let first = KPI[0].0;
+    let year = year.min(kpi_year());
+    let i = usize::try_from(year.checked_sub(first)?).ok()?;
+    Some(KPI[i].1)
+}
+
 impl FromSql<Integer, Pg> for Price {
     fn from_sql(
         bytes: <Pg as Backend>::RawValue<'_>,
@@ -72,3 +116,107 @@ impl From<ParseIntError> for BadPrice {
         BadPrice
     }
 }
+
+/// Swedish consumer price index (KPI, yearly average, 1980 = 100).
+///
+/// Based on the tables from Statistics Sweden (SCB).  Must contain
+/// every year from the first to the last, in order.
+static KPI: [(i16, f64); 76] = [
+    (1950, 17.9),
+    (1951, 20.7),
+    (1952, 22.2),
+    (1953, 22.5),
+    (1954, 22.6),
+    (1955, 23.3),
+    (1956, 24.4),
+    (1957, 25.4),
+    (1958, 26.5),
+    (1959, 26.7),
+    (1960, 27.8),
+    (1961, 28.5),
+    (1962, 29.8),
+    (1963, 30.7),
+    (1964, 31.7),
+    (1965, 33.3),
+    (1966, 35.5),
+    (1967, 37.0),
+    (1968, 37.7),
+    (1969, 38.7),
+    (1970, 41.4),
+    (1971, 44.5),
+    (1972, 47.1),
+    (1973, 50.3),
+    (1974, 55.3),
+    (1975, 60.7),
+    (1976, 67.0),
+    (1977, 74.6),
+    (1978, 82.0),
+    (1979, 88.0),
+    (1980, 100.0),
+    (1981, 112.1),
+    (1982, 121.8),
+    (1983, 132.6),
+    (1984, 143.2),
+    (1985, 153.8),
+    (1986, 160.3),
+    (1987, 167.1),
+    (1988, 176.8),
+    (1989, 188.1),
+    (1990, 207.9),
+    (1991, 227.2),
+    (1992, 232.3),
+    (1993, 243.0),
+    (1994, 248.4),
+    (1995, 254.6),
+    (1996, 255.9),
+    (1997, 257.2),
+    (1998, 257.0),
+    (1999, 258.3),
+    (2000, 260.7),
+    (2001, 266.7),
+    (2002, 272.7),
+    (2003, 278.0),
+    (2004, 279.2),
+    (2005, 280.7),
+    (2006, 284.8),
+    (2007, 291.1),
+    (2008, 301.2),
+    (2009, 299.8),
+    (2010, 303.5),
+    (2011, 311.3),
+    (2012, 314.0),
+    (2013, 314.0),
+    (2014, 313.3),
+    (2015, 313.2),
+    (2016, 316.3),
+    (2017, 321.9),
+    (2018, 328.3),
+    (2019, 334.1),
+    (2020, 335.7),
+    (2021, 343.8),
+    (2022, 372.7),
+    (2023, 404.4),
+    (2024, 415.7),
+    (2025, 419.4),
+];
+
+#[test]
+fn test_kpi_contiguous() {
+    assert!(KPI.windows(2).all(|w| w[0].0 + 1 == w[1].0));
+}
+
+#[test]
+fn test_adjusted() {
+    let price = Price::from_ore(150);
+    assert_eq!(price.adjusted(1980, 1980), Some(price));
+    assert_eq!(price.adjusted(1970, 1980), Some(Price::from_ore(362)));
+    assert_eq!(price.adjusted(1949, 1980), None);
+    assert_eq!(price.adjusted(2025, 2099), Some(price));
+}
+
+#[test]
+fn test_per_page() {
+    let price = Price::from_ore(4950);
+    assert_eq!(price.per_page(68), Some(Price::from_ore(73)));
+    assert_eq!(price.per_page(0), None);
+}
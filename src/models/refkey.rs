@@ -1,7 +1,9 @@
 use super::{Cloud, CloudItem};
+use crate::i18n::{Lang, ToHtmlIn};
 use crate::schema;
 use crate::schema::episode_refkeys::dsl as er;
 use crate::schema::episodes::dsl as e;
+use crate::schema::refkey_aliases::dsl as ra;
 use crate::schema::refkeys::dsl as r;
 use crate::templates::ToHtml;
 use diesel::deserialize::{self, Queryable};
@@ -60,8 +62,11 @@ impl IdRefKey {
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum RefKey {
-    /// slug
-    Fa(String),
+    /// Name, slug
+    ///
+    /// Numbered phantoms get their name from the slug, other names
+    /// (such as Julie for 17j) are stored as the title of the refkey.
+    Fa(String, String),
     /// Actual key and slug
     Key(String, String),
     /// Name, slug
@@ -78,18 +83,18 @@ impl RefKey {
     pub const WHO_ID: i16 = 3;
     pub const TITLE_ID: i16 = 4;
 
+    /// A phantom by slug, as given in the data files.
+    ///
+    /// The name is the slug until the refkey is read from the database.
     pub fn fa(slug: &str) -> RefKey {
-        RefKey::Fa(slug.into())
+        RefKey::Fa(slug.into(), slug.into())
     }
+    /// A key by name.
+    ///
+    /// Keys that are aliases for other refkeys are resolved when
+    /// stored, see [`RefKey::get_or_create_id`].
     pub fn key(name: &str) -> RefKey {
-        match name {
-            "Julie" => RefKey::fa("17j"),
-            // To be replaced with both 21.1 and 21.2 somehow
-            "Kit & Heloise" => Self::fa("22"),
-            "Kit" => Self::fa("22k"),
-            "Heloise" => Self::fa("22h"),
-            _ => RefKey::Key(name.into(), slugify(name)),
-        }
+        RefKey::Key(name.into(), slugify(name))
     }
     pub fn who(name: &str) -> RefKey {
         RefKey::Who(name.into(), slugify(name))
@@ -98,12 +103,27 @@ impl RefKey {
         RefKey::Title(name.into(), slugify(name))
     }
 
+    /// Get the id of this refkey, creating it if needed.
+    ///
+    /// A key that is an alias, as defined in `extra-refkeys.data`,
+    /// gives the id of the refkey it is an alias for.
     pub async fn get_or_create_id(
         &self,
         db: &mut AsyncPgConnection,
     ) -> Result<i32, Error> {
+        if let RefKey::Key(name, _) = self
+            && let Some(id) = ra::refkey_aliases
+                .select(ra::refkey_id)
+                .filter(ra::kind.eq(RefKey::KEY_ID))
+                .filter(ra::name.eq(name))
+                .first(db)
+                .await
+                .optional()?
+        {
+            return Ok(id);
+        }
         let (kind, title, slug) = match self {
-            RefKey::Fa(s) => (RefKey::FA_ID, self.name(), s.clone()),
+            RefKey::Fa(_, s) => (RefKey::FA_ID, self.name(), s.clone()),
             RefKey::Key(t, s) => (RefKey::KEY_ID, t.clone(), s.clone()),
             RefKey::Who(n, _s) => {
                 use super::Creator;
@@ -113,15 +133,16 @@ impl RefKey {
             }
             RefKey::Title(n, s) => (RefKey::TITLE_ID, n.clone(), s.clone()),
         };
-        match r::refkeys
+        let mut existing = r::refkeys
             .select(r::id)
             .filter(r::kind.eq(kind))
-            .filter(r::title.eq(&title))
             .filter(r::slug.eq(&slug))
-            .first(db)
-            .await
-            .optional()?
-        {
+            .into_boxed();
+        // A phantom is known by slug only, the name is from the database.
+        if kind != RefKey::FA_ID {
+            existing = existing.filter(r::title.eq(&title));
+        }
+        match existing.first(db).await.optional()? {
             Some(id) => Ok(id),
             None => {
                 diesel::insert_into(r::refkeys)
@@ -137,9 +158,19 @@ impl RefKey {
         }
     }
 
+    /// The kind of refkey, as stored in the database.
+    pub fn kind_id(&self) -> i16 {
+        match self {
+            RefKey::Fa(..) => RefKey::FA_ID,
+            RefKey::Key(..) => RefKey::KEY_ID,
+            RefKey::Who(..) => RefKey::WHO_ID,
+            RefKey::Title(..) => RefKey::TITLE_ID,
+        }
+    }
+
     pub fn url(&self) -> String {
         match self {
-            RefKey::Fa(slug) => format!("/fa/{slug}"),
+            RefKey::Fa(_, slug) => format!("/fa/{slug}"),
             RefKey::Key(_, slug) => format!("/what/{slug}"),
             RefKey::Who(_, slug) => format!("/who/{slug}"),
             RefKey::Title(_, slug) => format!("/titles/{slug}"),
@@ -147,7 +178,7 @@ impl RefKey {
     }
     pub fn slug(&self) -> &str {
         match self {
-            RefKey::Fa(slug)
+            RefKey::Fa(_, slug)
             | RefKey::Key(_, slug)
             | RefKey::Who(_, slug)
             | RefKey::Title(_, slug) => slug,
@@ -155,14 +186,19 @@ impl RefKey {
     }
 
     pub fn name(&self) -> String {
+        self.name_in(Lang::Sv)
+    }
+
+    pub fn name_in(&self, lang: Lang) -> String {
         match self {
-            RefKey::Fa(slug) => match slug.as_ref() {
-                "0" => "Kapten Walker".into(),
-                "1" => "Den 1:a Fantomen".into(),
-                "17j" => "Julie".into(),
-                "22h" => "Heloise".into(),
-                "22k" => "Kit".into(),
-                slug => format!("Den {slug}:e Fantomen"),
+            RefKey::Fa(name, slug) => match (slug.as_ref(), slug.parse()) {
+                ("0", _) => lang.t("Kapten Walker").into(),
+                (_, Ok(n)) => match lang {
+                    Lang::Sv if n == 1 => "Den 1:a Fantomen".into(),
+                    Lang::Sv => format!("Den {n}:e Fantomen"),
+                    Lang::En => format!("The {} Phantom", lang.ordinal(n)),
+                },
+                (_, Err(_)) => name.clone(),
             },
             RefKey::Key(name, _)
             | RefKey::Who(name, _)
@@ -172,12 +208,10 @@ impl RefKey {
 
     pub fn short(&self) -> String {
         match self {
-            RefKey::Fa(slug) => match slug.as_ref() {
+            RefKey::Fa(name, slug) => match slug.as_ref() {
                 "0" => "Kapten Walker".into(),
-                "17j" => "Julie".into(),
-                "22h" => "Heloise".into(),
-                "22k" => "Kit".into(),
-                slug => slug.to_string(),
+                slug if slug.parse::<i64>().is_ok() => slug.into(),
+                _ => name.clone(),
             },
             RefKey::Key(name, _)
             | RefKey::Who(name, _)
@@ -227,7 +261,7 @@ impl Queryable<schema::refkeys::SqlType, Pg> for IdRefKey {
             id: row.0,
             refkey: match (row.1, row.2, row.3) {
                 (RefKey::KEY_ID, t, s) => RefKey::Key(t, s),
-                (RefKey::FA_ID, _, s) => RefKey::Fa(s),
+                (RefKey::FA_ID, t, s) => RefKey::Fa(t, s),
                 (RefKey::WHO_ID, t, s) => RefKey::Who(t, s),
                 (RefKey::TITLE_ID, t, s) => RefKey::Title(t, s),
                 // TODO: Return an error instead of panic!
@@ -246,7 +280,7 @@ impl Queryable<(SmallInt, Text, Text), Pg> for RefKey {
     fn build(row: Self::Row) -> deserialize::Result<Self> {
         Ok(match row {
             (RefKey::KEY_ID, t, s) => RefKey::Key(t, s),
-            (RefKey::FA_ID, _, s) => RefKey::Fa(s),
+            (RefKey::FA_ID, t, s) => RefKey::Fa(t, s),
             (RefKey::WHO_ID, t, s) => RefKey::Who(t, s),
             (RefKey::TITLE_ID, t, s) => RefKey::Title(t, s),
             (k, t, s) => return Err(Box::new(BadRefKey(k, t, s))),
@@ -268,8 +302,8 @@ impl fmt::Debug for BadRefKey {
     }
 }
 
-impl ToHtml for RefKey {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for RefKey {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         out.write_all(b"<a href=\"")?;
         self.url().to_html(out)?;
         write!(
@@ -282,7 +316,7 @@ impl ToHtml for RefKey {
                 RefKey::Title(..) => "title",
             }
         )?;
-        self.name().to_html(out)?;
+        self.name_in(lang).to_html(out)?;
         out.write_all(b"</a>")
     }
 }
@@ -0,0 +1,87 @@
+use crate::schema::refkey_info::dsl as ri;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Encyclopedic data about a refkey.
+///
+/// This is maintained in `extra-refkeys.data` rather than in the
+/// yearly data files.
+#[derive(Debug, Default, Queryable, Selectable, PartialEq, Eq)]
+#[diesel(table_name = crate::schema::refkey_info)]
+pub struct RefKeyInfo {
+    /// What kind of thing the refkey is, one of [`RefKeyInfo::KINDS`].
+    pub kind: Option<String>,
+    pub description: Option<String>,
+}
+
+impl RefKeyInfo {
+    /// The known kinds, with their Swedish nouns.
+    pub const KINDS: &[(&str, &str)] = &[
+        ("character", "Person"),
+        ("place", "Plats"),
+        ("organisation", "Organisation"),
+        ("object", "Föremål"),
+    ];
+
+    pub async fn load(
+        refkey_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<RefKeyInfo, Error> {
+        Ok(ri::refkey_info
+            .select(RefKeyInfo::as_select())
+            .filter(ri::refkey_id.eq(refkey_id))
+            .first(db)
+            .await
+            .optional()?
+            .unwrap_or_default())
+    }
+
+    /// Store this info for a refkey, replacing any existing info.
+    pub async fn save(
+        &self,
+        refkey_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        if self.is_empty() {
+            diesel::delete(ri::refkey_info)
+                .filter(ri::refkey_id.eq(refkey_id))
+                .execute(db)
+                .await?;
+            return Ok(());
+        }
+        diesel::insert_into(ri::refkey_info)
+            .values((
+                ri::refkey_id.eq(refkey_id),
+                ri::kind.eq(&self.kind),
+                ri::description.eq(&self.description),
+            ))
+            .on_conflict(ri::refkey_id)
+            .do_update()
+            .set((
+                ri::kind.eq(excluded(ri::kind)),
+                ri::description.eq(excluded(ri::description)),
+            ))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == RefKeyInfo::default()
+    }
+
+    /// The Swedish noun for the kind of this refkey, if known.
+    pub fn kind_noun(&self) -> Option<&'static str> {
+        self.kind.as_deref().and_then(RefKeyInfo::noun)
+    }
+
+    /// The Swedish noun for a kind of refkey.
+    pub fn noun(kind: &str) -> Option<&'static str> {
+        RefKeyInfo::KINDS
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, noun)| *noun)
+    }
+}
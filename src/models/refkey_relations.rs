@@ -0,0 +1,106 @@
+use super::RefKey;
+use crate::schema::refkey_aliases::dsl as ra;
+use crate::schema::refkey_parents::dsl as rp;
+use crate::schema::refkey_see_also::dsl as rs;
+use crate::schema::refkeys::dsl as r;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::BTreeSet;
+
+/// Relations from a refkey to other refkeys.
+///
+/// This is maintained in `extra-refkeys.data` rather than in the
+/// yearly data files.
+#[derive(Debug, Default)]
+pub struct RefKeyRelations {
+    pub parents: Vec<RefKey>,
+    pub children: Vec<RefKey>,
+    /// Related refkeys, in either direction.
+    pub see_also: Vec<RefKey>,
+    /// Other names of the refkey in the data files.
+    pub aliases: Vec<String>,
+}
+
+impl RefKeyRelations {
+    pub async fn load(
+        refkey_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<RefKeyRelations, Error> {
+        let parents = rp::refkey_parents
+            .select(rp::parent_id)
+            .filter(rp::refkey_id.eq(refkey_id))
+            .load(db)
+            .await?;
+        let children = rp::refkey_parents
+            .select(rp::refkey_id)
+            .filter(rp::parent_id.eq(refkey_id))
+            .load(db)
+            .await?;
+        let mut see_also = rs::refkey_see_also
+            .select(rs::other_id)
+            .filter(rs::refkey_id.eq(refkey_id))
+            .load::<i32>(db)
+            .await?;
+        see_also.extend(
+            rs::refkey_see_also
+                .select(rs::refkey_id)
+                .filter(rs::other_id.eq(refkey_id))
+                .load::<i32>(db)
+                .await?,
+        );
+        let aliases = ra::refkey_aliases
+            .select(ra::name.assume_not_null())
+            .filter(ra::refkey_id.eq(refkey_id))
+            .filter(ra::name.is_not_null())
+            .order(ra::name)
+            .load(db)
+            .await?;
+        Ok(RefKeyRelations {
+            parents: load_refkeys(parents, db).await?,
+            children: load_refkeys(children, db).await?,
+            see_also: load_refkeys(see_also, db).await?,
+            aliases,
+        })
+    }
+
+    /// True if there are no related refkeys.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+            && self.children.is_empty()
+            && self.see_also.is_empty()
+    }
+
+    /// The ids of a refkey and all refkeys below it in the hierarchy.
+    pub async fn with_descendants(
+        refkey_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Vec<i32>, Error> {
+        let mut all = BTreeSet::from([refkey_id]);
+        let mut current = vec![refkey_id];
+        while !current.is_empty() {
+            current = rp::refkey_parents
+                .select(rp::refkey_id)
+                .filter(rp::parent_id.eq_any(&current))
+                .load::<i32>(db)
+                .await?
+                .into_iter()
+                .filter(|id| all.insert(*id))
+                .collect();
+        }
+        Ok(all.into_iter().collect())
+    }
+}
+
+async fn load_refkeys(
+    ids: Vec<i32>,
+    db: &mut AsyncPgConnection,
+) -> Result<Vec<RefKey>, Error> {
+    let mut refkeys = r::refkeys
+        .select((r::kind, r::title, r::slug))
+        .filter(r::id.eq_any(ids))
+        .load::<RefKey>(db)
+        .await?;
+    refkeys.sort();
+    Ok(refkeys)
+}
@@ -1,5 +1,5 @@
 use super::{Article, Episode, IdRefKey, RefKey};
-use crate::templates::ToHtml;
+use crate::i18n::{Lang, ToHtmlIn};
 use diesel::prelude::*;
 use diesel::result::Error;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
@@ -50,15 +50,15 @@ impl RefKeySet {
     }
 }
 
-impl ToHtml for RefKeySet {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for RefKeySet {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         if let Some((last_ref, refs)) = self.0.split_last() {
-            out.write_all(b"<p class='info refs'>Referenser: ")?;
+            write!(out, "<p class='info refs'>{} ", lang.t("Referenser:"))?;
             for r in refs {
-                r.to_html(out)?;
+                r.to_html_in(lang, out)?;
                 out.write_all(b", ")?;
             }
-            last_ref.to_html(out)?;
+            last_ref.to_html_in(lang, out)?;
             out.write_all(b".</p>")?;
         }
         Ok(())
@@ -0,0 +1,33 @@
+use crate::schema::roles::dsl as r;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// A role a creator can have in an episode or article.
+///
+/// The known roles are kept in the `roles` table, and used in the
+/// `role` column of `episodes_by` and `articles_by`.  The table also
+/// has display label and order, a flag for main roles and a weight
+/// for creator contributions, which are mainly used directly in
+/// queries.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::roles)]
+pub struct Role {
+    pub code: String,
+    /// What a creator with this role is called, e.g. "tecknare".
+    pub noun: String,
+}
+
+impl Role {
+    /// The role used when none is specified.
+    pub const DEFAULT: &'static str = "by";
+
+    /// All known roles, in display order.
+    pub async fn all(db: &mut AsyncPgConnection) -> Result<Vec<Role>, Error> {
+        r::roles
+            .select(Role::as_select())
+            .order(r::ord)
+            .load(db)
+            .await
+    }
+}
@@ -13,7 +13,7 @@ use std::io::{self, Write};
 /// A title of a comic.
 ///
 /// May be recurring, such as "Fantomen" or "Spirit", or a one-shot.
-#[derive(Debug, Queryable, Selectable, PartialEq, Eq)]
+#[derive(Clone, Debug, Queryable, Selectable, PartialEq, Eq)]
 pub struct Title {
     pub id: i32,
     pub title: String,
@@ -47,13 +47,6 @@ impl Title {
         t::titles.filter(t::slug.eq(slug)).first(db).await
     }
 
-    pub fn has_daystrip(&self) -> bool {
-        let t: &str = &self.slug;
-        DAYSTRIPS.binary_search(&t).is_ok()
-    }
-    pub fn has_sundays(&self) -> bool {
-        SUNDAYS.binary_search(&self.slug.as_ref()).is_ok()
-    }
     pub async fn cloud(
         num: i64,
         db: &mut AsyncPgConnection,
@@ -101,34 +94,3 @@ impl CloudItem for Title {
         write!(out, "</a>")
     }
 }
-
-static DAYSTRIPS: [&str; 7] = [
-    "blixt-gordon",
-    "fantomen",
-    "johnny-hazard",
-    "king-vid-granspolisen",
-    "latigo",
-    "mandrake",
-    "rick-o-shay",
-];
-
-static SUNDAYS: [&str; 5] = [
-    "fantomen",
-    "johnny-hazard",
-    "ludvig",
-    "mandrake",
-    "mandrake-fantomen",
-];
-
-#[test]
-fn test_daystrips_sorted() {
-    assert!(is_sorted(&DAYSTRIPS));
-}
-#[test]
-fn test_sundays_sorted() {
-    assert!(is_sorted(&SUNDAYS));
-}
-#[cfg(test)]
-fn is_sorted(data: &[&str]) -> bool {
-    data.windows(2).all(|w| w[0] < w[1])
-}
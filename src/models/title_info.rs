@@ -0,0 +1,90 @@
+use crate::schema::title_info::dsl as ti;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Metadata about a title.
+///
+/// This is maintained in `extra-titles.data` rather than in the
+/// yearly data files.
+#[derive(Debug, Default, Queryable, Selectable, PartialEq, Eq)]
+#[diesel(table_name = crate::schema::title_info)]
+pub struct TitleInfo {
+    /// True if the title is originally published as daily strips.
+    pub daystrip: bool,
+    /// True if the title is originally published as sunday pages.
+    pub sundays: bool,
+    pub syndicate: Option<String>,
+    /// Country of origin.
+    pub country: Option<String>,
+    /// The name of the original creator of the comic.
+    pub orig_creator: Option<String>,
+}
+
+impl TitleInfo {
+    pub async fn load(
+        title_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<TitleInfo, Error> {
+        Ok(ti::title_info
+            .select(TitleInfo::as_select())
+            .filter(ti::title_id.eq(title_id))
+            .first(db)
+            .await
+            .optional()?
+            .unwrap_or_default())
+    }
+
+    /// Store this info for a title, replacing any existing info.
+    pub async fn save(
+        &self,
+        title_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        if self.is_empty() {
+            diesel::delete(ti::title_info)
+                .filter(ti::title_id.eq(title_id))
+                .execute(db)
+                .await?;
+            return Ok(());
+        }
+        diesel::insert_into(ti::title_info)
+            .values((
+                ti::title_id.eq(title_id),
+                ti::daystrip.eq(self.daystrip),
+                ti::sundays.eq(self.sundays),
+                ti::syndicate.eq(&self.syndicate),
+                ti::country.eq(&self.country),
+                ti::orig_creator.eq(&self.orig_creator),
+            ))
+            .on_conflict(ti::title_id)
+            .do_update()
+            .set((
+                ti::daystrip.eq(excluded(ti::daystrip)),
+                ti::sundays.eq(excluded(ti::sundays)),
+                ti::syndicate.eq(excluded(ti::syndicate)),
+                ti::country.eq(excluded(ti::country)),
+                ti::orig_creator.eq(excluded(ti::orig_creator)),
+            ))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == TitleInfo::default()
+    }
+
+    /// True if the title has any kind of newspaper strips.
+    pub fn has_strips(&self) -> bool {
+        self.daystrip || self.sundays
+    }
+
+    /// True if there is any information about the origin of the title.
+    pub fn has_origin(&self) -> bool {
+        self.orig_creator.is_some()
+            || self.syndicate.is_some()
+            || self.country.is_some()
+    }
+}
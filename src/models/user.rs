@@ -0,0 +1,272 @@
+use crate::schema::owned_issues::dsl as oi;
+use crate::schema::read_episodes::dsl as re;
+use crate::schema::sessions::dsl as s;
+use crate::schema::users::dsl as u;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use argon2::password_hash::{PasswordHash, SaltString};
+use argon2::{Argon2, PasswordHasher, PasswordVerifier};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::fmt::Write;
+use tracing::warn;
+
+/// A local user account.
+///
+/// Users can keep track of the issues they own and the episodes they
+/// have read.  Accounts are created with the `users` command, there
+/// is no public registration.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::users)]
+pub struct User {
+    pub id: i32,
+    pub username: String,
+    /// Admin users can edit the contents of issues.
+    pub admin: bool,
+}
+
+impl User {
+    /// How long a session is valid after login.
+    pub const SESSION_DAYS: i64 = 30;
+
+    pub async fn create(
+        username: &str,
+        password: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<User, Error> {
+        diesel::insert_into(u::users)
+            .values((
+                u::username.eq(username),
+                u::password_hash.eq(hash_password(password)),
+            ))
+            .returning(User::as_returning())
+            .get_result(db)
+            .await
+    }
+
+    pub async fn by_name(
+        username: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Option<User>, Error> {
+        u::users
+            .select(User::as_select())
+            .filter(u::username.eq(username))
+            .first(db)
+            .await
+            .optional()
+    }
+
+    pub async fn set_admin(
+        &self,
+        admin: bool,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        diesel::update(u::users.filter(u::id.eq(self.id)))
+            .set(u::admin.eq(admin))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_password(
+        &self,
+        password: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        diesel::update(u::users.filter(u::id.eq(self.id)))
+            .set(u::password_hash.eq(hash_password(password)))
+            .execute(db)
+            .await?;
+        diesel::delete(s::sessions.filter(s::user_id.eq(self.id)))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the user with a given username and password, if correct.
+    pub async fn authenticate(
+        username: &str,
+        password: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Option<User>, Error> {
+        let found = u::users
+            .select((User::as_select(), u::password_hash))
+            .filter(u::username.eq(username))
+            .first::<(User, String)>(db)
+            .await
+            .optional()?;
+        // Verify against a dummy hash for unknown users, so the
+        // response time doesn't tell which usernames exist.
+        let hash = found.as_ref().map_or(DUMMY_HASH, |(_, hash)| hash);
+        let valid = PasswordHash::new(hash)
+            .map_err(|e| warn!("Bad password hash for {username}: {e}"))
+            .is_ok_and(|hash| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &hash)
+                    .is_ok()
+            });
+        Ok(found.filter(|_| valid).map(|(user, _)| user))
+    }
+
+    /// Start a new session for this user, returning the session token.
+    pub async fn start_session(
+        &self,
+        db: &mut AsyncPgConnection,
+    ) -> Result<String, Error> {
+        diesel::delete(s::sessions.filter(s::created.lt(session_limit())))
+            .execute(db)
+            .await?;
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let token = bytes.iter().fold(String::new(), |mut s, b| {
+            let _ = write!(s, "{b:02x}");
+            s
+        });
+        diesel::insert_into(s::sessions)
+            .values((s::token.eq(&token), s::user_id.eq(self.id)))
+            .execute(db)
+            .await?;
+        Ok(token)
+    }
+
+    /// Get the user of a valid session.
+    pub async fn by_session(
+        token: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Option<User>, Error> {
+        s::sessions
+            .inner_join(u::users)
+            .select(User::as_select())
+            .filter(s::token.eq(token))
+            .filter(s::created.ge(session_limit()))
+            .first(db)
+            .await
+            .optional()
+    }
+
+    pub async fn end_session(
+        token: &str,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        diesel::delete(s::sessions.filter(s::token.eq(token)))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Mark an issue as owned, with an optional condition.
+    pub async fn set_owned(
+        &self,
+        issue_id: i32,
+        condition: Option<&str>,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        diesel::insert_into(oi::owned_issues)
+            .values((
+                oi::user_id.eq(self.id),
+                oi::issue_id.eq(issue_id),
+                oi::condition.eq(condition),
+            ))
+            .on_conflict((oi::user_id, oi::issue_id))
+            .do_update()
+            .set(oi::condition.eq(excluded(oi::condition)))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn unset_owned(
+        &self,
+        issue_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        diesel::delete(oi::owned_issues)
+            .filter(oi::user_id.eq(self.id))
+            .filter(oi::issue_id.eq(issue_id))
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the condition of an issue, `None` if not owned.
+    ///
+    /// An owned issue with unknown condition gives `Some(None)`.
+    pub async fn owned(
+        &self,
+        issue_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Option<Option<String>>, Error> {
+        oi::owned_issues
+            .select(oi::condition)
+            .filter(oi::user_id.eq(self.id))
+            .filter(oi::issue_id.eq(issue_id))
+            .first(db)
+            .await
+            .optional()
+    }
+
+    pub async fn set_read(
+        &self,
+        episodes: &[i32],
+        read: bool,
+        db: &mut AsyncPgConnection,
+    ) -> Result<(), Error> {
+        if read {
+            diesel::insert_into(re::read_episodes)
+                .values(
+                    episodes
+                        .iter()
+                        .map(|id| {
+                            (re::user_id.eq(self.id), re::episode_id.eq(id))
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .on_conflict_do_nothing()
+                .execute(db)
+                .await?;
+        } else {
+            diesel::delete(re::read_episodes)
+                .filter(re::user_id.eq(self.id))
+                .filter(re::episode_id.eq_any(episodes))
+                .execute(db)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// A valid hash of a password no user has, see [`User::authenticate`].
+const DUMMY_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$\
+                          8naKMr3wYRIyb1Vf6w6dGg$\
+                          iT8YKSsMtOQQo4KpRvjyakg2bkWP/xEZ6PO8XXmtT04";
+
+fn hash_password(password: &str) -> String {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("Argon2 hashing with default params should work")
+        .to_string()
+}
+
+fn session_limit() -> chrono::DateTime<Utc> {
+    Utc::now() - Duration::days(User::SESSION_DAYS)
+}
+
+#[test]
+fn password_roundtrip() {
+    let hash = hash_password("the ghost who walks");
+    let hash = PasswordHash::new(&hash).unwrap();
+    assert!(
+        Argon2::default()
+            .verify_password(b"the ghost who walks", &hash)
+            .is_ok()
+    );
+    assert!(Argon2::default().verify_password(b"kit", &hash).is_err());
+}
+
+#[test]
+fn dummy_hash_is_valid() {
+    assert!(PasswordHash::new(DUMMY_HASH).is_ok());
+}
@@ -1,6 +1,8 @@
 use crate::DbOpt;
 use crate::models::{
-    Article, Creator, Episode, Issue, OtherMag, Part, RefKey, Title,
+    Article, Creator, CreatorInfo, Episode, ImportRun, Issue, IssueData,
+    Language, OtherMag, Part, RefKey, RefKeyInfo, Role, Title, TitleInfo,
+    User,
 };
 use anyhow::{Context, Result, anyhow, bail};
 use chrono::{Datelike, Local, NaiveDate};
@@ -12,6 +14,7 @@ use diesel::sql_query;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use roxmltree::{Document, Node};
 use slug::slugify;
+use std::collections::BTreeMap;
 use std::fs::read_to_string;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
@@ -28,10 +31,16 @@ pub struct Args {
     #[arg(long, short, env = "FANTOMEN_DATA")]
     basedir: PathBuf,
 
-    /// Read data for all years, from 1950 to current.
+    /// Read data for all years, from 1950 to next year.
+    ///
+    /// Next year is included for issues announced in advance.
     #[arg(long, short)]
     all: bool,
 
+    /// Record issues with a `skick` (condition) as owned by this user.
+    #[arg(long)]
+    owner: Option<String>,
+
     /// Year(s) to read data for.
     #[arg(name = "year")]
     years: Vec<i16>,
@@ -43,33 +52,102 @@ impl Args {
             bail!("No year specified for reading.");
         }
         let mut db = self.db.get_db().await?;
-        read_persondata(&self.basedir, &mut db).await?;
+        let owner = match &self.owner {
+            Some(name) => Some(
+                User::by_name(name, &mut db)
+                    .await?
+                    .ok_or_else(|| anyhow!("No such user {name:?}"))?,
+            ),
+            None => None,
+        };
+        let run =
+            ImportRun::start("read-files", &self.options(), &mut db).await?;
+        let result = self.import(&run, owner.as_ref(), &mut db).await;
+        run.finish(result.as_ref().err().map(|e| format!("{e:#}")), &mut db)
+            .await?;
+        result
+    }
+
+    /// The options of this run, as recorded in the run history.
+    ///
+    /// The database url is left out, as it may contain a password.
+    fn options(&self) -> String {
+        let mut options =
+            vec![format!("--basedir {}", self.basedir.display())];
+        if self.all {
+            options.push("--all".into());
+        }
+        if let Some(owner) = &self.owner {
+            options.push(format!("--owner {owner}"));
+        }
+        options.extend(self.years.iter().map(ToString::to_string));
+        options.join(" ")
+    }
+
+    async fn import(
+        &self,
+        run: &ImportRun,
+        owner: Option<&User>,
+        db: &mut AsyncPgConnection,
+    ) -> Result<()> {
+        read_persondata(&self.basedir, db).await?;
+        read_titledata(&self.basedir, db).await?;
+        read_refkeydata(&self.basedir, db).await?;
+        let roles = load_roles(db).await?;
+        let base = &self.basedir;
         if self.all {
             let current_year = i16::try_from(Local::now().year())?;
-            for year in 1950..=current_year {
-                load_year(&self.basedir, year, &mut db).await?;
+            for year in 1950..=current_year + 1 {
+                load_year(base, year, owner, &roles, run, db).await?;
             }
         } else {
-            for year in self.years {
-                load_year(&self.basedir, year, &mut db).await?;
+            for &year in &self.years {
+                load_year(base, year, owner, &roles, run, db).await?;
             }
         }
-        delete_unpublished(&mut db).await?;
+        delete_unpublished(run, db).await?;
         let start = Instant::now();
         sql_query("refresh materialized view creator_contributions;")
-            .execute(&mut db)
+            .execute(db)
             .await?;
         println!("Updated creators view in {:.3?}", start.elapsed());
         Ok(())
     }
 }
 
+/// Import the xml source of a single issue, as edited on the web.
+///
+/// Like `read-files`, this also removes content that is no longer
+/// published and updates the creators view.  The changes are recorded
+/// as an import run from `source`.
+pub async fn import_issue(
+    year: i16,
+    xml: &str,
+    source: &str,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    let doc = Document::parse(xml)?;
+    let roles = load_roles(db).await?;
+    let run = ImportRun::start(source, "", db).await?;
+    register_issue(year, doc.root_element(), None, &roles, db).await?;
+    run.add_year(year, db).await?;
+    delete_unpublished(&run, db).await?;
+    run.finish(None, db).await?;
+    sql_query("refresh materialized view creator_contributions;")
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
 async fn load_year(
     base: &Path,
     year: i16,
+    owner: Option<&User>,
+    roles: &Roles,
+    run: &ImportRun,
     db: &mut AsyncPgConnection,
 ) -> Result<()> {
-    do_load_year(base, year, db)
+    do_load_year(base, year, owner, roles, run, db)
         .await
         .with_context(|| format!("Failed to read data for {year}"))
 }
@@ -77,6 +155,9 @@ async fn load_year(
 async fn do_load_year(
     base: &Path,
     year: i16,
+    owner: Option<&User>,
+    roles: &Roles,
+    run: &ImportRun,
     db: &mut AsyncPgConnection,
 ) -> Result<()> {
     match read_to_string(base.join(format!("{year}.data"))) {
@@ -85,18 +166,19 @@ async fn do_load_year(
                 match elem.tag_name().name() {
                     "info" => (), // ignore
                     "issue" => {
-                        register_issue(year, elem, db).await.with_context(
-                            || {
+                        register_issue(year, elem, owner, roles, db)
+                            .await
+                            .with_context(|| {
                                 format!(
                                     "Error reading issue {}:",
                                     elem.attribute("nr").unwrap_or("?"),
                                 )
-                            },
-                        )?;
+                            })?;
                     }
                     _ => return Err(unexpected_element(&elem)),
                 }
             }
+            run.add_year(year, db).await?;
         }
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
             eprintln!("No data found for {year}");
@@ -111,23 +193,24 @@ async fn do_load_year(
 async fn register_issue<'a>(
     year: i16,
     i: Node<'a, 'a>,
+    owner: Option<&User>,
+    roles: &Roles,
     db: &mut AsyncPgConnection,
 ) -> Result<()> {
     let nr =
         parse_attribute(i, "nr")?.ok_or_else(|| anyhow!("nr missing"))?;
-    let issue = Issue::get_or_create(
-        year,
-        nr,
-        parse_attribute(i, "ord")?,
-        parse_attribute(i, "pages")?,
-        parse_attribute(i, "price")?,
-        get_child(i, "omslag")
+    let data = IssueData {
+        ord: parse_attribute(i, "ord")?,
+        pages: parse_attribute(i, "pages")?,
+        price: parse_attribute(i, "price")?,
+        cover_best: get_child(i, "omslag")
             .and_then(|e| get_best_plac(e).transpose())
             .transpose()?,
-        db,
-    )
-    .await
-    .context("issue")?;
+        date: parse_attribute(i, "date")?,
+    };
+    let issue = Issue::get_or_create(year, nr, &data, db)
+        .await
+        .context("issue")?;
     info!(%issue, "Found");
     issue.clear(db).await?;
 
@@ -166,13 +249,20 @@ async fn register_issue<'a>(
                     }
                 }
             }
-            "text" => register_article(&issue, seqno, c, db)
+            "text" => register_article(&issue, seqno, c, roles, db)
                 .await
                 .context("text")?,
-            "serie" => register_serie(&issue, seqno, c, db)
+            "serie" => register_serie(&issue, seqno, c, roles, db)
                 .await
                 .context("serie")?,
-            "skick" => (), // ignore
+            "skick" => {
+                if let Some(owner) = owner {
+                    let condition = c.text().map(normalize_space);
+                    owner
+                        .set_owned(issue.id, condition.as_deref(), db)
+                        .await?;
+                }
+            }
             _ => return Err(unexpected_element(&c)),
         }
     }
@@ -183,6 +273,7 @@ async fn register_article<'a>(
     issue: &Issue,
     seqno: i16,
     c: Node<'a, 'a>,
+    roles: &Roles,
     db: &mut AsyncPgConnection,
 ) -> Result<()> {
     let article = Article::get_or_create(
@@ -197,7 +288,7 @@ async fn register_article<'a>(
         match e.tag_name().name() {
             "title" | "subtitle" | "note" => (), // handled above
             "by" => {
-                let role = e.attribute("role").unwrap_or("by");
+                let role = get_role(e, roles)?;
                 for by in get_creators(e, db).await? {
                     use crate::schema::articles_by::dsl as ab;
                     diesel::insert_into(ab::articles_by)
@@ -222,6 +313,7 @@ async fn register_serie<'a>(
     issue: &Issue,
     seqno: i16,
     c: Node<'a, 'a>,
+    roles: &Roles,
     db: &mut AsyncPgConnection,
 ) -> Result<()> {
     use crate::schema::episodes::dsl as e;
@@ -262,6 +354,7 @@ async fn register_serie<'a>(
                     .text()
                     .map(normalize_space)
                     .expect("orig should have name");
+                Language::get_or_create(lang, db).await?;
                 diesel::update(e::episodes)
                     .set((e::orig_lang.eq(lang), e::orig_episode.eq(orig)))
                     .filter(e::id.eq(episode.id))
@@ -271,7 +364,7 @@ async fn register_serie<'a>(
             "label" | "title" | "episode" | "teaser" | "part" | "note"
             | "copyright" | "best" => (), // handled above
             "by" => {
-                let role = e.attribute("role").unwrap_or("by");
+                let role = get_role(e, roles)?;
                 for by in get_creators(e, db).await? {
                     diesel::insert_into(eb::episodes_by)
                         .values((
@@ -403,6 +496,264 @@ fn test_parse_refs() -> Result<()> {
     Ok(())
 }
 
+/// Read title metadata from `extra-titles.data`, if it exists.
+///
+/// Titles not mentioned in the file gets their metadata removed.
+async fn read_titledata(
+    base: &Path,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    use crate::schema::title_info::dsl as ti;
+    let buf = match read_to_string(base.join("extra-titles.data")) {
+        Ok(buf) => buf,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("No extra-titles.data found, keeping title metadata");
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let mut seen = Vec::new();
+    for e in child_elems(Document::parse(&buf)?.root_element()) {
+        match e.tag_name().name() {
+            "title" => {
+                let name = get_req_text(e, "name")?;
+                let title = Title::get_or_create(name, db).await?;
+                parse_title_info(e)
+                    .with_context(|| format!("Bad info for {name:?}"))?
+                    .save(title.id, db)
+                    .await?;
+                seen.push(title.id);
+            }
+            _ => return Err(unexpected_element(&e)),
+        }
+    }
+    diesel::delete(ti::title_info.filter(ti::title_id.ne_all(seen)))
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// Read refkey info, relations and aliases from `extra-refkeys.data`.
+///
+/// All existing info, relations and aliases are replaced by the ones in the
+/// file.  If the file does not exist, they are kept as they are.
+async fn read_refkeydata(
+    base: &Path,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
+    use crate::schema::refkey_aliases::dsl as ra;
+    use crate::schema::refkey_info::dsl as ri;
+    use crate::schema::refkey_parents::dsl as rp;
+    use crate::schema::refkey_see_also::dsl as rs;
+    use crate::schema::refkeys::dsl as r;
+    let buf = match read_to_string(base.join("extra-refkeys.data")) {
+        Ok(buf) => buf,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!(
+                "No extra-refkeys.data found, keeping refkey relations"
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let mut refkeys = Vec::new();
+    for e in child_elems(Document::parse(&buf)?.root_element()) {
+        match e.tag_name().name() {
+            "refkey" => refkeys.push(parse_refkey_data(e)?),
+            _ => return Err(unexpected_element(&e)),
+        }
+    }
+    diesel::delete(ra::refkey_aliases).execute(db).await?;
+    diesel::delete(ri::refkey_info).execute(db).await?;
+    diesel::delete(rp::refkey_parents).execute(db).await?;
+    diesel::delete(rs::refkey_see_also).execute(db).await?;
+
+    // Aliases first, so they apply to refkeys in the relations.
+    let mut ids = Vec::with_capacity(refkeys.len());
+    for data in &refkeys {
+        let id = data.refkey.get_or_create_id(db).await?;
+        if let Some(name) = &data.name {
+            diesel::update(r::refkeys.filter(r::id.eq(id)))
+                .set(r::title.eq(name))
+                .execute(db)
+                .await?;
+        }
+        data.info.save(id, db).await?;
+        let aliases = data
+            .aliases
+            .iter()
+            .map(|name| (RefKey::KEY_ID, Some(name.as_str()), slugify(name)))
+            .chain(
+                data.old_slugs
+                    .iter()
+                    .map(|slug| (data.refkey.kind_id(), None, slug.clone())),
+            );
+        for (kind, name, slug) in aliases {
+            diesel::insert_into(ra::refkey_aliases)
+                .values((
+                    ra::refkey_id.eq(id),
+                    ra::kind.eq(kind),
+                    ra::name.eq(name),
+                    ra::slug.eq(&slug),
+                ))
+                .execute(db)
+                .await
+                .with_context(|| {
+                    format!("Bad alias {slug:?} for {:?}", data.refkey)
+                })?;
+        }
+        ids.push(id);
+    }
+    for (data, id) in refkeys.iter().zip(ids) {
+        for parent in &data.parents {
+            let parent_id = parent.get_or_create_id(db).await?;
+            diesel::insert_into(rp::refkey_parents)
+                .values((rp::refkey_id.eq(id), rp::parent_id.eq(parent_id)))
+                .on_conflict_do_nothing()
+                .execute(db)
+                .await?;
+        }
+        for other in &data.see_also {
+            let other_id = other.get_or_create_id(db).await?;
+            diesel::insert_into(rs::refkey_see_also)
+                .values((rs::refkey_id.eq(id), rs::other_id.eq(other_id)))
+                .on_conflict_do_nothing()
+                .execute(db)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// A refkey with its relations, as given in `extra-refkeys.data`.
+#[derive(Debug, PartialEq, Eq)]
+struct RefKeyData {
+    refkey: RefKey,
+    /// The name of a phantom that is not just known by number.
+    name: Option<String>,
+    info: RefKeyInfo,
+    /// Keys in the data files that means this refkey.
+    aliases: Vec<String>,
+    /// Old slugs that should redirect to this refkey.
+    old_slugs: Vec<String>,
+    parents: Vec<RefKey>,
+    see_also: Vec<RefKey>,
+}
+
+fn parse_refkey_data(e: Node) -> Result<RefKeyData> {
+    let mut refkey = None;
+    let mut name = None;
+    let mut info = RefKeyInfo::default();
+    let mut aliases = Vec::new();
+    let mut old_slugs = Vec::new();
+    let mut parents = Vec::new();
+    let mut see_also = Vec::new();
+    for c in child_elems(e) {
+        match c.tag_name().name() {
+            "fa" | "key" | "who" | "serie" if refkey.is_none() => {
+                refkey = Some(parse_ref(c)?);
+            }
+            "name" if matches!(refkey, Some(RefKey::Fa(..))) => {
+                name = c.text().map(normalize_space);
+            }
+            "kind" => {
+                let kind = c.text().map(normalize_space).unwrap_or_default();
+                if RefKeyInfo::noun(&kind).is_none() {
+                    let known = RefKeyInfo::KINDS.iter().map(|(k, _)| *k);
+                    bail!(
+                        "Unknown refkey kind {kind:?}.  Known kinds are: {}.",
+                        known.collect::<Vec<_>>().join(", "),
+                    );
+                }
+                info.kind = Some(kind);
+            }
+            "description" => info.description = c.text().map(normalize_space),
+            "alias" => aliases.push(
+                c.text()
+                    .map(normalize_space)
+                    .ok_or_else(|| anyhow!("Alias without text"))?,
+            ),
+            "oldslug" => old_slugs.push(
+                c.text()
+                    .map(normalize_space)
+                    .ok_or_else(|| anyhow!("Old slug without text"))?,
+            ),
+            "parent" => parents.extend(parse_refs(c)?),
+            "seealso" => see_also.extend(parse_refs(c)?),
+            _ => return Err(unexpected_element(&c)),
+        }
+    }
+    Ok(RefKeyData {
+        refkey: refkey.ok_or_else(|| anyhow!("No refkey in {e:?}"))?,
+        name,
+        info,
+        aliases,
+        old_slugs,
+        parents,
+        see_also,
+    })
+}
+
+#[test]
+fn test_parse_refkey_data() -> Result<()> {
+    let doc = "<refkey>
+        <fa no=\"22k\"/>
+        <name>Kit</name>
+        <kind>character</kind>
+        <description>Son of the 21st Phantom.</description>
+        <alias>Kit</alias>
+        <oldslug>22.1</oldslug>
+        <parent><key>Walkers</key></parent>
+        <seealso><fa no=\"22h\"/><key>Guran</key></seealso>
+      </refkey>\n";
+
+    assert_eq!(
+        parse_refkey_data(Document::parse(doc)?.root_element())?,
+        RefKeyData {
+            refkey: RefKey::fa("22k"),
+            name: Some("Kit".into()),
+            info: RefKeyInfo {
+                kind: Some("character".into()),
+                description: Some("Son of the 21st Phantom.".into()),
+            },
+            aliases: vec!["Kit".into()],
+            old_slugs: vec!["22.1".into()],
+            parents: vec![RefKey::key("Walkers")],
+            see_also: vec![RefKey::fa("22h"), RefKey::key("Guran")],
+        },
+    );
+    Ok(())
+}
+
+fn parse_title_info(e: Node) -> Result<TitleInfo> {
+    for c in child_elems(e) {
+        match c.tag_name().name() {
+            "name" | "daystrip" | "sundays" | "syndicate" | "country"
+            | "creator" => (),
+            _ => return Err(unexpected_element(&c)),
+        }
+    }
+    Ok(TitleInfo {
+        daystrip: get_child(e, "daystrip").is_some(),
+        sundays: get_child(e, "sundays").is_some(),
+        syndicate: get_text_norm(e, "syndicate"),
+        country: get_text_norm(e, "country"),
+        orig_creator: get_text_norm(e, "creator"),
+    })
+}
+
+fn parse_creator_info(e: Node) -> Result<CreatorInfo> {
+    Ok(CreatorInfo {
+        born: parse_text(e, "born")?,
+        died: parse_text(e, "died")?,
+        nationality: get_text_norm(e, "nationality"),
+        bio: get_text_norm(e, "bio"),
+        wikidata: get_text_norm(e, "wikidata"),
+        lambiek: get_text_norm(e, "lambiek"),
+        phantomwiki: get_text_norm(e, "phantomwiki"),
+    })
+}
+
 fn get_req_text<'a>(e: Node<'a, 'a>, name: &str) -> Result<&'a str> {
     get_text(e, name).ok_or_else(|| anyhow!("{:?} missing child {}", e, name))
 }
@@ -444,6 +795,34 @@ fn get_best_plac(e: Node) -> Result<Option<i16>> {
         .transpose()
 }
 
+/// The known roles, as a map from code to noun.
+type Roles = BTreeMap<String, String>;
+
+/// Load the known roles, once per import run.
+async fn load_roles(db: &mut AsyncPgConnection) -> Result<Roles> {
+    Ok(Role::all(db)
+        .await?
+        .into_iter()
+        .map(|r| (r.code, r.noun))
+        .collect())
+}
+
+/// Get the role of a by element, which must be a known role.
+fn get_role<'a>(e: Node<'a, 'a>, roles: &Roles) -> Result<&'a str> {
+    let role = e.attribute("role").unwrap_or(Role::DEFAULT);
+    if !roles.contains_key(role) {
+        let known = roles
+            .iter()
+            .map(|(code, noun)| format!("{code} ({noun})"))
+            .collect::<Vec<_>>();
+        bail!(
+            "Unknown role {role:?}.  Known roles are: {}.",
+            known.join(", "),
+        );
+    }
+    Ok(role)
+}
+
 async fn get_creators<'a>(
     by: Node<'a, 'a>,
     db: &mut AsyncPgConnection,
@@ -506,6 +885,10 @@ async fn read_persondata(
                         .await?;
                     c
                 };
+                parse_creator_info(e)
+                    .with_context(|| format!("Bad info for {name:?}"))?
+                    .save(creator.id, db)
+                    .await?;
 
                 for a in
                     e.children().filter(|a| a.tag_name().name() == "alias")
@@ -540,7 +923,13 @@ async fn read_persondata(
     Ok(())
 }
 
-async fn delete_unpublished(db: &mut AsyncPgConnection) -> Result<()> {
+/// Delete content that is no longer published anywhere.
+///
+/// The number of deleted rows of each kind is counted in `run`.
+async fn delete_unpublished(
+    run: &ImportRun,
+    db: &mut AsyncPgConnection,
+) -> Result<()> {
     use crate::schema::article_refkeys::dsl as ar;
     use crate::schema::articles::dsl as a;
     use crate::schema::articles_by::dsl as ab;
@@ -549,10 +938,13 @@ async fn delete_unpublished(db: &mut AsyncPgConnection) -> Result<()> {
     use crate::schema::episodes::dsl as e;
     use crate::schema::episodes_by::dsl as eb;
     use crate::schema::publications::dsl as p;
+    use crate::schema::refkey_aliases::dsl as ra;
+    use crate::schema::refkey_parents::dsl as rp;
+    use crate::schema::refkey_see_also::dsl as rs;
     use crate::schema::refkeys::dsl as r;
     use crate::schema::titles::dsl as t;
 
-    do_clear(db, "episode parts", {
+    do_clear(run, db, "episode parts", {
         let published_parts = p::publications
             .select(p::episode_part)
             .filter(p::episode_part.is_not_null())
@@ -561,7 +953,7 @@ async fn delete_unpublished(db: &mut AsyncPgConnection) -> Result<()> {
     })
     .await?;
 
-    do_clear(db, "episode refkeys", {
+    do_clear(run, db, "episode refkeys", {
         er::episode_refkeys.filter(er::episode_id.eq_any(
             e::episodes.select(e::id).filter(
                 e::id.ne_all(
@@ -572,7 +964,7 @@ async fn delete_unpublished(db: &mut AsyncPgConnection) -> Result<()> {
     })
     .await?;
 
-    do_clear(db, "episodes-by", {
+    do_clear(run, db, "episodes-by", {
         eb::episodes_by.filter(eb::episode_id.eq_any(
             e::episodes.select(e::id).filter(
                 e::id.ne_all(
@@ -583,14 +975,14 @@ async fn delete_unpublished(db: &mut AsyncPgConnection) -> Result<()> {
     })
     .await?;
 
-    do_clear(db, "episodes", {
+    do_clear(run, db, "episodes", {
         e::episodes.filter(
             e::id.ne_all(ep::episode_parts.select(ep::episode_id).distinct()),
         )
     })
     .await?;
 
-    do_clear(db, "titles", {
+    do_clear(run, db, "titles", {
         t::titles
             .filter(t::id.ne_all(e::episodes.select(e::title_id).distinct()))
     })
@@ -606,33 +998,39 @@ async fn delete_unpublished(db: &mut AsyncPgConnection) -> Result<()> {
         .flatten()
         .collect::<Vec<_>>();
 
-    do_clear(db, "article refkeys", {
+    do_clear(run, db, "article refkeys", {
         ar::article_refkeys.filter(ar::article_id.ne_all(&published_articles))
     })
     .await?;
 
-    do_clear(db, "articles-by", {
+    do_clear(run, db, "articles-by", {
         ab::articles_by.filter(ab::article_id.ne_all(&published_articles))
     })
     .await?;
 
-    do_clear(db, "articles", {
+    do_clear(run, db, "articles", {
         a::articles.filter(a::id.ne_all(&published_articles))
     })
     .await?;
 
-    do_clear(db, "refkeys", {
+    do_clear(run, db, "refkeys", {
         r::refkeys
             .filter(r::id.ne_all(er::episode_refkeys.select(er::refkey_id)))
             .filter(r::id.ne_all(ar::article_refkeys.select(ar::refkey_id)))
+            .filter(r::id.ne_all(ra::refkey_aliases.select(ra::refkey_id)))
+            .filter(r::id.ne_all(rp::refkey_parents.select(rp::refkey_id)))
+            .filter(r::id.ne_all(rp::refkey_parents.select(rp::parent_id)))
+            .filter(r::id.ne_all(rs::refkey_see_also.select(rs::refkey_id)))
+            .filter(r::id.ne_all(rs::refkey_see_also.select(rs::other_id)))
     })
     .await?;
 
     Ok(())
 }
 
-#[instrument(skip(db, how))]
+#[instrument(skip(run, db, how))]
 async fn do_clear<'a, T: 'a + IntoUpdateTarget>(
+    run: &ImportRun,
     db: &'a mut AsyncPgConnection,
     what: &'static str,
     how: T,
@@ -648,6 +1046,7 @@ where
     let n = diesel::delete(how).execute(db).await.context(what)?;
     let elapsed = format!("{:.0?}", start.elapsed());
     info!(%n, %elapsed, "Cleared");
+    run.add_count(&format!("cleared {what}"), n, db).await?;
     Ok(())
 }
 
@@ -0,0 +1,57 @@
+use crate::DbOpt;
+use crate::models::ImportRun;
+use anyhow::Result;
+
+#[derive(clap::Parser)]
+pub struct Args {
+    #[clap(flatten)]
+    db: DbOpt,
+
+    #[clap(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(clap::Subcommand)]
+enum Cmd {
+    /// List the latest runs, most recent first.
+    List {
+        /// Number of runs to list.
+        #[arg(long, short = 'n', default_value = "10")]
+        limit: i64,
+    },
+}
+
+impl Args {
+    pub async fn run(self) -> Result<()> {
+        let mut db = self.db.get_db().await?;
+        match self.cmd {
+            Cmd::List { limit } => {
+                for run in ImportRun::latest(limit, &mut db).await? {
+                    let duration = run
+                        .duration()
+                        .and_then(|d| d.to_std().ok())
+                        .map(|d| format!(" in {d:.1?}"))
+                        .unwrap_or_default();
+                    println!(
+                        "#{} {} {} {}{duration}: {}",
+                        run.id,
+                        run.started.format("%Y-%m-%d %H:%M:%S"),
+                        run.source,
+                        run.status(),
+                        run.years_text(),
+                    );
+                    if !run.args.is_empty() {
+                        println!("    args: {}", run.args);
+                    }
+                    for (name, n) in run.counts(&mut db).await? {
+                        println!("    {name}: {n}");
+                    }
+                    if let Some(error) = &run.error {
+                        println!("    error: {error}");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
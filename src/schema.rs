@@ -29,6 +29,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    change_fields (change_id, field) {
+        change_id -> Int4,
+        #[max_length = 32]
+        field -> Varchar,
+        old_value -> Nullable<Text>,
+        new_value -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    changes (id) {
+        id -> Int4,
+        run_id -> Nullable<Int4>,
+        at -> Timestamptz,
+        #[max_length = 32]
+        table_name -> Varchar,
+        row_id -> Int4,
+        #[max_length = 8]
+        action -> Varchar,
+        label -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     covers (id) {
         id -> Int4,
@@ -55,6 +79,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    creator_info (creator_id) {
+        creator_id -> Int4,
+        born -> Nullable<Int2>,
+        died -> Nullable<Int2>,
+        #[max_length = 100]
+        nationality -> Nullable<Varchar>,
+        bio -> Nullable<Text>,
+        #[max_length = 20]
+        wikidata -> Nullable<Varchar>,
+        #[max_length = 100]
+        lambiek -> Nullable<Varchar>,
+        #[max_length = 200]
+        phantomwiki -> Nullable<Varchar>,
+    }
+}
+
 diesel::table! {
     creators (id) {
         id -> Int4,
@@ -112,6 +153,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    import_run_counts (run_id, name) {
+        run_id -> Int4,
+        #[max_length = 50]
+        name -> Varchar,
+        n -> Int4,
+    }
+}
+
+diesel::table! {
+    import_runs (id) {
+        id -> Int4,
+        started -> Timestamptz,
+        #[max_length = 100]
+        source -> Varchar,
+        finished -> Nullable<Timestamptz>,
+        args -> Text,
+        years -> Array<Int2>,
+        error -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     issues (id) {
         id -> Int4,
@@ -124,6 +187,16 @@ diesel::table! {
         cover_best -> Nullable<Int2>,
         magic -> Int2,
         ord -> Nullable<Int4>,
+        date -> Nullable<Date>,
+    }
+}
+
+diesel::table! {
+    languages (code) {
+        #[max_length = 10]
+        code -> Varchar,
+        #[max_length = 100]
+        name -> Varchar,
     }
 }
 
@@ -137,6 +210,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    owned_issues (user_id, issue_id) {
+        user_id -> Int4,
+        issue_id -> Int4,
+        #[max_length = 100]
+        condition -> Nullable<Varchar>,
+    }
+}
+
 diesel::table! {
     publications (id) {
         id -> Int4,
@@ -149,6 +231,48 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    read_episodes (user_id, episode_id) {
+        user_id -> Int4,
+        episode_id -> Int4,
+    }
+}
+
+diesel::table! {
+    refkey_aliases (id) {
+        id -> Int4,
+        refkey_id -> Int4,
+        kind -> Int2,
+        #[max_length = 200]
+        name -> Nullable<Varchar>,
+        #[max_length = 100]
+        slug -> Varchar,
+    }
+}
+
+diesel::table! {
+    refkey_info (refkey_id) {
+        refkey_id -> Int4,
+        #[max_length = 20]
+        kind -> Nullable<Varchar>,
+        description -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    refkey_parents (refkey_id, parent_id) {
+        refkey_id -> Int4,
+        parent_id -> Int4,
+    }
+}
+
+diesel::table! {
+    refkey_see_also (refkey_id, other_id) {
+        refkey_id -> Int4,
+        other_id -> Int4,
+    }
+}
+
 diesel::table! {
     refkeys (id) {
         id -> Int4,
@@ -159,6 +283,43 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    roles (code) {
+        #[max_length = 10]
+        code -> Varchar,
+        #[max_length = 100]
+        label -> Varchar,
+        #[max_length = 100]
+        noun -> Varchar,
+        ord -> Int2,
+        main -> Bool,
+        weight -> Int2,
+    }
+}
+
+diesel::table! {
+    sessions (token) {
+        #[max_length = 64]
+        token -> Varchar,
+        user_id -> Int4,
+        created -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    title_info (title_id) {
+        title_id -> Int4,
+        daystrip -> Bool,
+        sundays -> Bool,
+        #[max_length = 200]
+        syndicate -> Nullable<Varchar>,
+        #[max_length = 100]
+        country -> Nullable<Varchar>,
+        #[max_length = 200]
+        orig_creator -> Nullable<Varchar>,
+    }
+}
+
 diesel::table! {
     titles (id) {
         id -> Int4,
@@ -167,40 +328,82 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        #[max_length = 100]
+        username -> Varchar,
+        password_hash -> Varchar,
+        created -> Timestamptz,
+        admin -> Bool,
+    }
+}
+
 diesel::joinable!(article_refkeys -> articles (article_id));
 diesel::joinable!(article_refkeys -> refkeys (refkey_id));
 diesel::joinable!(articles_by -> articles (article_id));
 diesel::joinable!(articles_by -> creator_aliases (creator_alias_id));
+diesel::joinable!(articles_by -> roles (role));
+diesel::joinable!(change_fields -> changes (change_id));
+diesel::joinable!(changes -> import_runs (run_id));
+diesel::joinable!(import_run_counts -> import_runs (run_id));
 diesel::joinable!(covers -> issues (issue));
 diesel::joinable!(covers_by -> creator_aliases (creator_alias_id));
 diesel::joinable!(covers_by -> issues (issue_id));
 diesel::joinable!(creator_aliases -> creators (creator_id));
+diesel::joinable!(creator_info -> creators (creator_id));
 diesel::joinable!(episode_parts -> episodes (episode_id));
 diesel::joinable!(episode_refkeys -> episodes (episode_id));
 diesel::joinable!(episode_refkeys -> refkeys (refkey_id));
+diesel::joinable!(episodes -> languages (orig_lang));
 diesel::joinable!(episodes -> other_mags (orig_mag_id));
 diesel::joinable!(episodes -> titles (title_id));
 diesel::joinable!(episodes_by -> creator_aliases (creator_alias_id));
 diesel::joinable!(episodes_by -> episodes (episode_id));
+diesel::joinable!(episodes_by -> roles (role));
+diesel::joinable!(owned_issues -> issues (issue_id));
+diesel::joinable!(owned_issues -> users (user_id));
 diesel::joinable!(publications -> articles (article_id));
 diesel::joinable!(publications -> episode_parts (episode_part));
 diesel::joinable!(publications -> issues (issue_id));
+diesel::joinable!(read_episodes -> episodes (episode_id));
+diesel::joinable!(read_episodes -> users (user_id));
+diesel::joinable!(refkey_aliases -> refkeys (refkey_id));
+diesel::joinable!(refkey_info -> refkeys (refkey_id));
+diesel::joinable!(sessions -> users (user_id));
+diesel::joinable!(title_info -> titles (title_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     article_refkeys,
     articles,
     articles_by,
+    change_fields,
+    changes,
     covers,
     covers_by,
     creator_aliases,
+    creator_info,
     creators,
     episode_parts,
     episode_refkeys,
     episodes,
     episodes_by,
+    import_run_counts,
+    import_runs,
     issues,
+    languages,
     other_mags,
+    owned_issues,
     publications,
+    read_episodes,
+    refkey_aliases,
+    refkey_info,
+    refkey_parents,
+    refkey_see_also,
     refkeys,
+    roles,
+    sessions,
+    title_info,
     titles,
+    users,
 );
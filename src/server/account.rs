@@ -0,0 +1,477 @@
+//! Login and per-user tracking of owned issues and read episodes.
+use super::locale::lang;
+use super::{
+    FullEpisode, PgFilter, PgPool, Result, ViewError, ViewResult, goh, wrap,
+};
+use crate::i18n::Lang;
+use crate::models::collection::{export_owned, import_owned};
+use crate::models::{Episode, IssueRef, Title, User};
+use crate::schema::episode_parts::dsl as ep;
+use crate::schema::episodes::dsl as e;
+use crate::schema::issues::dsl as i;
+use crate::schema::owned_issues::dsl as oi;
+use crate::schema::publications::dsl as p;
+use crate::schema::read_episodes::dsl as re;
+use crate::schema::titles::dsl as t;
+use crate::templates::{
+    RenderRucte, login_html, my_html, my_import_html, my_missing_html,
+    my_unread_html,
+};
+use diesel::dsl::{count, count_star, exists, min, not, sql};
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Deserialize;
+use tracing::{info, warn};
+use warp::filters::BoxedFilter;
+use warp::http::Uri;
+use warp::http::header::{
+    CONTENT_DISPOSITION, CONTENT_TYPE, LOCATION, SET_COOKIE,
+};
+use warp::http::response::Builder;
+use warp::http::status::StatusCode;
+use warp::reply::Response;
+use warp::{self, Filter, Reply};
+
+/// Name of the session cookie.
+const COOKIE: &str = "fanrs_session";
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
+    use warp::body::{content_length_limit, form};
+    use warp::filters::cookie;
+    use warp::path::{end, param, path};
+    use warp::post;
+    let login_form = path("login")
+        .and(end())
+        .and(goh())
+        .and(lang())
+        .then(login_form);
+    let login = path("login")
+        .and(end())
+        .and(post())
+        .and(content_length_limit(4096))
+        .and(form())
+        .and(s.clone())
+        .and(lang())
+        .then(login);
+    let logout = path("logout")
+        .and(end())
+        .and(post())
+        .and(cookie::optional(COOKIE))
+        .and(s.clone())
+        .then(logout);
+    let my = end()
+        .and(goh())
+        .and(user(s.clone()))
+        .and(s.clone())
+        .and(lang())
+        .then(my_page);
+    let missing = path("missing")
+        .and(end())
+        .and(goh())
+        .and(user(s.clone()))
+        .and(s.clone())
+        .and(lang())
+        .then(missing);
+    let unread = path("unread")
+        .and(param())
+        .and(end())
+        .and(goh())
+        .and(user(s.clone()))
+        .and(s.clone())
+        .and(lang())
+        .then(unread);
+    let read = path("read")
+        .and(end())
+        .and(post())
+        .and(content_length_limit(4096))
+        .and(form())
+        .and(user(s.clone()))
+        .and(s.clone())
+        .then(set_read);
+    let issue = path("issue")
+        .and(end())
+        .and(post())
+        .and(content_length_limit(4096))
+        .and(form())
+        .and(user(s.clone()))
+        .and(s.clone())
+        .then(set_issue);
+    let export = path("owned.csv")
+        .and(end())
+        .and(goh())
+        .and(user(s.clone()))
+        .and(s.clone())
+        .then(export);
+    let import = path("import")
+        .and(end())
+        .and(post())
+        .and(content_length_limit(1 << 20))
+        .and(form())
+        .and(user(s.clone()))
+        .and(s)
+        .and(lang())
+        .then(import);
+    let my = path("my").and(
+        my.or(missing)
+            .unify()
+            .or(export)
+            .unify()
+            .or(import)
+            .unify()
+            .or(unread)
+            .unify()
+            .or(read)
+            .unify()
+            .or(issue)
+            .unify(),
+    );
+    login_form
+        .or(login)
+        .unify()
+        .or(logout)
+        .unify()
+        .or(my)
+        .unify()
+        .map(wrap)
+        .boxed()
+}
+
+/// A filter providing the logged in user, if any.
+pub fn user(s: PgFilter) -> BoxedFilter<(Option<User>,)> {
+    warp::filters::cookie::optional(COOKIE)
+        .and(s)
+        .then(session_user)
+        .boxed()
+}
+
+async fn session_user(token: Option<String>, db: PgPool) -> Option<User> {
+    let token = token?;
+    let mut db = db
+        .get()
+        .await
+        .map_err(|e| warn!("Failed to get db for session: {e}"))
+        .ok()?;
+    User::by_session(&token, &mut db)
+        .await
+        .map_err(|e| warn!("Failed to check session: {e}"))
+        .ok()
+        .flatten()
+}
+
+async fn login_form(lang: Lang) -> Result<Response> {
+    Ok(Builder::new().html(|o| login_html(o, lang, None))?)
+}
+
+#[derive(Deserialize)]
+struct LoginForm {
+    username: String,
+    password: String,
+}
+
+async fn login(form: LoginForm, db: PgPool, lang: Lang) -> Result<Response> {
+    let mut db = db.get().await?;
+    match User::authenticate(&form.username, &form.password, &mut db).await? {
+        Some(user) => {
+            info!(user = user.username, "Logged in");
+            let token = user.start_session(&mut db).await?;
+            let cookie = format!(
+                "{COOKIE}={token}; Path=/; HttpOnly; Secure; SameSite=Lax; \
+                 Max-Age={}",
+                User::SESSION_DAYS * 24 * 60 * 60,
+            );
+            see_other("/my", Some(&cookie))
+        }
+        None => {
+            Ok(Builder::new().status(StatusCode::FORBIDDEN).html(|o| {
+                let msg = lang.t("Fel användarnamn eller lösenord.");
+                login_html(o, lang, Some(msg))
+            })?)
+        }
+    }
+}
+
+async fn logout(token: Option<String>, db: PgPool) -> Result<Response> {
+    if let Some(token) = token {
+        let mut db = db.get().await?;
+        User::end_session(&token, &mut db).await?;
+    }
+    let cookie = format!("{COOKIE}=; Path=/; HttpOnly; Secure; Max-Age=0");
+    see_other("/", Some(&cookie))
+}
+
+async fn my_page(
+    user: Option<User>,
+    db: PgPool,
+    lang: Lang,
+) -> Result<Response> {
+    let Some(user) = user else {
+        return see_other("/login", None);
+    };
+    let mut db = db.get().await?;
+    let years = i::issues
+        .left_join(
+            oi::owned_issues
+                .on(oi::issue_id.eq(i::id).and(oi::user_id.eq(user.id))),
+        )
+        .group_by(i::year)
+        .select((i::year, count(oi::issue_id.nullable()), count_star()))
+        .order(i::year)
+        .load::<(i16, i64, i64)>(&mut db)
+        .await?;
+    let titles = t::titles
+        .inner_join(e::episodes.inner_join(re::read_episodes))
+        .filter(re::user_id.eq(user.id))
+        .group_by(t::titles::all_columns())
+        .select((
+            Title::as_select(),
+            count_star(),
+            sql::<BigInt>(
+                "(select count(*) from episodes x where x.title_id = titles.id)",
+            ),
+        ))
+        .order(t::title)
+        .load::<(Title, i64, i64)>(&mut db)
+        .await?;
+    let owned = years.iter().map(|y| y.1).sum();
+    let total = years.iter().map(|y| y.2).sum();
+    Ok(Builder::new()
+        .html(|o| my_html(o, lang, &user, owned, total, &years, &titles))?)
+}
+
+async fn missing(
+    user: Option<User>,
+    db: PgPool,
+    lang: Lang,
+) -> Result<Response> {
+    let Some(user) = user else {
+        return see_other("/login", None);
+    };
+    let mut db = db.get().await?;
+    let issues = i::issues
+        .select((i::year, (i::number, i::number_str)))
+        .filter(not(exists(
+            oi::owned_issues
+                .filter(oi::issue_id.eq(i::id))
+                .filter(oi::user_id.eq(user.id)),
+        )))
+        .order(i::magic)
+        .load::<IssueRef>(&mut db)
+        .await?;
+    let mut years: Vec<(i16, Vec<IssueRef>)> = Vec::new();
+    for issue in issues {
+        match years.last_mut() {
+            Some((year, issues)) if *year == issue.year => issues.push(issue),
+            _ => years.push((issue.year, vec![issue])),
+        }
+    }
+    Ok(Builder::new().html(|o| my_missing_html(o, lang, &user, &years))?)
+}
+
+async fn unread(
+    slug: String,
+    user: Option<User>,
+    db: PgPool,
+    lang: Lang,
+) -> Result<Response> {
+    let Some(user) = user else {
+        return see_other("/login", None);
+    };
+    let mut db = db.get().await?;
+    let title = t::titles
+        .filter(t::slug.eq(slug))
+        .first::<Title>(&mut db)
+        .await
+        .optional()?
+        .ok_or(ViewError::NotFound)?;
+    let episodes_raw = e::episodes
+        .filter(e::title_id.eq(title.id))
+        .filter(not(exists(
+            re::read_episodes
+                .filter(re::episode_id.eq(e::id))
+                .filter(re::user_id.eq(user.id)),
+        )))
+        .select(e::episodes::all_columns())
+        .inner_join(
+            ep::episode_parts
+                .inner_join(p::publications.inner_join(i::issues)),
+        )
+        .group_by(e::episodes::all_columns())
+        .order(min(i::magic))
+        .load::<Episode>(&mut db)
+        .await?;
+    let mut episodes = Vec::with_capacity(episodes_raw.len());
+    for episode in episodes_raw {
+        episodes.push(FullEpisode::load_details(episode, &mut db).await?);
+    }
+    Ok(Builder::new()
+        .html(|o| my_unread_html(o, lang, &user, &title, &episodes))?)
+}
+
+async fn export(user: Option<User>, db: PgPool) -> Result<Response> {
+    let Some(user) = user else {
+        return see_other("/login", None);
+    };
+    let mut db = db.get().await?;
+    let csv = export_owned(&user, &mut db).await?;
+    Builder::new()
+        .header(CONTENT_TYPE, "text/csv; charset=utf-8")
+        .header(
+            CONTENT_DISPOSITION,
+            "attachment; filename=\"fantomen-samling.csv\"",
+        )
+        .body(csv.into())
+        .ise()
+}
+
+#[derive(Deserialize)]
+struct ImportForm {
+    csv: String,
+}
+
+async fn import(
+    form: ImportForm,
+    user: Option<User>,
+    db: PgPool,
+    lang: Lang,
+) -> Result<Response> {
+    let Some(user) = user else {
+        return see_other("/login", None);
+    };
+    let mut db = db.get().await?;
+    let report = import_owned(&user, &form.csv, &mut db).await?;
+    Ok(Builder::new().html(|o| my_import_html(o, lang, &user, &report))?)
+}
+
+#[derive(Deserialize)]
+struct ReadForm {
+    episode: i32,
+    read: bool,
+    next: String,
+}
+
+async fn set_read(
+    form: ReadForm,
+    user: Option<User>,
+    db: PgPool,
+) -> Result<Response> {
+    let Some(user) = user else {
+        return see_other("/login", None);
+    };
+    let mut db = db.get().await?;
+    user.set_read(&[form.episode], form.read, &mut db).await?;
+    see_other(local_url(&form.next), None)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum IssueAction {
+    Own,
+    Disown,
+    Read,
+}
+
+#[derive(Deserialize)]
+struct IssueForm {
+    issue: i32,
+    action: IssueAction,
+    #[serde(default)]
+    condition: String,
+    next: String,
+}
+
+async fn set_issue(
+    form: IssueForm,
+    user: Option<User>,
+    db: PgPool,
+) -> Result<Response> {
+    let Some(user) = user else {
+        return see_other("/login", None);
+    };
+    let mut db = db.get().await?;
+    match form.action {
+        IssueAction::Own => {
+            let condition = form.condition.trim();
+            let condition = (!condition.is_empty()).then_some(condition);
+            user.set_owned(form.issue, condition, &mut db).await?;
+        }
+        IssueAction::Disown => user.unset_owned(form.issue, &mut db).await?,
+        IssueAction::Read => {
+            let episodes = issue_episodes(form.issue, &mut db).await?;
+            user.set_read(&episodes, true, &mut db).await?;
+        }
+    }
+    see_other(local_url(&form.next), None)
+}
+
+/// Ids of the episodes with any part published in an issue.
+async fn issue_episodes(
+    issue: i32,
+    db: &mut AsyncPgConnection,
+) -> Result<Vec<i32>> {
+    Ok(ep::episode_parts
+        .inner_join(p::publications)
+        .select(ep::episode_id)
+        .filter(p::issue_id.eq(issue))
+        .filter(p::seqno.is_not_null())
+        .distinct()
+        .load(db)
+        .await?)
+}
+
+/// The user-specific state of an issue.
+pub struct MyIssue {
+    pub owned: bool,
+    pub condition: Option<String>,
+    /// True if the user can edit the issue.
+    pub admin: bool,
+}
+
+impl MyIssue {
+    pub async fn load(
+        user: &User,
+        issue: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<MyIssue> {
+        let owned = user.owned(issue, db).await?;
+        Ok(MyIssue {
+            owned: owned.is_some(),
+            condition: owned.flatten(),
+            admin: user.admin,
+        })
+    }
+}
+
+/// Only redirect to local urls after a form post.
+///
+/// Browsers treat a backslash as a slash, so `/\host` is not local
+/// either.
+fn local_url(url: &str) -> &str {
+    let local = url.starts_with('/')
+        && !url.starts_with("//")
+        && !url.contains(['\\', '\r', '\n'])
+        && url
+            .parse::<Uri>()
+            .is_ok_and(|uri| uri.scheme().is_none() && uri.host().is_none());
+    if local { url } else { "/my" }
+}
+
+/// Redirect after a form post (or to the login page).
+pub fn see_other(url: &str, cookie: Option<&str>) -> Result<Response> {
+    let mut builder = Builder::new()
+        .status(StatusCode::SEE_OTHER)
+        .header(LOCATION, url);
+    if let Some(cookie) = cookie {
+        builder = builder.header(SET_COOKIE, cookie);
+    }
+    builder.body(format!("See {url:?}").into()).ise()
+}
+
+#[test]
+fn only_local_urls() {
+    assert_eq!(local_url("/1970/3"), "/1970/3");
+    assert_eq!(local_url("//evil.example/"), "/my");
+    assert_eq!(local_url("https://evil.example/"), "/my");
+    assert_eq!(local_url("/\\evil.example"), "/my");
+    assert_eq!(local_url("/\\/evil.example"), "/my");
+}
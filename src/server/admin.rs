@@ -0,0 +1,324 @@
+//! Editing the contents of issues, for admin users.
+//!
+//! An issue is edited in a form with a row per publication, or as xml
+//! source, and saved both to the database and back to the
+//! `{year}.data` file, which is still the source of truth.  Admin
+//! users can also see the history of import runs.
+use super::account::{see_other, user};
+use super::issueform::IssueForm;
+use super::locale::lang;
+use super::{PgFilter, PgPool, Result, ViewError, goh, wrap};
+use crate::i18n::Lang;
+use crate::models::{ImportRun, Role, User};
+use crate::readfiles::import_issue;
+use crate::templates::{
+    RenderRucte, admin_issue_html, admin_issue_xml_html, admin_runs_html,
+};
+use crate::yearfile::{YearFile, xml_escape};
+use anyhow::Context;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use std::fs::{remove_file, rename};
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+use tokio::task::spawn_blocking;
+use tracing::{error, info};
+use warp::filters::BoxedFilter;
+use warp::http::response::Builder;
+use warp::http::status::StatusCode;
+use warp::reply::Response;
+use warp::{self, Filter, Reply};
+
+/// Only one edit at a time, as each edit rewrites a whole data file.
+static EDIT_LOCK: Mutex<()> = Mutex::const_new(());
+
+pub fn routes(
+    s: PgFilter,
+    basedir: Option<PathBuf>,
+) -> BoxedFilter<(impl Reply,)> {
+    use warp::body::{content_length_limit, form};
+    use warp::path::{end, param, path};
+    use warp::post;
+    let basedir = warp::any().map(move || basedir.clone()).boxed();
+    let runs = path("admin")
+        .and(path("runs"))
+        .and(end())
+        .and(goh())
+        .and(user(s.clone()))
+        .and(s.clone())
+        .and(lang())
+        .then(import_runs);
+    let issue = path("admin").and(param()).and(param());
+    let edit = issue
+        .and(end())
+        .and(goh())
+        .and(user(s.clone()))
+        .and(s.clone())
+        .and(basedir.clone())
+        .and(lang())
+        .then(edit_issue);
+    let xml = issue
+        .and(path("xml"))
+        .and(end())
+        .and(goh())
+        .and(user(s.clone()))
+        .and(basedir.clone())
+        .and(lang())
+        .then(edit_xml);
+    let save = issue
+        .and(end())
+        .and(post())
+        .and(content_length_limit(1 << 20))
+        .and(form())
+        .and(user(s.clone()))
+        .and(s)
+        .and(basedir)
+        .and(lang())
+        .then(save_issue);
+    runs.or(edit)
+        .unify()
+        .or(xml)
+        .unify()
+        .or(save)
+        .unify()
+        .map(wrap)
+        .boxed()
+}
+
+/// Number of import runs shown on the runs page.
+const RUNS: i64 = 50;
+
+async fn import_runs(
+    user: Option<User>,
+    db: PgPool,
+    lang: Lang,
+) -> Result<Response> {
+    let Some(user) = admin_user(user)? else {
+        return see_other("/login", None);
+    };
+    let mut db = db.get().await?;
+    let mut runs = Vec::new();
+    for run in ImportRun::latest(RUNS, &mut db).await? {
+        let counts = run.counts(&mut db).await?;
+        runs.push((run, counts));
+    }
+    Ok(Builder::new().html(|o| admin_runs_html(o, lang, &user, &runs))?)
+}
+
+async fn edit_issue(
+    year: i16,
+    nr: String,
+    user: Option<User>,
+    db: PgPool,
+    basedir: Option<PathBuf>,
+    lang: Lang,
+) -> Result<Response> {
+    let Some((user, basedir)) = admin(user, basedir)? else {
+        return see_other("/login", None);
+    };
+    let file = load_file(&basedir, year)?;
+    let orig = file.issue_xml(&nr).map_err(ise)?.unwrap_or_default();
+    let form = if orig.is_empty() {
+        IssueForm::from_xml(&format!("<issue nr=\"{}\"/>", xml_escape(&nr)))
+    } else {
+        IssueForm::from_xml(orig)
+    };
+    let page = EditPage {
+        year,
+        nr: nr.clone(),
+        orig: orig.to_string(),
+        form: form.map_err(ise)?.with_blanks(),
+        roles: Role::all(&mut *db.get().await?).await?,
+    };
+    Ok(Builder::new()
+        .html(|o| admin_issue_html(o, lang, &user, &page, None))?)
+}
+
+async fn edit_xml(
+    year: i16,
+    nr: String,
+    user: Option<User>,
+    basedir: Option<PathBuf>,
+    lang: Lang,
+) -> Result<Response> {
+    let Some((user, basedir)) = admin(user, basedir)? else {
+        return see_other("/login", None);
+    };
+    let file = load_file(&basedir, year)?;
+    let orig = file.issue_xml(&nr).map_err(ise)?.unwrap_or_default();
+    let xml = if orig.is_empty() {
+        format!("<issue nr=\"{nr}\">\n  </issue>")
+    } else {
+        orig.to_string()
+    };
+    let form = EditForm {
+        xml,
+        orig: orig.to_string(),
+    };
+    Ok(Builder::new().html(|o| {
+        admin_issue_xml_html(o, lang, &user, year, &nr, &form, None)
+    })?)
+}
+
+/// An issue being edited in the structured form.
+pub struct EditPage {
+    pub year: i16,
+    pub nr: String,
+    /// The source of the issue when the edit started.
+    pub orig: String,
+    pub form: IssueForm,
+    pub roles: Vec<Role>,
+}
+
+/// The xml source of an issue, as edited in the xml editor.
+pub struct EditForm {
+    pub xml: String,
+    /// The source of the issue when the edit started.
+    pub orig: String,
+}
+
+async fn save_issue(
+    year: i16,
+    nr: String,
+    fields: Vec<(String, String)>,
+    user: Option<User>,
+    db: PgPool,
+    basedir: Option<PathBuf>,
+    lang: Lang,
+) -> Result<Response> {
+    let Some((user, basedir)) = admin(user, basedir)? else {
+        return see_other("/login", None);
+    };
+    // The xml editor posts the issue as `xml`, the structured form
+    // posts a field per value.
+    let mut orig = String::new();
+    let mut xml = None;
+    let mut form = Vec::new();
+    for (name, value) in fields {
+        // Browsers send line breaks in form fields as crlf.
+        let value = value.replace("\r\n", "\n");
+        match name.as_str() {
+            "orig" => orig = value,
+            "xml" => xml = Some(value),
+            _ => form.push((name, value)),
+        }
+    }
+    let form = xml.is_none().then(|| IssueForm::from_form(form));
+    let xml =
+        xml.unwrap_or_else(|| form.iter().map(IssueForm::to_xml).collect());
+    let mut db = db.get().await?;
+    let _lock = EDIT_LOCK.lock().await;
+    let file = load_file(&basedir, year)?;
+    let current = file
+        .issue_xml(&nr)
+        .map_err(ise)?
+        .unwrap_or_default()
+        .to_string();
+    let (status, message) = if current == orig {
+        match store(year, &nr, &xml, file, &user, &mut db).await {
+            Ok(()) => {
+                info!(user = user.username, year, nr, "Saved issue");
+                let first = nr.split('-').next().unwrap_or(&nr);
+                return see_other(&format!("/{year}/{first}"), None);
+            }
+            Err(e) => (StatusCode::BAD_REQUEST, format!("{e:#}")),
+        }
+    } else {
+        let message = lang
+            .t("Numret har ändrats i datafilen sedan du började redigera. \
+             Kontrollera dina ändringar och spara igen.");
+        (StatusCode::CONFLICT, message.into())
+    };
+    let message = Some(message.as_str());
+    let builder = Builder::new().status(status);
+    if let Some(form) = form {
+        let page = EditPage {
+            year,
+            nr,
+            orig: current,
+            form: form.with_blanks(),
+            roles: Role::all(&mut db).await?,
+        };
+        Ok(builder
+            .html(|o| admin_issue_html(o, lang, &user, &page, message))?)
+    } else {
+        let form = EditForm { xml, orig: current };
+        Ok(builder.html(|o| {
+            admin_issue_xml_html(o, lang, &user, year, &nr, &form, message)
+        })?)
+    }
+}
+
+/// Import an edited issue to the database and save it to its file.
+///
+/// The file is written to a temporary path in the transaction, and
+/// replaces the data file only when the transaction is committed, so
+/// the data file and the database are not left out of sync.
+async fn store(
+    year: i16,
+    nr: &str,
+    xml: &str,
+    mut file: YearFile,
+    user: &User,
+    db: &mut AsyncPgConnection,
+) -> anyhow::Result<()> {
+    file.set_issue(nr, xml)?;
+    let path = file.path().to_owned();
+    let tmp = file.temp_path();
+    let source = format!("web: {}", user.username);
+    let xml = xml.trim();
+    let result = db
+        .transaction::<_, anyhow::Error, _>(|db| {
+            let tmp = tmp.clone();
+            async move {
+                import_issue(year, xml, &source, db).await?;
+                spawn_blocking(move || file.save_as(&tmp)).await?
+            }
+            .scope_boxed()
+        })
+        .await;
+    if let Err(e) = result {
+        // The temporary file may not have been written.
+        let _ = spawn_blocking(move || remove_file(tmp)).await;
+        return Err(e);
+    }
+    spawn_blocking(move || rename(&tmp, &path))
+        .await?
+        .context("Failed to replace the data file")
+}
+
+/// Check that the user is an admin and that editing is enabled.
+///
+/// Gives `None` for anonymous users, that should be redirected to the
+/// login page.  Other users get a not found.
+fn admin(
+    user: Option<User>,
+    basedir: Option<PathBuf>,
+) -> Result<Option<(User, PathBuf)>> {
+    let Some(user) = admin_user(user)? else {
+        return Ok(None);
+    };
+    let basedir = basedir.ok_or(ViewError::NotFound)?;
+    Ok(Some((user, basedir)))
+}
+
+/// Check that the user is an admin, like [`admin`].
+fn admin_user(user: Option<User>) -> Result<Option<User>> {
+    match user {
+        Some(user) if user.admin => Ok(Some(user)),
+        Some(_) => Err(ViewError::NotFound),
+        None => Ok(None),
+    }
+}
+
+fn load_file(basedir: &std::path::Path, year: i16) -> Result<YearFile> {
+    YearFile::load(basedir, year).map_err(|e| {
+        info!("No data file for {year}: {e:#}");
+        ViewError::NotFound
+    })
+}
+
+fn ise(e: anyhow::Error) -> ViewError {
+    error!("Failed to handle data file: {e:#}");
+    ViewError::Err("Fel i datafilen")
+}
@@ -0,0 +1,149 @@
+//! Release calendar of issues with a known date, as iCalendar.
+use super::meta::absolute;
+use super::{PgFilter, PgPool, Result, ViewResult, goh, wrap};
+use crate::schema::episode_parts::dsl as ep;
+use crate::schema::episodes::dsl as e;
+use crate::schema::issues::dsl as i;
+use crate::schema::publications::dsl as p;
+use crate::schema::titles::dsl as t;
+use chrono::{Days, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::collections::BTreeMap;
+use warp::filters::BoxedFilter;
+use warp::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use warp::http::response::Builder;
+use warp::reply::Response;
+use warp::{self, Filter, Reply};
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
+    use warp::path::{end, path};
+    path("calendar.ics")
+        .and(end())
+        .and(goh())
+        .and(s)
+        .then(calendar)
+        .map(wrap)
+        .boxed()
+}
+
+async fn calendar(db: PgPool) -> Result<Response> {
+    let mut db = db.get().await?;
+    let issues = i::issues
+        .select((i::id, i::year, i::number, i::number_str, i::date))
+        .filter(i::date.is_not_null())
+        .order((i::date, i::magic))
+        .load::<(i32, i16, i16, String, Option<NaiveDate>)>(&mut db)
+        .await?;
+    let mut titles = BTreeMap::<i32, Vec<String>>::new();
+    for (issue, title) in p::publications
+        .inner_join(
+            ep::episode_parts.inner_join(e::episodes.inner_join(t::titles)),
+        )
+        .select((p::issue_id, t::title))
+        .filter(p::issue_id.eq_any(issues.iter().map(|(id, ..)| *id)))
+        .order((p::issue_id, p::seqno))
+        .load::<(i32, String)>(&mut db)
+        .await?
+    {
+        let titles = titles.entry(issue).or_default();
+        if !titles.contains(&title) {
+            titles.push(title);
+        }
+    }
+
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+    let mut ics = String::new();
+    let mut line = |line: &str| {
+        ics.push_str(&fold(line));
+        ics.push_str("\r\n");
+    };
+    line("BEGIN:VCALENDAR");
+    line("VERSION:2.0");
+    line(&format!(
+        "PRODID:-//Rasmus Kaj//fanrs {}//SV",
+        env!("CARGO_PKG_VERSION"),
+    ));
+    line("CALSCALE:GREGORIAN");
+    line("METHOD:PUBLISH");
+    line("X-WR-CALNAME:Fantomen");
+    for (id, year, number, number_str, date) in &issues {
+        let Some(date) = date else { continue };
+        let url = absolute(&format!("/{year}/{number}"));
+        line("BEGIN:VEVENT");
+        line(&format!("UID:{url}"));
+        line(&format!("DTSTAMP:{stamp}"));
+        line(&format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+        if let Some(end) = date.checked_add_days(Days::new(1)) {
+            line(&format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+        }
+        line(&format!(
+            "SUMMARY:{}",
+            escape(&format!("Fantomen {number_str}/{year}"))
+        ));
+        if let Some(titles) = titles.get(id) {
+            line(&format!("DESCRIPTION:{}", escape(&titles.join(", "))));
+        }
+        line(&format!("URL:{url}"));
+        line("END:VEVENT");
+    }
+    line("END:VCALENDAR");
+    Builder::new()
+        .header(CONTENT_TYPE, "text/calendar; charset=utf-8")
+        .header(CONTENT_DISPOSITION, "inline; filename=\"fantomen.ics\"")
+        .body(ics.into())
+        .ise()
+}
+
+/// Escape a text value for iCalendar.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                result.push('\\');
+                result.push(c);
+            }
+            '\n' => result.push_str("\\n"),
+            '\r' => (),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Fold a content line to at most 75 octets per line.
+fn fold(line: &str) -> String {
+    let mut result = String::with_capacity(line.len() + line.len() / 37);
+    let mut len = 0;
+    for c in line.chars() {
+        if len + c.len_utf8() > 75 {
+            result.push_str("\r\n ");
+            len = 1;
+        }
+        result.push(c);
+        len += c.len_utf8();
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{escape, fold};
+
+    #[test]
+    fn escape_text() {
+        assert_eq!(
+            escape("Fantomen, Mandrake; a\\b"),
+            "Fantomen\\, Mandrake\\; a\\\\b"
+        );
+    }
+
+    #[test]
+    fn fold_long_line() {
+        let line = format!("DESCRIPTION:{}", "ö".repeat(40));
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
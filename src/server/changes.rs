@@ -0,0 +1,81 @@
+//! Changes to the data made by recent imports.
+use super::locale::lang;
+use super::{PgFilter, PgPool, Result, ViewError, goh, wrap};
+use crate::i18n::Lang;
+use crate::models::{Change, ChangeSummary, ImportRun};
+use crate::templates::{RenderRucte, changes_html, run_changes_html};
+use warp::filters::BoxedFilter;
+use warp::http::response::Builder;
+use warp::reply::Response;
+use warp::{self, Filter, Reply};
+
+/// Number of import runs listed on the changes page.
+const RUNS: i64 = 20;
+/// Max number of changes shown for one import run.
+const MAX_CHANGES: i64 = 1000;
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
+    use warp::path::{end, param, path};
+    let index = end().and(goh()).and(s.clone()).and(lang()).then(changes);
+    let run = param()
+        .and(end())
+        .and(goh())
+        .and(s)
+        .and(lang())
+        .then(run_changes);
+    path("changes").and(index.or(run).unify()).map(wrap).boxed()
+}
+
+async fn changes(db: PgPool, lang: Lang) -> Result<Response> {
+    let mut db = db.get().await?;
+    let mut runs = Vec::new();
+    for run in ImportRun::latest(RUNS, &mut db).await? {
+        let summary = ChangeSummary::for_run(&run, &mut db).await?;
+        runs.push((run, summary));
+    }
+    Ok(Builder::new().html(|o| changes_html(o, lang, &runs))?)
+}
+
+async fn run_changes(id: i32, db: PgPool, lang: Lang) -> Result<Response> {
+    let mut db = db.get().await?;
+    let run = ImportRun::get(id, &mut db)
+        .await?
+        .ok_or(ViewError::NotFound)?;
+    let summary = ChangeSummary::for_run(&run, &mut db).await?;
+    let changes = Change::for_run(&run, MAX_CHANGES, &mut db).await?;
+    let more = (summary.total() - MAX_CHANGES).max(0);
+    Ok(Builder::new().html(|o| {
+        run_changes_html(o, lang, &run, &summary, &changes, more)
+    })?)
+}
+
+/// Name of a table with recorded changes.
+pub fn table_name(lang: Lang, table: &str) -> &str {
+    lang.t(match table {
+        "issues" => "tidningar",
+        "titles" => "serier",
+        "episodes" => "episoder",
+        "refkeys" => "referenser",
+        other => other,
+    })
+}
+
+/// Description of a change action.
+pub fn action_name(lang: Lang, action: &str) -> &str {
+    lang.t(match action {
+        "insert" => "ny",
+        "update" => "ändrad",
+        "delete" => "borttagen",
+        other => other,
+    })
+}
+
+/// Description of a change action, for several rows.
+pub fn actions_name(lang: Lang, action: &str) -> &str {
+    lang.t(match action {
+        "insert" => "nya",
+        "update" => "ändrade",
+        "delete" => "borttagna",
+        other => other,
+    })
+}
@@ -0,0 +1,336 @@
+//! Chronology of the original run of daystrip and sunday page titles.
+use super::{PartsPublished, PgPool, Result, ViewError};
+use crate::i18n::{Lang, ToHtmlIn};
+use crate::models::{OrigDates, Title, TitleInfo};
+use crate::schema::episodes::dsl as e;
+use crate::schema::titles::dsl as t;
+use crate::templates::{RenderRucte, chronology_html};
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::io::{self, Write};
+use warp::http::response::Builder;
+use warp::reply::Response;
+
+pub async fn title_chronology(
+    db: PgPool,
+    slug: String,
+    lang: Lang,
+) -> Result<Response> {
+    let mut db = db.get().await?;
+    let title = t::titles
+        .filter(t::slug.eq(slug))
+        .first::<Title>(&mut db)
+        .await
+        .optional()?
+        .ok_or(ViewError::NotFound)?;
+    let info = TitleInfo::load(title.id, &mut db).await?;
+    let mut runs = Vec::new();
+    for sundays in [false, true] {
+        let run = Chronology::load(&title, sundays, &mut db).await?;
+        if !run.items.is_empty() {
+            runs.push(run);
+        }
+    }
+    if runs.is_empty() && !info.has_strips() {
+        return Err(ViewError::NotFound);
+    }
+    Ok(Builder::new().html(|o| chronology_html(o, lang, &title, &runs))?)
+}
+
+/// The known part of the original run of a title, either as daystrips
+/// or as sunday pages.
+pub struct Chronology {
+    pub sundays: bool,
+    pub items: Vec<ChronoItem>,
+}
+
+/// An episode in the chronology, with any gap or overlap before it.
+pub struct ChronoItem {
+    pub name: Option<String>,
+    pub span: Span,
+    pub strips: Option<(i32, i32)>,
+    pub published: PartsPublished,
+    /// Strips between the previous episode and this that are missing.
+    pub gap: Option<Span>,
+    /// Strips of this episode also included in a previous episode.
+    pub overlap: Option<Span>,
+}
+
+/// A range of strips (or sunday pages) by date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    sundays: bool,
+}
+
+impl Chronology {
+    async fn load(
+        title: &Title,
+        sundays: bool,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Chronology> {
+        let episodes = e::episodes
+            .select((
+                e::id,
+                e::name,
+                e::orig_date,
+                e::orig_to_date,
+                e::strip_from,
+                e::strip_to,
+            ))
+            .filter(e::title_id.eq(title.id))
+            .filter(e::orig_sundays.eq(sundays))
+            .filter(e::orig_to_date.is_not_null())
+            .order((e::orig_date, e::orig_to_date))
+            .load::<(
+                i32,
+                Option<String>,
+                Option<NaiveDate>,
+                Option<NaiveDate>,
+                Option<i32>,
+                Option<i32>,
+            )>(db)
+            .await?;
+        let ids = episodes.iter().map(|(id, ..)| *id).collect::<Vec<_>>();
+        let mut published = PartsPublished::for_episodes(&ids, db).await?;
+        let mut items = Vec::with_capacity(episodes.len());
+        let mut covered: Option<NaiveDate> = None;
+        for (id, name, from, to, strip_from, strip_to) in episodes {
+            let (Some(from), Some(to)) = (from, to) else {
+                continue;
+            };
+            let span = Span::new(from, to, sundays);
+            let (gap, overlap) = match covered {
+                Some(covered) => (
+                    Span::between(covered, from, sundays),
+                    (from <= covered)
+                        .then(|| Span::new(from, to.min(covered), sundays)),
+                ),
+                None => (None, None),
+            };
+            covered = Some(covered.map_or(to, |c| c.max(to)));
+            items.push(ChronoItem {
+                name,
+                span,
+                strips: strip_from.zip(strip_to),
+                published: published.remove(&id).unwrap_or_default(),
+                gap,
+                overlap,
+            });
+        }
+        Ok(Chronology { sundays, items })
+    }
+
+    pub fn kind(&self) -> &'static str {
+        if self.sundays {
+            "Söndagssidor"
+        } else {
+            "Dagstrippar"
+        }
+    }
+
+    /// Total span from the first to the last known strip.
+    pub fn span(&self) -> Option<Span> {
+        let first = self.items.iter().map(|i| i.span.from).min()?;
+        let last = self.items.iter().map(|i| i.span.to).max()?;
+        Some(Span::new(first, last, self.sundays))
+    }
+
+    /// Number of strips published, not counting overlaps twice.
+    pub fn n_published(&self) -> usize {
+        self.items.iter().map(|i| i.span.len()).sum::<usize>()
+            - self.n_overlapping()
+    }
+    pub fn n_missing(&self) -> usize {
+        self.items
+            .iter()
+            .filter_map(|i| i.gap)
+            .map(|s| s.len())
+            .sum()
+    }
+    pub fn n_overlapping(&self) -> usize {
+        self.items
+            .iter()
+            .filter_map(|i| i.overlap)
+            .map(|s| s.len())
+            .sum()
+    }
+    pub fn unit(&self) -> &'static str {
+        if self.sundays {
+            "söndagssidor"
+        } else {
+            "strippar"
+        }
+    }
+
+    pub fn bar(&self) -> ChronoBar<'_> {
+        ChronoBar(self)
+    }
+}
+
+impl Span {
+    fn new(from: NaiveDate, to: NaiveDate, sundays: bool) -> Span {
+        Span { from, to, sundays }
+    }
+
+    /// The strips strictly between `before` and `after`, if any.
+    fn between(
+        before: NaiveDate,
+        after: NaiveDate,
+        sundays: bool,
+    ) -> Option<Span> {
+        let from = next_strip(before, sundays);
+        let to = prev_strip(after, sundays);
+        (from <= to).then(|| Span::new(from, to, sundays))
+    }
+
+    /// The number of strips in this span.
+    pub fn len(&self) -> usize {
+        let days = usize::try_from((self.to - self.from).num_days())
+            .unwrap_or_default();
+        if self.sundays {
+            days / 7 + 1
+        } else {
+            // Count whole weeks, then the remaining days.
+            let mut n = days / 7 * 6;
+            let mut d = self.from;
+            for _ in 0..=days % 7 {
+                if d.weekday() != Weekday::Sun {
+                    n += 1;
+                }
+                d = d + Days::new(1);
+            }
+            n
+        }
+    }
+
+    pub fn dates(&self) -> OrigDates {
+        OrigDates::range(self.from, self.to, self.sundays)
+    }
+}
+
+fn next_strip(date: NaiveDate, sundays: bool) -> NaiveDate {
+    if sundays {
+        date + Days::new(7)
+    } else if date.weekday() == Weekday::Sat {
+        date + Days::new(2)
+    } else {
+        date + Days::new(1)
+    }
+}
+
+fn prev_strip(date: NaiveDate, sundays: bool) -> NaiveDate {
+    if sundays {
+        date - Days::new(7)
+    } else if date.weekday() == Weekday::Mon {
+        date - Days::new(2)
+    } else {
+        date - Days::new(1)
+    }
+}
+
+/// An svg bar showing published, missing and overlapping strips.
+pub struct ChronoBar<'a>(&'a Chronology);
+
+impl ToHtmlIn for ChronoBar<'_> {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
+        let Some(all) = self.0.span() else {
+            return Ok(());
+        };
+        let (w, h) = (800., 40.);
+        #[allow(clippy::cast_precision_loss)] // Days fits fine in f64.
+        let x = |d: NaiveDate| {
+            let total = (all.to - all.from).num_days().max(1) as f64;
+            (d - all.from).num_days() as f64 * w / total
+        };
+        let rect = |out: &mut dyn Write, class: &str, s: &Span| {
+            let (x0, x1) = (x(s.from), x(s.to));
+            write!(
+                out,
+                "<rect class='{class}' x='{x0:.1}' y='10' width='{:.1}' \
+                 height='20'><title>",
+                (x1 - x0).max(1.),
+            )?;
+            s.dates().to_html_in(lang, out)?;
+            write!(out, "</title></rect>")
+        };
+        write!(
+            out,
+            "<svg class='chronobar' viewBox='0 0 {w} {h}' role='img' \
+             aria-label='{}'>",
+            lang.text("Tidslinje för {}", &[&lang.t(self.0.kind())]),
+        )?;
+        for item in &self.0.items {
+            if let Some(gap) = &item.gap {
+                rect(out, "missing", gap)?;
+            }
+            rect(out, "published", &item.span)?;
+        }
+        for item in &self.0.items {
+            if let Some(overlap) = &item.overlap {
+                rect(out, "overlap", overlap)?;
+            }
+        }
+        for year in all.from.year() + 1..=all.to.year() {
+            if let Some(d) = NaiveDate::from_ymd_opt(year, 1, 1) {
+                write!(
+                    out,
+                    "<line x1='{x:.1}' x2='{x:.1}' y1='5' y2='35'/>\
+                     <text x='{x:.1}' y='40'>{year}</text>",
+                    x = x(d),
+                )?;
+            }
+        }
+        write!(out, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Span;
+    use chrono::NaiveDate;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn daystrip_len() {
+        // Monday to saturday the following week.
+        assert_eq!(
+            Span::new(date(1968, 1, 8), date(1968, 1, 20), false).len(),
+            12
+        );
+        assert_eq!(
+            Span::new(date(1968, 1, 8), date(1968, 1, 8), false).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn sunday_len() {
+        assert_eq!(
+            Span::new(date(1967, 2, 5), date(1967, 6, 25), true).len(),
+            21
+        );
+    }
+
+    #[test]
+    fn gaps() {
+        // Saturday to monday is no gap.
+        assert_eq!(
+            Span::between(date(1968, 4, 20), date(1968, 4, 22), false),
+            None
+        );
+        assert_eq!(
+            Span::between(date(1968, 4, 20), date(1968, 4, 24), false),
+            Some(Span::new(date(1968, 4, 22), date(1968, 4, 23), false)),
+        );
+        assert_eq!(
+            Span::between(date(1967, 6, 25), date(1967, 7, 9), true),
+            Some(Span::new(date(1967, 7, 2), date(1967, 7, 2), true)),
+        );
+    }
+}
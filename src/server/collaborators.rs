@@ -0,0 +1,396 @@
+//! Who has worked with whom, from the creators of episodes.
+//!
+//! Two creators collaborate when they are both credited for the same
+//! episode.  The network of collaborations is shown per creator, and
+//! can be exported as GraphML or JSON for analysis.
+use super::locale::lang;
+use super::{DbError, PgFilter, PgPool, Result, ViewError, ViewResult};
+use super::{goh, wrap};
+use crate::i18n::Lang;
+use crate::models::{Creator, CreatorSet, Role};
+use crate::schema::creator_aliases::dsl as ca;
+use crate::schema::creators::dsl as c;
+use crate::schema::episode_parts::dsl as ep;
+use crate::schema::episodes::dsl as e;
+use crate::schema::episodes_by::dsl as eb;
+use crate::schema::issues::dsl as i;
+use crate::schema::publications::dsl as p;
+use crate::templates::{RenderRucte, ToHtml, collaborators_html};
+use diesel::dsl::{max, min};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use warp::filters::BoxedFilter;
+use warp::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use warp::http::response::Builder;
+use warp::reply::{Response, json};
+use warp::{self, Filter, Reply};
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(Response,)> {
+    use warp::path::{end, param, path};
+    let graphml = path("collaborations.graphml")
+        .and(end())
+        .and(goh())
+        .and(s.clone())
+        .then(network_graphml);
+    let json = path("collaborations.json")
+        .and(end())
+        .and(goh())
+        .and(s.clone())
+        .then(network_json);
+    let one = param()
+        .and(path("collaborators"))
+        .and(end())
+        .and(goh())
+        .and(s)
+        .and(lang())
+        .then(collaborators);
+    graphml.or(json).unify().or(one).unify().map(wrap).boxed()
+}
+
+async fn collaborators(
+    slug: String,
+    db: PgPool,
+    lang: Lang,
+) -> Result<Response> {
+    let mut db = db.get().await?;
+    let creator = c::creators
+        .filter(c::slug.eq(slug))
+        .first::<Creator>(&mut db)
+        .await
+        .optional()?
+        .ok_or(ViewError::NotFound)?;
+    let episodes = eb::episodes_by
+        .inner_join(ca::creator_aliases)
+        .select(eb::episode_id)
+        .filter(ca::creator_id.eq(creator.id))
+        .distinct()
+        .load::<i32>(&mut db)
+        .await?;
+    let network = Network::load(&episodes, &mut db).await?;
+    let nouns = Role::all(&mut db)
+        .await?
+        .into_iter()
+        .map(|role| (role.code, role.noun))
+        .collect::<BTreeMap<_, _>>();
+    let collaborators = network.collaborators_of(creator.id, &nouns);
+    Ok(Builder::new().html(|o| {
+        collaborators_html(o, lang, &creator, episodes.len(), &collaborators)
+    })?)
+}
+
+async fn network_graphml(db: PgPool) -> Result<Response> {
+    let mut db = db.get().await?;
+    let network = Network::load_all(&mut db).await?;
+    let mut out = Vec::new();
+    network.write_graphml(&mut out).ise()?;
+    Builder::new()
+        .header(CONTENT_TYPE, "application/graphml+xml; charset=utf-8")
+        .header(
+            CONTENT_DISPOSITION,
+            "inline; filename=\"collaborations.graphml\"",
+        )
+        .body(out.into())
+        .ise()
+}
+
+async fn network_json(db: PgPool) -> Result<Response> {
+    let mut db = db.get().await?;
+    let network = Network::load_all(&mut db).await?;
+    Ok(json(&network.to_json()).into_response())
+}
+
+/// Collaborations between creators, from shared episodes.
+pub struct Network {
+    /// All creators, with their number of episodes.
+    creators: BTreeMap<i32, (Creator, usize)>,
+    /// Links between creators, keyed by the ids with the lowest first.
+    links: BTreeMap<(i32, i32), Link>,
+}
+
+#[derive(Default)]
+struct Link {
+    episodes: usize,
+    /// Number of episodes for each pair of roles, in key order.
+    roles: BTreeMap<(String, String), usize>,
+    years: Option<(i16, i16)>,
+}
+
+impl Network {
+    async fn load_all(db: &mut AsyncPgConnection) -> Result<Self, DbError> {
+        let episodes = e::episodes.select(e::id).load::<i32>(db).await?;
+        Network::load(&episodes, db).await
+    }
+
+    async fn load(
+        episodes: &[i32],
+        db: &mut AsyncPgConnection,
+    ) -> Result<Self, DbError> {
+        let sets = CreatorSet::for_episodes(episodes, db).await?;
+        let years = ep::episode_parts
+            .inner_join(p::publications.inner_join(i::issues))
+            .filter(ep::episode_id.eq_any(episodes))
+            .group_by(ep::episode_id)
+            .select((ep::episode_id, min(i::year), max(i::year)))
+            .load::<(i32, Option<i16>, Option<i16>)>(db)
+            .await?
+            .into_iter()
+            .filter_map(|(id, first, last)| Some((id, (first?, last?))))
+            .collect::<BTreeMap<_, _>>();
+        Ok(Network::build(&sets, &years))
+    }
+
+    fn build(
+        sets: &BTreeMap<i32, CreatorSet>,
+        years: &BTreeMap<i32, (i16, i16)>,
+    ) -> Self {
+        let mut creators = BTreeMap::new();
+        let mut links = BTreeMap::<_, Link>::new();
+        for (episode, set) in sets {
+            let mut roles = BTreeMap::<i32, BTreeSet<&str>>::new();
+            for (role, role_creators) in set.by_role() {
+                for creator in role_creators {
+                    roles.entry(creator.id).or_default().insert(role);
+                    creators
+                        .entry(creator.id)
+                        .or_insert_with(|| (creator.clone(), 0));
+                }
+            }
+            for id in roles.keys() {
+                if let Some((_, n)) = creators.get_mut(id) {
+                    *n += 1;
+                }
+            }
+            let years = years.get(episode).copied();
+            for (a, a_roles) in &roles {
+                for (b, b_roles) in roles.range(a + 1..) {
+                    let link = links.entry((*a, *b)).or_default();
+                    link.episodes += 1;
+                    for a_role in a_roles {
+                        for b_role in b_roles {
+                            *link
+                                .roles
+                                .entry((
+                                    a_role.to_string(),
+                                    b_role.to_string(),
+                                ))
+                                .or_default() += 1;
+                        }
+                    }
+                    link.years = match (link.years, years) {
+                        (Some((f1, l1)), Some((f2, l2))) => {
+                            Some((f1.min(f2), l1.max(l2)))
+                        }
+                        (y1, y2) => y1.or(y2),
+                    };
+                }
+            }
+        }
+        Network { creators, links }
+    }
+
+    /// The collaborators of a creator, most frequent first.
+    ///
+    /// Roles are given as the nouns in `nouns`, with the role of the
+    /// given creator first in each pair.
+    fn collaborators_of(
+        &self,
+        id: i32,
+        nouns: &BTreeMap<String, String>,
+    ) -> Vec<Collaborator> {
+        let noun = |code: &str| {
+            nouns.get(code).cloned().unwrap_or_else(|| code.to_string())
+        };
+        let mut result = self
+            .links
+            .iter()
+            .filter_map(|(&(a, b), link)| {
+                let (other, flip) = match (a == id, b == id) {
+                    (true, _) => (b, false),
+                    (_, true) => (a, true),
+                    _ => return None,
+                };
+                let (creator, _) = self.creators.get(&other)?;
+                let mut roles = link
+                    .roles
+                    .iter()
+                    .map(|((ra, rb), n)| {
+                        let (own, their) =
+                            if flip { (rb, ra) } else { (ra, rb) };
+                        (noun(own), noun(their), *n)
+                    })
+                    .collect::<Vec<_>>();
+                roles.sort_by_key(|(_, _, n)| Reverse(*n));
+                Some(Collaborator {
+                    creator: creator.clone(),
+                    episodes: link.episodes,
+                    roles,
+                    years: link.years,
+                })
+            })
+            .collect::<Vec<_>>();
+        result.sort_by(|a, b| {
+            b.episodes
+                .cmp(&a.episodes)
+                .then_with(|| a.creator.name.cmp(&b.creator.name))
+        });
+        result
+    }
+
+    fn write_graphml(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            out,
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">"
+        )?;
+        for (id, owner, name, kind) in [
+            ("name", "node", "name", "string"),
+            ("episodes", "node", "episodes", "int"),
+            ("weight", "edge", "weight", "int"),
+            ("first", "edge", "first_year", "int"),
+            ("last", "edge", "last_year", "int"),
+        ] {
+            writeln!(
+                out,
+                "  <key id=\"{id}\" for=\"{owner}\" attr.name=\"{name}\" \
+                 attr.type=\"{kind}\"/>"
+            )?;
+        }
+        writeln!(
+            out,
+            "  <graph id=\"collaborations\" edgedefault=\"undirected\">"
+        )?;
+        for (creator, episodes) in self.creators.values() {
+            write!(out, "    <node id=\"")?;
+            creator.slug.to_html(out)?;
+            write!(out, "\"><data key=\"name\">")?;
+            creator.name.to_html(out)?;
+            writeln!(
+                out,
+                "</data><data key=\"episodes\">{episodes}</data></node>"
+            )?;
+        }
+        for ((a, b), link) in &self.links {
+            let (Some((a, _)), Some((b, _))) =
+                (self.creators.get(a), self.creators.get(b))
+            else {
+                continue;
+            };
+            write!(out, "    <edge source=\"")?;
+            a.slug.to_html(out)?;
+            write!(out, "\" target=\"")?;
+            b.slug.to_html(out)?;
+            write!(out, "\"><data key=\"weight\">{}</data>", link.episodes)?;
+            if let Some((first, last)) = link.years {
+                write!(
+                    out,
+                    "<data key=\"first\">{first}</data>\
+                     <data key=\"last\">{last}</data>"
+                )?;
+            }
+            writeln!(out, "</edge>")?;
+        }
+        writeln!(out, "  </graph>")?;
+        writeln!(out, "</graphml>")
+    }
+
+    fn to_json(&self) -> JsonNetwork<'_> {
+        let slug = |id| self.creators.get(id).map(|(c, _)| c.slug.as_str());
+        JsonNetwork {
+            nodes: self
+                .creators
+                .values()
+                .map(|(creator, episodes)| JsonNode {
+                    id: &creator.slug,
+                    name: &creator.name,
+                    episodes: *episodes,
+                })
+                .collect(),
+            links: self
+                .links
+                .iter()
+                .filter_map(|((a, b), link)| {
+                    Some(JsonLink {
+                        source: slug(a)?,
+                        target: slug(b)?,
+                        weight: link.episodes,
+                        first_year: link.years.map(|y| y.0),
+                        last_year: link.years.map(|y| y.1),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A creator that has worked with another creator.
+pub struct Collaborator {
+    pub creator: Creator,
+    /// Number of shared episodes.
+    pub episodes: usize,
+    /// Pairs of own and other role, with number of episodes.
+    pub roles: Vec<(String, String, usize)>,
+    /// First and last year of the shared episodes.
+    pub years: Option<(i16, i16)>,
+}
+
+#[derive(Serialize)]
+struct JsonNetwork<'a> {
+    nodes: Vec<JsonNode<'a>>,
+    links: Vec<JsonLink<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    id: &'a str,
+    name: &'a str,
+    episodes: usize,
+}
+
+#[derive(Serialize)]
+struct JsonLink<'a> {
+    source: &'a str,
+    target: &'a str,
+    weight: usize,
+    first_year: Option<i16>,
+    last_year: Option<i16>,
+}
+
+#[test]
+fn graphml_escaped() {
+    let creator = |id, name: &str, slug: &str| Creator {
+        id,
+        name: name.into(),
+        slug: slug.into(),
+    };
+    let network = Network {
+        creators: [
+            (1, (creator(1, "Lee Falk", "lee-falk"), 2)),
+            (2, (creator(2, "Sy <Barry> & co", "sy-barry"), 1)),
+        ]
+        .into(),
+        links: [(
+            (1, 2),
+            Link {
+                episodes: 1,
+                years: Some((1970, 1995)),
+                ..Link::default()
+            },
+        )]
+        .into(),
+    };
+    let mut out = Vec::new();
+    network.write_graphml(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(
+        out.contains("<data key=\"name\">Sy &lt;Barry&gt; &amp; co</data>")
+    );
+    assert!(out.contains(
+        "<edge source=\"lee-falk\" target=\"sy-barry\">\
+         <data key=\"weight\">1</data><data key=\"first\">1970</data>\
+         <data key=\"last\">1995</data></edge>"
+    ));
+}
@@ -0,0 +1,297 @@
+//! Compare two titles, creators or refkeys side by side.
+use super::locale::lang;
+use super::search::SearchQuery;
+use super::{DbError, PgFilter, PgPool, Result, ViewError, goh, wrap};
+use crate::i18n::{Lang, ToHtmlIn};
+use crate::models::{IssueRef, Title};
+use crate::schema::episode_parts::dsl as ep;
+use crate::schema::episodes::dsl as e;
+use crate::schema::issues::dsl as i;
+use crate::schema::publications::dsl as p;
+use crate::schema::titles::dsl as t;
+use crate::templates::{RenderRucte, ToHtml, compare_html};
+use diesel::dsl::min;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use warp::filters::BoxedFilter;
+use warp::http::response::Builder;
+use warp::reply::Response;
+use warp::{self, Filter, Reply};
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
+    use warp::path::{end, path};
+    use warp::query;
+    path("compare")
+        .and(end())
+        .and(query())
+        .and(goh())
+        .and(s)
+        .and(lang())
+        .then(compare)
+        .map(wrap)
+        .boxed()
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CompareQuery {
+    #[serde(default)]
+    pub a: String,
+    #[serde(default)]
+    pub b: String,
+}
+
+async fn compare(
+    query: CompareQuery,
+    db: PgPool,
+    lang: Lang,
+) -> Result<Response> {
+    let mut db = db.get().await?;
+    let comparison = if query.a.is_empty() || query.b.is_empty() {
+        None
+    } else {
+        let a = Side::load(&query.a, &mut db).await?;
+        let b = Side::load(&query.b, &mut db).await?;
+        Some(Comparison::load(a, b, &mut db).await?)
+    };
+    Ok(Builder::new()
+        .html(|o| compare_html(o, lang, &query, comparison.as_ref()))?)
+}
+
+/// One of the things to compare, and its episodes.
+pub struct Side {
+    query: SearchQuery,
+    episodes: BTreeSet<i32>,
+}
+
+impl Side {
+    /// Load a side given as `t:slug`, `p:slug`, `k:slug` or `f:slug`.
+    ///
+    /// The letters are the same as in the search query.
+    async fn load(spec: &str, db: &mut AsyncPgConnection) -> Result<Self> {
+        let (kind, slug) = spec
+            .split_once(':')
+            .filter(|(kind, _)| matches!(*kind, "t" | "p" | "k" | "f"))
+            .ok_or(ViewError::NotFound)?;
+        let query = SearchQuery::load(vec![(kind.into(), slug.into())], db)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => ViewError::NotFound,
+                e => e.into(),
+            })?;
+        let episodes = query
+            .filtered_episodes()
+            .select(e::id)
+            .load::<i32>(db)
+            .await?
+            .into_iter()
+            .collect();
+        Ok(Side { query, episodes })
+    }
+
+    pub fn n_episodes(&self) -> usize {
+        self.episodes.len()
+    }
+
+    /// The name of the title, creator or refkey, as plain text.
+    pub fn name(&self, lang: Lang) -> String {
+        let q = &self.query;
+        match (q.t.as_slice(), q.p.as_slice(), q.k.as_slice()) {
+            ([title, ..], _, _) => title.title.clone(),
+            (_, [creator, ..], _) => creator.name.clone(),
+            (_, _, [key, ..]) => key.refkey.name_in(lang),
+            _ => String::new(),
+        }
+    }
+}
+
+impl ToHtmlIn for Side {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
+        let q = &self.query;
+        match (q.t.as_slice(), q.p.as_slice(), q.k.as_slice()) {
+            ([title, ..], _, _) => {
+                write!(out, "<a href=\"/titles/{}\">", title.slug)?;
+                title.title.to_html(out)?;
+                write!(out, "</a>")
+            }
+            (_, [creator, ..], _) => creator.to_html(out),
+            (_, _, [key, ..]) => key.refkey.to_html_in(lang, out),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Two sides, with their shared and exclusive episodes.
+pub struct Comparison {
+    pub a: Side,
+    pub b: Side,
+    /// Number of episodes published each year, for a, b and both.
+    pub years: Vec<(i16, usize, usize, usize)>,
+    pub shared: Vec<ShortEpisode>,
+    pub only_a: Vec<ShortEpisode>,
+    pub only_b: Vec<ShortEpisode>,
+}
+
+impl Comparison {
+    async fn load(
+        a: Side,
+        b: Side,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Self> {
+        let all = a.episodes.union(&b.episodes).copied().collect::<Vec<_>>();
+        let mut per_year = BTreeMap::<i16, (usize, usize, usize)>::new();
+        for (year, episode) in i::issues
+            .inner_join(p::publications.inner_join(ep::episode_parts))
+            .select((i::year, ep::episode_id))
+            .filter(ep::episode_id.eq_any(&all))
+            .distinct()
+            .load::<(i16, i32)>(db)
+            .await?
+        {
+            let (in_a, in_b) = (
+                a.episodes.contains(&episode),
+                b.episodes.contains(&episode),
+            );
+            let count = per_year.entry(year).or_default();
+            count.0 += usize::from(in_a);
+            count.1 += usize::from(in_b);
+            count.2 += usize::from(in_a && in_b);
+        }
+        let years =
+            match (per_year.first_key_value(), per_year.last_key_value()) {
+                (Some((first, _)), Some((last, _))) => (*first..=*last)
+                    .map(|year| {
+                        let (a, b, both) =
+                            per_year.get(&year).copied().unwrap_or_default();
+                        (year, a, b, both)
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+
+        let mut episodes = e::episodes
+            .inner_join(t::titles)
+            .select((
+                e::id,
+                Title::as_select(),
+                e::name,
+                i::issues
+                    .inner_join(p::publications.inner_join(ep::episode_parts))
+                    .select(min(i::magic))
+                    .filter(ep::episode_id.eq(e::id))
+                    .single_value(),
+            ))
+            .filter(e::id.eq_any(&all))
+            .load::<(i32, Title, Option<String>, Option<i16>)>(db)
+            .await?;
+        episodes.sort_by_key(|(.., first)| *first);
+        let (mut shared, mut only_a, mut only_b) = (vec![], vec![], vec![]);
+        for (id, title, name, first) in episodes {
+            let episode = ShortEpisode {
+                title,
+                name,
+                first: first.map(IssueRef::from_magic),
+            };
+            match (a.episodes.contains(&id), b.episodes.contains(&id)) {
+                (true, true) => shared.push(episode),
+                (true, false) => only_a.push(episode),
+                _ => only_b.push(episode),
+            }
+        }
+        Ok(Comparison {
+            a,
+            b,
+            years,
+            shared,
+            only_a,
+            only_b,
+        })
+    }
+
+    pub fn chart(&self) -> CompareChart<'_> {
+        CompareChart(&self.years)
+    }
+}
+
+/// An episode, as listed in a comparison.
+pub struct ShortEpisode {
+    pub title: Title,
+    pub name: Option<String>,
+    /// The first issue the episode was published in.
+    pub first: Option<IssueRef>,
+}
+
+/// An svg bar chart of episodes per year, with a above and b below.
+pub struct CompareChart<'a>(&'a [(i16, usize, usize, usize)]);
+
+impl ToHtmlIn for CompareChart<'_> {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
+        let (Some(first), Some(last)) = (self.0.first(), self.0.last())
+        else {
+            return Ok(());
+        };
+        let (w, h, margin) = (800., 300., 20.);
+        let (y0, y1) = (first.0, last.0 + 1);
+        let max = self
+            .0
+            .iter()
+            .map(|(_, a, b, _)| *a.max(b))
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        let bar = (w - 2. * margin) / f64::from(y1 - y0);
+        let x = |year: i16| margin + f64::from(year - y0) * bar;
+        let mid = h / 2.;
+        #[allow(clippy::cast_precision_loss)]
+        let len = |n: usize| n as f64 * (mid - margin) / max as f64;
+
+        write!(
+            out,
+            "<svg class='comparechart' viewBox='0 0 {w} {h}' role='img' \
+             aria-label='{}'>",
+            lang.t("Episoder per år"),
+        )?;
+        for year in (y0..y1).filter(|y| y % 10 == 0) {
+            write!(
+                out,
+                "<line class='grid' x1='{x:.1}' x2='{x:.1}' y1='{m}' \
+                 y2='{b}'/><text class='xlabel' x='{x:.1}' y='{t}'>\
+                 {year}</text>",
+                x = x(year),
+                m = margin,
+                b = h - margin,
+                t = h - 4.,
+            )?;
+        }
+        for (year, a, b, _) in self.0 {
+            let x = x(*year);
+            let bw = (bar - 1.).max(0.5);
+            if *a > 0 {
+                write!(
+                    out,
+                    "<rect class='a' x='{x:.1}' y='{y:.1}' width='{bw:.1}' \
+                     height='{l:.1}'><title>{year}: {a}</title></rect>",
+                    y = mid - len(*a),
+                    l = len(*a),
+                )?;
+            }
+            if *b > 0 {
+                write!(
+                    out,
+                    "<rect class='b' x='{x:.1}' y='{mid:.1}' \
+                     width='{bw:.1}' height='{l:.1}'>\
+                     <title>{year}: {b}</title></rect>",
+                    l = len(*b),
+                )?;
+            }
+        }
+        write!(
+            out,
+            "<line class='axis' x1='{margin}' x2='{x1}' y1='{mid}' \
+             y2='{mid}'/></svg>",
+            x1 = w - margin,
+        )
+    }
+}
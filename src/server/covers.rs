@@ -1,4 +1,4 @@
-use super::{PgPool, Result, ViewError, redirect};
+use super::{PgPool, Result, ViewError, metrics, redirect};
 use crate::schema::covers::dsl as c;
 use crate::schema::issues::dsl as i;
 use crate::templates::statics::xcover_jpg;
@@ -24,6 +24,7 @@ pub async fn cover_image(issue: CoverRef, db: PgPool) -> Result<impl Reply> {
         .await
         .optional()?;
 
+    metrics::cover(data.is_some());
     if let Some(data) = data {
         let medium_expires = Utc::now() + Duration::days(90);
         Ok(Response::builder()
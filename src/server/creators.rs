@@ -1,10 +1,13 @@
+use super::locale::lang;
+use super::meta::{self, PageMeta};
 use super::{
     DbError, FullArticle, FullEpisode, OtherContribs, PgFilter, PgPool,
-    Result, ViewError, goh, redirect, wrap,
+    Result, Timeline, ViewError, goh, redirect, wrap,
 };
+use crate::i18n::Lang;
 use crate::models::creator_contributions::CreatorContributions;
 use crate::models::{
-    Article, Creator, CreatorSet, Episode, IssueRef, RefKey, Title,
+    Article, Creator, CreatorInfo, Episode, IssueRef, RefKey, Title,
 };
 use crate::schema::article_refkeys::dsl as ar;
 use crate::schema::articles::dsl as a;
@@ -18,11 +21,13 @@ use crate::schema::episodes_by::dsl as eb;
 use crate::schema::issues::dsl as i;
 use crate::schema::publications::dsl as p;
 use crate::schema::refkeys::dsl as r;
+use crate::schema::roles::dsl as ro;
 use crate::schema::titles::dsl as t;
 use crate::templates::{RenderRucte, creator_html, creators_html};
 use diesel::dsl::min;
 use diesel::prelude::*;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::json;
 use tracing::{debug, info, instrument};
 use warp::filters::BoxedFilter;
 use warp::http::response::Builder;
@@ -31,12 +36,21 @@ use warp::{self, Filter, Reply};
 
 pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
     use warp::path::{end, param};
-    let list = goh().and(end()).and(s.clone()).then(list_creators);
-    let one = goh().and(s).and(param()).and(end()).then(one_creator);
+    let list = goh()
+        .and(end())
+        .and(s.clone())
+        .and(lang())
+        .then(list_creators);
+    let one = goh()
+        .and(s)
+        .and(param())
+        .and(end())
+        .and(lang())
+        .then(one_creator);
     list.or(one).unify().map(wrap).boxed()
 }
 
-async fn list_creators(db: PgPool) -> Result<Response> {
+async fn list_creators(db: PgPool, lang: Lang) -> Result<Response> {
     use crate::models::creator_contributions::creator_contributions::dsl as cc;
     let mut db = db.get().await?;
     let all = cc::creator_contributions
@@ -51,11 +65,15 @@ async fn list_creators(db: PgPool) -> Result<Response> {
         ))
         .load::<CreatorContributions>(&mut db)
         .await?;
-    Ok(Builder::new().html(|o| creators_html(o, &all))?)
+    Ok(Builder::new().html(|o| creators_html(o, lang, &all))?)
 }
 
 #[instrument(skip(db), err)]
-async fn one_creator(db: PgPool, slug: String) -> Result<Response> {
+async fn one_creator(
+    db: PgPool,
+    slug: String,
+    lang: Lang,
+) -> Result<Response> {
     let mut db = db.get().await?;
     let creator = c::creators
         .filter(c::slug.eq(slug.clone()))
@@ -108,6 +126,7 @@ async fn one_creator(db: PgPool, slug: String) -> Result<Response> {
         .filter(
             e::id.eq_any(
                 eb::episodes_by
+                    .inner_join(ro::roles)
                     .select(eb::episode_id)
                     .filter(
                         eb::creator_alias_id.eq_any(
@@ -116,7 +135,7 @@ async fn one_creator(db: PgPool, slug: String) -> Result<Response> {
                                 .filter(ca::creator_id.eq(creator.id)),
                         ),
                     )
-                    .filter(eb::role.eq_any(CreatorSet::MAIN_ROLES)),
+                    .filter(ro::main),
             ),
         )
         .order(
@@ -157,18 +176,79 @@ async fn one_creator(db: PgPool, slug: String) -> Result<Response> {
 
     let covers = CoverSet::by(&creator, &mut db).await?;
     let others = OtherContribs::for_creator(&creator, &mut db).await?;
+    let info = CreatorInfo::load(creator.id, &mut db).await?;
+    let timeline = Timeline::for_creator(creator.id, &mut db).await?;
+    let meta = creator_meta(&creator, &info, &covers);
 
-    Ok(Builder::new().html(|o| {
-        creator_html(
-            o,
-            &creator,
-            &about,
-            &covers,
-            &main_episodes,
-            &articles_by,
-            &others,
-        )
-    })?)
+    let page = CreatorPage {
+        meta,
+        creator,
+        info,
+        about,
+        covers,
+        timeline,
+        episodes: main_episodes,
+        articles_by,
+        other: others,
+    };
+    Ok(Builder::new().html(|o| creator_html(o, lang, &page))?)
+}
+
+/// Everything shown on the page for a creator.
+pub struct CreatorPage {
+    pub meta: PageMeta,
+    pub creator: Creator,
+    pub info: CreatorInfo,
+    /// Articles about the creator, with the issues they are in.
+    pub about: Vec<(FullArticle, Vec<IssueRef>)>,
+    pub covers: CoverSet,
+    pub timeline: Timeline,
+    /// Episodes where the creator has a main role.
+    pub episodes: Vec<(Title, FullEpisode)>,
+    /// Articles by the creator, with the issues they are in.
+    pub articles_by: Vec<(FullArticle, Vec<IssueRef>)>,
+    pub other: OtherContribs,
+}
+
+/// Page metadata for a creator, with schema.org `Person` data.
+///
+/// A cover by the creator, if any, is used as preview image.
+fn creator_meta(
+    creator: &Creator,
+    info: &CreatorInfo,
+    covers: &CoverSet,
+) -> PageMeta {
+    let url = format!("/who/{}", creator.slug);
+    let mut data = meta::person(creator);
+    data["@context"] = "https://schema.org".into();
+    if let Some(born) = info.born {
+        data["birthDate"] = born.to_string().into();
+    }
+    if let Some(died) = info.died {
+        data["deathDate"] = died.to_string().into();
+    }
+    if let Some(nationality) = &info.nationality {
+        data["nationality"] = nationality.as_str().into();
+    }
+    if let Some(bio) = &info.bio {
+        data["description"] = bio.as_str().into();
+    }
+    let links = info.links();
+    if !links.is_empty() {
+        data["sameAs"] =
+            json!(links.into_iter().map(|(_, url)| url).collect::<Vec<_>>());
+    }
+    PageMeta {
+        kind: "profile",
+        image: covers
+            .best
+            .iter()
+            .chain(&covers.all)
+            .next()
+            .map(|(issue, _)| issue.cover_url()),
+        url: Some(url),
+        json_ld: Some(data),
+    }
 }
 
 pub struct CoverSet {
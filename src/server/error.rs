@@ -1,3 +1,4 @@
+use crate::i18n::Lang;
 use crate::templates::{RenderError, RenderRucte, error_html, notfound_html};
 use diesel_async::pooled_connection::deadpool::PoolError;
 use std::fmt;
@@ -7,7 +8,7 @@ use warp::http::status::StatusCode;
 use warp::reply::Response;
 use warp::{self, Rejection, Reply};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum ViewError {
     /// 404
     NotFound,
@@ -44,49 +45,75 @@ where
     }
 }
 
+/// The response for an error is rendered by [`localize`], when the
+/// language is known.
 impl Reply for ViewError {
     fn into_response(self) -> Response {
-        match self {
-            ViewError::NotFound => {
-                let code = StatusCode::NOT_FOUND;
-                Builder::new()
-                    .status(code)
-                    .html(|o| notfound_html(o, code))
-                    .unwrap()
-            }
-            ViewError::ServiceUnavailable => error_response(
-                StatusCode::SERVICE_UNAVAILABLE,
-                "Servern är överlastad",
-                "Fantomen vilar först då fred råder i världen. \
-                 Den här webbservern verkar dock behöva lite vila just nu. \
-                 Försök gärna igen om ett litet tag.",
-            ),
-            ViewError::Err(msg) => error_response(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                msg,
-                "Något gick snett. \
-                 Detta missöde är noterat i webserverns krönika. \
-                 Du kanske kan försöka igen om en stund? \
-                 Eller gå tillbaks till \
-                 <a href='/'>fantomenindexets förstasida</a>? \
-                 Om det fortfarande verkar trasigt så får du gärna rapportera \
-                 felet till \
-                 <a href='mailto:rasmus@krats.se'>rasmus@krats.se</a>.",
-            ),
-        }
+        let code = match self {
+            ViewError::NotFound => StatusCode::NOT_FOUND,
+            ViewError::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            ViewError::Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let mut response = code.into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+/// Render the page for an error response in the language of the request.
+///
+/// Other responses are returned as they are.
+pub fn localize(lang: Lang, reply: impl Reply) -> Response {
+    let response = reply.into_response();
+    let Some(err) = response.extensions().get::<ViewError>() else {
+        return response;
+    };
+    let code = response.status();
+    match err {
+        ViewError::NotFound => Builder::new()
+            .status(code)
+            .html(|o| notfound_html(o, lang, code))
+            .unwrap(),
+        ViewError::ServiceUnavailable => error_response(
+            lang,
+            code,
+            "Servern är överlastad",
+            "Fantomen vilar först då fred råder i världen. \
+             Den här webbservern verkar dock behöva lite vila just nu. \
+             Försök gärna igen om ett litet tag.",
+        ),
+        ViewError::Err(msg) => error_response(
+            lang,
+            code,
+            msg,
+            "Något gick snett. \
+             Detta missöde är noterat i webserverns krönika. \
+             Du kanske kan försöka igen om en stund? \
+             Eller gå tillbaks till \
+             <a href='/'>fantomenindexets förstasida</a>? \
+             Om det fortfarande verkar trasigt så får du gärna rapportera \
+             felet till \
+             <a href='mailto:rasmus@krats.se'>rasmus@krats.se</a>.",
+        ),
     }
 }
 
-fn error_response(code: StatusCode, message: &str, detail: &str) -> Response {
+fn error_response(
+    lang: Lang,
+    code: StatusCode,
+    message: &str,
+    detail: &'static str,
+) -> Response {
     Builder::new()
         .status(code)
-        .html(|o| error_html(o, code, message, detail))
+        .html(|o| error_html(o, lang, code, lang.t(message), lang.t(detail)))
         .unwrap()
 }
 
 impl From<RenderError> for ViewError {
     fn from(e: RenderError) -> Self {
         error!("Rendering error: {}\n    {:?}", e, e);
+        super::metrics::render_error();
         ViewError::Err("Renderingsfel")
     }
 }
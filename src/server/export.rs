@@ -0,0 +1,61 @@
+//! The full index as a zip of csv files, as by `fanrs export --zip`.
+use super::{PgFilter, PgPool, Result, ViewResult, goh, wrap};
+use crate::export::Export;
+use crate::models::ImportRun;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+use tokio::task::spawn_blocking;
+use warp::filters::BoxedFilter;
+use warp::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use warp::http::response::Builder;
+use warp::reply::Response;
+use warp::{self, Filter, Reply};
+
+/// The latest import run when a zip was built, by id and finish time.
+type RunKey = Option<(i32, Option<DateTime<Utc>>)>;
+
+/// The last built zip, reused until there is a new import run.
+///
+/// The lock also makes sure only one zip is built at a time.
+static CACHE: Mutex<Option<(RunKey, Bytes)>> = Mutex::const_new(None);
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
+    use warp::path::{end, path};
+    path("export.zip")
+        .and(end())
+        .and(goh())
+        .and(s)
+        .then(export_zip)
+        .map(wrap)
+        .boxed()
+}
+
+async fn export_zip(db: PgPool) -> Result<Response> {
+    let mut db = db.get().await?;
+    let key = ImportRun::latest(1, &mut db)
+        .await?
+        .pop()
+        .map(|run| (run.id, run.finished));
+    let mut cache = CACHE.lock().await;
+    let zip = match &*cache {
+        Some((built, zip)) if *built == key => zip.clone(),
+        _ => {
+            let export = Export::load(&mut db).await?;
+            let zip =
+                spawn_blocking(move || export.to_zip()).await.ise()?.ise()?;
+            let zip = Bytes::from(zip);
+            *cache = Some((key, zip.clone()));
+            zip
+        }
+    };
+    drop(cache);
+    Builder::new()
+        .header(CONTENT_TYPE, "application/zip")
+        .header(
+            CONTENT_DISPOSITION,
+            "attachment; filename=\"fantomenindex.zip\"",
+        )
+        .body(zip.into())
+        .ise()
+}
@@ -0,0 +1,400 @@
+//! A structured form for the contents of an issue.
+//!
+//! Each publication in an issue (an episode, a text or the cover) is a
+//! row with fields for title, episode, part, best placement, creators
+//! and refs.  Child elements without a field of their own, such as
+//! `prevpub` or `teaser`, are kept as xml in the row, and other content
+//! of the issue is kept as rows of plain xml.
+use crate::models::Role;
+use crate::yearfile::xml_escape;
+use anyhow::Result;
+use roxmltree::{Document, Node, NodeType};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The contents of an issue, as edited in the admin form.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct IssueForm {
+    /// The attributes of the issue element, such as `nr` and `pages`.
+    pub attrs: Vec<(String, String)>,
+    pub items: Vec<Item>,
+}
+
+/// A row of the issue form.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Item {
+    /// `serie`, `text` or `omslag`, or `xml` for other content.
+    ///
+    /// An item without kind is left out of the issue.
+    pub kind: String,
+    pub title: String,
+    /// The episode name of a serie, or the subtitle of a text.
+    pub episode: String,
+    pub part_no: String,
+    pub part_name: String,
+    pub best: String,
+    /// Role and name of each creator.
+    pub by: Vec<(String, String)>,
+    /// Kind (`fa`, `key`, `who` or `serie`) and text of each ref.
+    pub refs: Vec<(String, String)>,
+    /// Child elements without fields, or all xml of an `xml` item.
+    pub extra: String,
+}
+
+/// The kinds of items, with labels for the form.
+pub const KINDS: &[(&str, &str)] = &[
+    ("serie", "Serie"),
+    ("text", "Artikel"),
+    ("omslag", "Omslag"),
+    ("xml", "Xml"),
+    ("", "(ingen)"),
+];
+
+/// The kinds of refs, with labels for the form.
+pub const REF_KINDS: &[(&str, &str)] = &[
+    ("key", "Företeelse"),
+    ("fa", "Fantom"),
+    ("who", "Serieskapare"),
+    ("serie", "Serie"),
+];
+
+impl IssueForm {
+    /// Read the form from the xml source of an issue element.
+    pub fn from_xml(xml: &str) -> Result<IssueForm> {
+        let doc = Document::parse(xml)?;
+        let root = doc.root_element();
+        let attrs = root
+            .attributes()
+            .map(|a| (a.name().into(), a.value().into()))
+            .collect();
+        let items = root
+            .children()
+            .filter(|n| !is_blank(n))
+            .map(|n| match n.tag_name().name() {
+                kind @ ("serie" | "text" | "omslag") if n.is_element() => {
+                    Item::from_xml(kind, n, xml)
+                }
+                _ => Item {
+                    kind: "xml".into(),
+                    extra: xml[n.range()].into(),
+                    ..Item::default()
+                },
+            })
+            .collect();
+        Ok(IssueForm { attrs, items })
+    }
+
+    /// Read the form from posted fields.
+    ///
+    /// Fields of an item are named by field and row number, such as
+    /// `title.3`.  Items without kind, and creators and refs without
+    /// text, are left out.
+    pub fn from_form(fields: Vec<(String, String)>) -> IssueForm {
+        let mut attrs = Vec::new();
+        let mut items = BTreeMap::<usize, Item>::new();
+        for (name, value) in fields {
+            let value = value.trim().to_string();
+            let Some((field, row)) = name.split_once('.') else {
+                continue;
+            };
+            if field == "attr" {
+                attrs.push((row.to_string(), value));
+                continue;
+            }
+            let Ok(row) = row.parse() else {
+                continue;
+            };
+            let item = items.entry(row).or_default();
+            match field {
+                "kind" => item.kind = value,
+                "title" => item.title = value,
+                "episode" => item.episode = value,
+                "part_no" => item.part_no = value,
+                "part_name" => item.part_name = value,
+                "best" => item.best = value,
+                "extra" => item.extra = value,
+                // Each role is posted before the name it belongs to.
+                "by_role" => item.by.push((value, String::new())),
+                "by_name" => set_last(&mut item.by, value),
+                "ref_kind" => item.refs.push((value, String::new())),
+                "ref_text" => set_last(&mut item.refs, value),
+                _ => (),
+            }
+        }
+        let items = items
+            .into_values()
+            .filter(|item| !item.kind.is_empty())
+            .map(|mut item| {
+                item.by.retain(|(_, name)| !name.is_empty());
+                item.refs.retain(|(_, text)| !text.is_empty());
+                item
+            })
+            .collect();
+        IssueForm { attrs, items }
+    }
+
+    /// Add an empty item, and an empty creator and ref to each item.
+    ///
+    /// Missing common attributes are also added, without value.
+    pub fn with_blanks(mut self) -> IssueForm {
+        for name in ["pages", "price", "date"] {
+            if !self.attrs.iter().any(|(n, _)| n == name) {
+                self.attrs.push((name.into(), String::new()));
+            }
+        }
+        self.items.push(Item::default());
+        for item in &mut self.items {
+            item.by.push((Role::DEFAULT.into(), String::new()));
+            item.refs.push((REF_KINDS[0].0.into(), String::new()));
+        }
+        self
+    }
+
+    /// The issue element, formatted as in the data files.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<issue");
+        for (name, value) in self.attrs.iter().filter(|(_, v)| !v.is_empty())
+        {
+            let _ = write!(xml, " {name}=\"{}\"", xml_escape(value));
+        }
+        let items = self.items.iter().filter(|item| !item.kind.is_empty());
+        let mut items = items.peekable();
+        if items.peek().is_none() {
+            xml.push_str("/>");
+            return xml;
+        }
+        xml.push('>');
+        for item in items {
+            item.write_xml(&mut xml);
+        }
+        xml.push_str("\n  </issue>");
+        xml
+    }
+}
+
+impl Item {
+    fn from_xml(kind: &str, e: Node, src: &str) -> Item {
+        let mut item = Item {
+            kind: kind.into(),
+            ..Item::default()
+        };
+        let mut extra = Vec::new();
+        for c in e.children().filter(|n| !is_blank(n)) {
+            let text = || c.text().unwrap_or_default().trim().to_string();
+            match (kind, c.tag_name().name()) {
+                (_, "title") if c.is_element() && item.title.is_empty() => {
+                    item.title = text();
+                }
+                ("serie", "episode") | ("text", "subtitle")
+                    if c.attribute("role").is_none()
+                        && item.episode.is_empty() =>
+                {
+                    item.episode = text();
+                }
+                ("serie", "part") if item.part_no.is_empty() => {
+                    item.part_no = c.attribute("no").unwrap_or("").into();
+                    item.part_name = text();
+                }
+                ("serie" | "omslag", "best") if item.best.is_empty() => {
+                    item.best = c.attribute("plac").unwrap_or("").into();
+                }
+                (_, "by") if c.is_element() => {
+                    let role = c.attribute("role").unwrap_or(Role::DEFAULT);
+                    let names = c.children().filter(Node::is_element);
+                    let mut names = names
+                        .map(|n| n.text().unwrap_or_default().trim().into())
+                        .collect::<Vec<String>>();
+                    if names.is_empty() {
+                        names.push(text());
+                    }
+                    item.by
+                        .extend(names.into_iter().map(|n| (role.into(), n)));
+                }
+                ("serie" | "text", "ref") if c.is_element() => {
+                    item.refs.extend(
+                        c.children().filter(Node::is_element).map(|r| {
+                            let text = match r.tag_name().name() {
+                                "fa" => r.attribute("no"),
+                                _ => r.text(),
+                            };
+                            (
+                                r.tag_name().name().into(),
+                                text.unwrap_or_default().trim().into(),
+                            )
+                        }),
+                    );
+                }
+                _ => extra.push(&src[c.range()]),
+            }
+        }
+        item.extra = extra.join("\n");
+        item
+    }
+
+    fn write_xml(&self, xml: &mut String) {
+        let kind = &self.kind;
+        if kind == "xml" {
+            let _ = write!(xml, "\n    {}", self.extra.trim());
+            return;
+        }
+        let _ = write!(xml, "\n    <{kind}>");
+        let mut elem = |s: String| {
+            xml.push_str("\n      ");
+            xml.push_str(&s);
+        };
+        if !self.title.is_empty() {
+            elem(format!("<title>{}</title>", xml_escape(&self.title)));
+        }
+        if !self.episode.is_empty() {
+            let tag = if kind == "text" {
+                "subtitle"
+            } else {
+                "episode"
+            };
+            elem(format!("<{tag}>{}</{tag}>", xml_escape(&self.episode)));
+        }
+        if !self.part_no.is_empty() || !self.part_name.is_empty() {
+            let no = if self.part_no.is_empty() {
+                String::new()
+            } else {
+                format!(" no=\"{}\"", xml_escape(&self.part_no))
+            };
+            elem(if self.part_name.is_empty() {
+                format!("<part{no}/>")
+            } else {
+                format!("<part{no}>{}</part>", xml_escape(&self.part_name))
+            });
+        }
+        for chunk in self.by.chunk_by(|a, b| a.0 == b.0) {
+            let role = &chunk[0].0;
+            let role = if role == Role::DEFAULT {
+                String::new()
+            } else {
+                format!(" role=\"{}\"", xml_escape(role))
+            };
+            elem(if let [(_, name)] = chunk {
+                format!("<by{role}>{}</by>", xml_escape(name))
+            } else {
+                let names = chunk.iter().map(|(_, name)| {
+                    format!("<who>{}</who>", xml_escape(name))
+                });
+                format!("<by{role}>{}</by>", names.collect::<String>())
+            });
+        }
+        for line in self.extra.trim().lines().filter(|l| !l.is_empty()) {
+            elem(line.trim_end().into());
+        }
+        if !self.refs.is_empty() {
+            let refs = self.refs.iter().map(|(kind, text)| match &**kind {
+                "fa" => format!("<fa no=\"{}\"/>", xml_escape(text)),
+                kind => format!("<{kind}>{}</{kind}>", xml_escape(text)),
+            });
+            elem(format!("<ref>{}</ref>", refs.collect::<String>()));
+        }
+        if !self.best.is_empty() {
+            elem(format!("<best plac=\"{}\"/>", xml_escape(&self.best)));
+        }
+        let _ = write!(xml, "\n    </{kind}>");
+    }
+}
+
+/// The label of an issue attribute in the form.
+pub fn attr_label(name: &str) -> &str {
+    match name {
+        "pages" => "Sidor",
+        "price" => "Pris",
+        "date" => "Datum",
+        "ord" => "Ordning",
+        other => other,
+    }
+}
+
+fn is_blank(node: &Node) -> bool {
+    node.node_type() == NodeType::Text
+        && node.text().is_none_or(|t| t.trim().is_empty())
+}
+
+fn set_last(pairs: &mut Vec<(String, String)>, value: String) {
+    match pairs.last_mut() {
+        Some((_, last)) if last.is_empty() => *last = value,
+        _ => pairs.push((Role::DEFAULT.into(), value)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IssueForm;
+
+    const ISSUE: &str = "<issue nr=\"1\" pages=\"52\">
+    <omslag>
+      <by>J. Vallvé</by>
+      <best plac=\"2\"/>
+    </omslag>
+    <serie>
+      <title>Fantomen</title>
+      <episode>Kannibalernas ö</episode>
+      <part no=\"2\">Fortsättning</part>
+      <by role=\"text\">Tony DePaul</by>
+      <by role=\"bild\"><who>A</who><who>B</who></by>
+      <episode role=\"orig\" xml:lang=\"en\">Cannibal Island</episode>
+      <ref><fa no=\"22\"/><key>Kit &amp; Heloise</key></ref>
+      <best plac=\"1\"/>
+    </serie>
+    <text>
+      <title>Fantompostin</title>
+      <by role=\"redax\">Redaktionen</by>
+    </text>
+    <!-- keep -->
+  </issue>";
+
+    #[test]
+    fn read_xml() {
+        let form = IssueForm::from_xml(ISSUE).unwrap();
+        assert_eq!(form.attrs.len(), 2);
+        let kinds = form.items.iter().map(|i| &*i.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, ["omslag", "serie", "text", "xml"]);
+        let serie = &form.items[1];
+        assert_eq!(serie.episode, "Kannibalernas ö");
+        assert_eq!((&*serie.part_no, &*serie.best), ("2", "1"));
+        assert_eq!(serie.by.len(), 3);
+        assert_eq!(serie.refs[1], ("key".into(), "Kit & Heloise".into()));
+        assert_eq!(
+            serie.extra,
+            "<episode role=\"orig\" xml:lang=\"en\">Cannibal Island</episode>",
+        );
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let form = IssueForm::from_xml(ISSUE).unwrap();
+        assert_eq!(form.to_xml(), ISSUE);
+    }
+
+    #[test]
+    fn read_form() {
+        let fields = [
+            ("attr.nr", "3"),
+            ("kind.0", "serie"),
+            ("title.0", "Mandrake"),
+            ("by_role.0", "text"),
+            ("by_name.0", " Lee Falk "),
+            ("by_role.0", "by"),
+            ("by_name.0", ""),
+            ("ref_kind.0", "key"),
+            ("ref_text.0", ""),
+            ("kind.1", ""),
+            ("title.1", "Removed"),
+        ];
+        let fields = fields.map(|(n, v)| (n.to_string(), v.to_string()));
+        let form = IssueForm::from_form(fields.into());
+        assert_eq!(
+            form.to_xml(),
+            "<issue nr=\"3\">
+    <serie>
+      <title>Mandrake</title>
+      <by role=\"text\">Lee Falk</by>
+    </serie>
+  </issue>",
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Choice of language for the web ui.
+//!
+//! The language is taken from a `lang` cookie if set, otherwise from
+//! the `Accept-Language` header, defaulting to Swedish.  Visiting a
+//! url with a `/en/` or `/sv/` prefix sets the cookie and redirects
+//! to the same page without the prefix, so the prefix can be used for
+//! links in any language while the rest of the site keeps its urls.
+use super::account::see_other;
+use super::{Result, goh, wrap};
+use crate::i18n::Lang;
+use warp::filters::BoxedFilter;
+use warp::http::Uri;
+use warp::path::Tail;
+use warp::reply::Response;
+use warp::{self, Filter};
+
+/// Name of the language cookie.
+const COOKIE: &str = "lang";
+
+/// A filter providing the language for the request.
+pub fn lang() -> BoxedFilter<(Lang,)> {
+    warp::cookie::optional::<String>(COOKIE)
+        .and(warp::header::optional::<String>("accept-language"))
+        .map(|cookie: Option<String>, accept: Option<String>| {
+            cookie
+                .as_deref()
+                .and_then(Lang::from_code)
+                .or_else(|| accept.as_deref().and_then(Lang::from_accept))
+                .unwrap_or_default()
+        })
+        .boxed()
+}
+
+pub fn routes() -> BoxedFilter<(Response,)> {
+    use warp::path::{path, tail};
+    use warp::query::raw;
+    let prefix = |lang: Lang| path(lang.code()).map(move || lang);
+    prefix(Lang::Sv)
+        .or(prefix(Lang::En))
+        .unify()
+        .and(tail())
+        .and(raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::optional::<String>("referer"))
+        .and(goh())
+        .map(set_lang)
+        .map(wrap)
+        .boxed()
+}
+
+/// Remember the language and redirect to the page without prefix.
+///
+/// For a bare prefix, such as the language link in the page footer,
+/// redirect back to the referring page.
+#[allow(clippy::needless_pass_by_value)]
+fn set_lang(
+    lang: Lang,
+    tail: Tail,
+    query: String,
+    referer: Option<String>,
+) -> Result<Response> {
+    let tail = tail.as_str().trim_start_matches('/');
+    let target = if !tail.is_empty() {
+        let mut target = format!("/{tail}");
+        if !query.is_empty() {
+            target.push('?');
+            target.push_str(&query);
+        }
+        target
+    } else {
+        referer
+            .and_then(|r| r.parse::<Uri>().ok())
+            .and_then(|uri| uri.path_and_query().map(|p| p.to_string()))
+            .filter(|p| p.starts_with('/') && !p.starts_with("//"))
+            .unwrap_or_else(|| "/".into())
+    };
+    let cookie = format!(
+        "{COOKIE}={}; Path=/; SameSite=Lax; Max-Age={}",
+        lang.code(),
+        365 * 24 * 60 * 60,
+    );
+    see_other(&target, Some(&cookie))
+}
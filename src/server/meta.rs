@@ -0,0 +1,132 @@
+//! Metadata for link previews and search engines.
+//!
+//! Every page gets Open Graph and Twitter card tags from its title
+//! and description.  Pages about a specific thing, such as an issue
+//! or a creator, can also give an image, a canonical url and
+//! schema.org data to embed as JSON-LD.
+use crate::models::{Creator, CreatorSet};
+use crate::templates::ToHtml;
+use crate::templates::statics::fa_lindahl_9915_png;
+use serde_json::{Value, json};
+use std::io::{self, Write};
+use std::sync::OnceLock;
+
+static BASE_URL: OnceLock<String> = OnceLock::new();
+
+/// Set the public base url of the site, used for absolute urls.
+pub fn set_base_url(url: &str) {
+    let _ = BASE_URL.set(url.trim_end_matches('/').to_string());
+}
+
+/// Absolute url for a site-relative path.
+pub fn absolute(path: &str) -> String {
+    let base = BASE_URL.get().map_or(DEFAULT_BASE_URL, String::as_str);
+    format!("{base}{path}")
+}
+
+pub const DEFAULT_BASE_URL: &str = "https://fantomenindex.krats.se";
+
+/// Metadata for a page, in addition to its title and description.
+pub struct PageMeta {
+    /// The Open Graph type, such as "website" or "profile".
+    pub kind: &'static str,
+    /// Site-relative url of the page.
+    pub url: Option<String>,
+    /// Site-relative url of an image to show in previews.
+    pub image: Option<String>,
+    pub json_ld: Option<Value>,
+}
+
+impl Default for PageMeta {
+    fn default() -> Self {
+        PageMeta {
+            kind: "website",
+            url: None,
+            image: None,
+            json_ld: None,
+        }
+    }
+}
+
+impl PageMeta {
+    pub fn url(&self) -> Option<String> {
+        self.url.as_deref().map(absolute)
+    }
+    /// Absolute url of the preview image, or a default image.
+    pub fn image(&self) -> String {
+        match &self.image {
+            Some(image) => absolute(image),
+            None => absolute(&format!("/s/{}", fa_lindahl_9915_png.name)),
+        }
+    }
+    pub fn card(&self) -> &'static str {
+        if self.image.is_some() {
+            "summary_large_image"
+        } else {
+            "summary"
+        }
+    }
+    pub fn json_ld(&self) -> Option<JsonLd<'_>> {
+        self.json_ld.as_ref().map(JsonLd)
+    }
+}
+
+/// Schema.org data, to be written in a `script` element.
+pub struct JsonLd<'a>(&'a Value);
+
+impl ToHtml for JsonLd<'_> {
+    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+        // Escape what could end the script element or start a comment.
+        let json = serde_json::to_string(self.0)?
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026");
+        out.write_all(json.as_bytes())
+    }
+}
+
+/// A schema.org `Person` for a creator.
+pub fn person(creator: &Creator) -> Value {
+    json!({
+        "@type": "Person",
+        "name": creator.name,
+        "url": absolute(&format!("/who/{}", creator.slug)),
+    })
+}
+
+/// Add the creators of a work to its schema.org data.
+pub fn add_creators(data: &mut Value, creators: &CreatorSet) {
+    for (role, creators) in creators.by_role() {
+        let property = &mut data[role_property(role)];
+        if property.is_null() {
+            *property = json!([]);
+        }
+        if let Value::Array(list) = property {
+            list.extend(creators.iter().map(person));
+        }
+    }
+}
+
+/// The schema.org property for creators with a role.
+fn role_property(role: &str) -> &'static str {
+    match role {
+        "by" | "text" => "author",
+        "bild" => "artist",
+        "ink" => "inker",
+        "color" => "colorist",
+        "textning" => "letterer",
+        "xlat" => "translator",
+        "redax" => "editor",
+        _ => "contributor",
+    }
+}
+
+#[test]
+fn json_ld_escaped() {
+    let data = json!({"name": "</script><!-- & -->"});
+    let mut out = Vec::new();
+    JsonLd(&data).to_html(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(!out.contains('<'));
+    assert_eq!(serde_json::from_str::<Value>(&out).unwrap(), data);
+}
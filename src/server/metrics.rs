@@ -0,0 +1,283 @@
+//! Operational metrics, in the prometheus text format.
+//!
+//! Metrics are collected in a global registry and served at
+//! `/metrics` on a separate address, given by `--metrics-bind`, so
+//! they are not exposed to the public.
+use crate::dbopt;
+use diesel_async::AsyncPgConnection;
+use diesel_async::pooled_connection::deadpool::{Object, PoolError};
+use mime::TEXT_PLAIN_UTF_8;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use warp::filters::BoxedFilter;
+use warp::filters::log::Info;
+use warp::http::header::CONTENT_TYPE;
+use warp::reply::{Response, with_header};
+use warp::{Filter, Reply};
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(Mutex::default);
+
+/// The database pool, measuring the time spent waiting for connections.
+#[derive(Clone)]
+pub struct PgPool(dbopt::PgPool);
+
+impl PgPool {
+    pub fn new(pool: dbopt::PgPool) -> Self {
+        PgPool(pool)
+    }
+
+    pub async fn get(&self) -> Result<Object<AsyncPgConnection>, PoolError> {
+        let start = Instant::now();
+        let result = self.0.get().await;
+        let elapsed = start.elapsed();
+        with_registry(|r| {
+            r.pool_wait.observe(elapsed);
+            if matches!(result, Err(PoolError::Timeout(_))) {
+                r.pool_timeouts += 1;
+            }
+        });
+        result
+    }
+}
+
+/// The metrics route, to be served on the metrics address.
+pub fn routes(pool: PgPool) -> BoxedFilter<(Response,)> {
+    use warp::path::{end, path};
+    path("metrics")
+        .and(end())
+        .and(warp::get())
+        .map(move || {
+            let status = pool.0.status();
+            let mut text = with_registry(|r| r.to_string());
+            gauge(&mut text, "db_pool_max_size", status.max_size);
+            gauge(&mut text, "db_pool_size", status.size);
+            gauge(&mut text, "db_pool_available", status.available);
+            gauge(&mut text, "db_pool_waiting", status.waiting);
+            with_header(text, CONTENT_TYPE, TEXT_PLAIN_UTF_8.as_ref())
+                .into_response()
+        })
+        .boxed()
+}
+
+/// Record a served request, for use with `warp::log::custom`.
+pub fn record_request(info: Info) {
+    let route = route_family(info.path());
+    let status = info.status().as_u16();
+    with_registry(|r| {
+        *r.requests.entry((route, status)).or_default() += 1;
+        r.durations
+            .entry(route)
+            .or_default()
+            .observe(info.elapsed());
+        if status == 404 {
+            *r.not_found.entry(route).or_default() += 1;
+        }
+    });
+}
+
+pub fn render_error() {
+    with_registry(|r| r.render_errors += 1);
+}
+
+/// Record a cover image request, found in the database or not.
+pub fn cover(found: bool) {
+    let result = if found { "hit" } else { "miss" };
+    with_registry(|r| *r.covers.entry(result).or_default() += 1);
+}
+
+fn with_registry<T>(f: impl FnOnce(&mut Registry) -> T) -> T {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut registry)
+}
+
+/// The first path segment of known routes, other paths are "other".
+const ROUTES: &[&str] = &[
+    "s",
+    "c",
+    "search",
+    "ac",
+    "login",
+    "logout",
+    "my",
+    "admin",
+    "en",
+    "sv",
+    "prices",
+    "prices.csv",
+    "prices.json",
+    "calendar.ics",
+    "export.zip",
+    "compare",
+    "reprints",
+    "changes",
+    "orig",
+    "titles",
+    "fa",
+    "what",
+    "who",
+    "static",
+    "robots.txt",
+];
+
+/// A low-cardinality name for the route of a request path.
+fn route_family(path: &str) -> &'static str {
+    let mut segments = path.trim_start_matches('/').split('/');
+    match segments.next() {
+        None | Some("") => "frontpage",
+        Some(first) if first.parse::<i16>().is_ok() => {
+            match segments.next() {
+                None | Some("" | "details") => "year",
+                Some(_) => "issue",
+            }
+        }
+        Some(first) => ROUTES
+            .iter()
+            .find(|r| **r == first)
+            .copied()
+            .unwrap_or("other"),
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    requests: BTreeMap<(&'static str, u16), u64>,
+    durations: BTreeMap<&'static str, Histogram>,
+    not_found: BTreeMap<&'static str, u64>,
+    covers: BTreeMap<&'static str, u64>,
+    pool_wait: Histogram,
+    pool_timeouts: u64,
+    render_errors: u64,
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(out, "# TYPE fanrs_http_requests_total counter")?;
+        for ((route, status), n) in &self.requests {
+            writeln!(
+                out,
+                "fanrs_http_requests_total\
+                 {{route=\"{route}\",status=\"{status}\"}} {n}",
+            )?;
+        }
+        writeln!(
+            out,
+            "# TYPE fanrs_http_request_duration_seconds histogram"
+        )?;
+        for (route, histogram) in &self.durations {
+            histogram.write(
+                out,
+                "fanrs_http_request_duration_seconds",
+                &format!("route=\"{route}\","),
+            )?;
+        }
+        writeln!(out, "# TYPE fanrs_http_not_found_total counter")?;
+        for (route, n) in &self.not_found {
+            writeln!(
+                out,
+                "fanrs_http_not_found_total{{route=\"{route}\"}} {n}",
+            )?;
+        }
+        writeln!(out, "# TYPE fanrs_covers_total counter")?;
+        for (result, n) in &self.covers {
+            writeln!(out, "fanrs_covers_total{{result=\"{result}\"}} {n}")?;
+        }
+        writeln!(out, "# TYPE fanrs_db_pool_wait_seconds histogram")?;
+        self.pool_wait
+            .write(out, "fanrs_db_pool_wait_seconds", "")?;
+        writeln!(out, "# TYPE fanrs_db_pool_timeouts_total counter")?;
+        writeln!(out, "fanrs_db_pool_timeouts_total {}", self.pool_timeouts)?;
+        writeln!(out, "# TYPE fanrs_render_errors_total counter")?;
+        writeln!(out, "fanrs_render_errors_total {}", self.render_errors)
+    }
+}
+
+fn gauge(out: &mut String, name: &str, value: usize) {
+    let _ = writeln!(out, "# TYPE fanrs_{name} gauge\nfanrs_{name} {value}");
+}
+
+/// Upper bounds of histogram buckets, in seconds.
+const BUCKETS: [f64; 11] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0,
+];
+
+#[derive(Default)]
+struct Histogram {
+    /// Number of observations in each bucket (not cumulative).
+    counts: [u64; BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        if let Some(i) = BUCKETS.iter().position(|b| secs <= *b) {
+            self.counts[i] += 1;
+        }
+        self.count += 1;
+        self.sum += secs;
+    }
+
+    fn write(
+        &self,
+        out: &mut fmt::Formatter,
+        name: &str,
+        labels: &str,
+    ) -> fmt::Result {
+        let mut cumulative = 0;
+        for (bound, n) in BUCKETS.iter().zip(&self.counts) {
+            cumulative += n;
+            writeln!(
+                out,
+                "{name}_bucket{{{labels}le=\"{bound}\"}} {cumulative}",
+            )?;
+        }
+        writeln!(out, "{name}_bucket{{{labels}le=\"+Inf\"}} {}", self.count)?;
+        let labels = match labels.trim_end_matches(',') {
+            "" => String::new(),
+            labels => format!("{{{labels}}}"),
+        };
+        writeln!(out, "{name}_sum{labels} {}", self.sum)?;
+        writeln!(out, "{name}_count{labels} {}", self.count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Histogram, route_family};
+    use std::time::Duration;
+
+    #[test]
+    fn families() {
+        assert_eq!(route_family("/"), "frontpage");
+        assert_eq!(route_family("/1970"), "year");
+        assert_eq!(route_family("/1970/details"), "year");
+        assert_eq!(route_family("/1970/4-5"), "issue");
+        assert_eq!(route_family("/who/lee-falk"), "who");
+        assert_eq!(route_family("/en/who/lee-falk"), "en");
+        assert_eq!(route_family("/prices.csv"), "prices.csv");
+        assert_eq!(route_family("/fantomen"), "other");
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        struct Show(Histogram);
+        impl std::fmt::Display for Show {
+            fn fmt(&self, out: &mut std::fmt::Formatter) -> std::fmt::Result {
+                self.0.write(out, "t", "")
+            }
+        }
+        let mut h = Histogram::default();
+        h.observe(Duration::from_millis(3));
+        h.observe(Duration::from_millis(30));
+        h.observe(Duration::from_secs(60));
+        let text = Show(h).to_string();
+        assert!(text.contains("t_bucket{le=\"0.005\"} 1\n"));
+        assert!(text.contains("t_bucket{le=\"0.05\"} 2\n"));
+        assert!(text.contains("t_bucket{le=\"10\"} 2\n"));
+        assert!(text.contains("t_bucket{le=\"+Inf\"} 3\n"));
+        assert!(text.contains("t_count 3\n"));
+    }
+}
@@ -1,26 +1,59 @@
+mod account;
+mod admin;
+mod calendar;
+pub mod changes;
+mod chronology;
+mod collaborators;
+mod compare;
 mod covers;
 mod creators;
 mod error;
+mod export;
+mod issueform;
+mod locale;
+mod meta;
+mod metrics;
+mod origins;
 mod paginator;
+mod prices;
 mod publist;
 mod refs;
+mod reprints;
 pub mod search;
+mod thisweek;
+mod timeline;
 mod titles;
 mod yearsummary;
 
-pub use self::creators::CoverSet;
+pub use self::account::MyIssue;
+pub use self::admin::{EditForm, EditPage};
+pub use self::chronology::Chronology;
+pub use self::collaborators::Collaborator;
+pub use self::compare::{CompareQuery, Comparison, ShortEpisode};
+pub use self::creators::CreatorPage;
+pub use self::issueform::{KINDS, REF_KINDS, attr_label};
+pub use self::meta::PageMeta;
+pub use self::origins::{MagSummary, OrigItem};
 pub use self::paginator::Paginator;
+pub use self::prices::{PriceChart, YearPrices};
 pub use self::publist::{OtherContribs, PartsPublished};
+pub use self::refs::{ListedRef, RefKeyPage};
+pub use self::reprints::Reprinted;
+pub use self::thisweek::ThisWeek;
+pub use self::timeline::Timeline;
+pub use self::titles::TitlePage;
 pub use self::yearsummary::ContentSummary;
 
 use self::covers::{cover_image, redirect_cover};
 use self::error::{ViewError, ViewResult, for_rejection};
+use self::locale::lang;
+use self::metrics::PgPool;
 use self::search::{search, search_autocomplete};
 use crate::DbOpt;
-use crate::dbopt::PgPool;
+use crate::i18n::Lang;
 use crate::models::{
-    Article, Creator, CreatorSet, Episode, Issue, IssueRef, OtherMag, Part,
-    RefKey, RefKeySet, Title,
+    Article, Cloud, Creator, CreatorSet, Episode, Issue, IssueRef, Language,
+    OtherMag, Part, RefKey, RefKeySet, Title, User,
 };
 use crate::schema::articles::dsl as a;
 use crate::schema::covers_by::dsl as cb;
@@ -35,20 +68,22 @@ use crate::templates::{
     Html, RenderRucte, ToHtml, frontpage_html, issue_html, year_html,
 };
 use bytes::Bytes;
-use chrono::{Duration, Utc};
+use chrono::{Duration, Local, Utc};
 use diesel::dsl::{count, max, min, not};
 use diesel::prelude::*;
 use diesel::result::Error as DbError;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use mime::TEXT_PLAIN;
 use regex::Regex;
+use serde_json::json;
 use std::io::{self, Write};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::OnceLock;
 use tokio::net::TcpListener;
 use tracing::info;
 use warp::filters::BoxedFilter;
-use warp::http::header::{CONTENT_TYPE, EXPIRES};
+use warp::http::header::{CONTENT_TYPE, EXPIRES, VARY};
 use warp::http::response::Builder;
 use warp::http::status::StatusCode;
 use warp::path::Tail;
@@ -63,6 +98,23 @@ pub struct Args {
     /// Adress to listen on
     #[clap(long, default_value = "127.0.0.1:1536")]
     bind: SocketAddr,
+
+    /// The directory containing the data files.
+    ///
+    /// Editing issue contents on the web is only possible when this
+    /// is given, as edits are saved to the data files.
+    #[arg(long, short, env = "FANTOMEN_DATA")]
+    basedir: Option<PathBuf>,
+
+    /// Address to serve prometheus metrics on, at `/metrics`.
+    ///
+    /// This should not be publicly reachable.
+    #[clap(long)]
+    metrics_bind: Option<SocketAddr>,
+
+    /// Public base url of the site, for absolute urls in metadata.
+    #[clap(long, env = "FANTOMEN_URL", default_value = meta::DEFAULT_BASE_URL)]
+    base_url: String,
 }
 
 type PgFilter = BoxedFilter<(PgPool,)>;
@@ -77,7 +129,16 @@ impl Args {
     pub async fn run(&self) -> anyhow::Result<()> {
         use warp::filters::query::query;
         use warp::{path, path::end, path::param, path::tail};
-        let pool = self.db.get_pool().unwrap();
+        let pool = PgPool::new(self.db.get_pool().unwrap());
+        meta::set_base_url(&self.base_url);
+        if let Some(bind) = self.metrics_bind {
+            let acceptor = TcpListener::bind(bind).await?;
+            if let Ok(addr) = acceptor.local_addr() {
+                info!("Serving metrics on http://{addr}/metrics");
+            }
+            let routes = metrics::routes(pool.clone());
+            tokio::spawn(warp::serve(routes).incoming(acceptor).run());
+        }
         let s = warp::any().map(move || pool.clone()).boxed();
         let s = move || s.clone();
         let routes = warp::any()
@@ -89,12 +150,19 @@ impl Args {
                 .and(s())
                 .then(cover_image)
                 .map(wrap))
-            .or(end().and(goh()).and(s()).then(frontpage).map(wrap))
+            .or(end()
+                .and(goh())
+                .and(s())
+                .and(lang())
+                .then(frontpage)
+                .map(wrap))
+            .or(locale::routes())
             .or(path("search")
                 .and(end())
                 .and(query())
                 .and(goh())
                 .and(s())
+                .and(lang())
                 .then(search)
                 .map(wrap))
             .or(path("ac")
@@ -104,9 +172,19 @@ impl Args {
                 .and(s())
                 .then(search_autocomplete)
                 .map(wrap))
+            .or(account::routes(s()))
+            .or(admin::routes(s(), self.basedir.clone()))
+            .or(prices::routes(s()))
+            .or(export::routes(s()))
+            .or(calendar::routes(s()))
+            .or(compare::routes(s()))
+            .or(reprints::routes(s()))
+            .or(changes::routes(s()))
+            .or(origins::routes(s()))
             .or(path("titles").and(titles::routes(s())))
             .or(path("fa").and(refs::fa_route(s())))
             .or(path("what").and(refs::what_routes(s())))
+            .or(path("who").and(collaborators::routes(s())))
             .or(path("who").and(creators::routes(s())))
             .or(path("static")
                 .and(param())
@@ -125,6 +203,7 @@ impl Args {
                 .and(end())
                 .and(goh())
                 .and(s())
+                .and(lang())
                 .then(yearsummary::year_summary)
                 .map(wrap))
             .or(param()
@@ -132,6 +211,8 @@ impl Args {
                 .and(end())
                 .and(goh())
                 .and(s())
+                .and(account::user(s()))
+                .and(lang())
                 .then(issue)
                 .map(wrap))
             .or(param()
@@ -139,6 +220,7 @@ impl Args {
                 .and(end())
                 .and(goh())
                 .and(s())
+                .and(lang())
                 .then(list_year)
                 .map(wrap))
             .or(param()
@@ -148,6 +230,11 @@ impl Args {
                 .then(titles::oldslug)
                 .map(wrap))
             .recover(for_rejection);
+        let routes = lang()
+            .and(routes)
+            .map(error::localize)
+            .with(warp::reply::with::header(VARY, "Accept-Language, Cookie"))
+            .with(warp::log::custom(metrics::record_request));
 
         let acceptor = TcpListener::bind(self.bind).await?;
         if let Ok(addr) = acceptor.local_addr() {
@@ -193,11 +280,12 @@ async fn robots_txt() -> Result<impl Reply> {
         .body("User-agent: *\nDisallow: /search\nDisallow: /ac\n"))
 }
 
-async fn frontpage(pool: PgPool) -> Result<impl Reply> {
+async fn frontpage(pool: PgPool, lang: Lang) -> Result<impl Reply> {
     let mut db = pool.get().await?;
 
     let (n, of_n): (i64, Option<i32>) = i::issues
         .select((diesel::dsl::count(i::id), max(i::ord)))
+        .filter(i::date.is_null().or(i::date.le(Local::now().date_naive())))
         .first(&mut db)
         .await?;
 
@@ -222,12 +310,33 @@ async fn frontpage(pool: PgPool) -> Result<impl Reply> {
     let titles = Title::cloud(num, &mut db).await?;
     let refkeys = RefKey::cloud(num, &mut db).await?;
     let creators = Creator::cloud(num, &mut db).await?;
+    let this_week = ThisWeek::load(&mut db).await?;
+
+    let page = FrontPage {
+        n,
+        of_n,
+        all_fa,
+        years,
+        this_week,
+        titles,
+        refkeys,
+        creators,
+    };
+    Ok(Builder::new().html(|o| frontpage_html(o, lang, &page))?)
+}
 
-    Ok(Builder::new().html(|o| {
-        frontpage_html(
-            o, n, of_n, &all_fa, &years, &titles, &refkeys, &creators,
-        )
-    })?)
+/// Everything shown on the front page.
+pub struct FrontPage {
+    /// Number of indexed issues.
+    pub n: i64,
+    /// Number of known issues.
+    pub of_n: i64,
+    pub all_fa: Vec<RefKey>,
+    pub years: Vec<i16>,
+    pub this_week: ThisWeek,
+    pub titles: Cloud<Title>,
+    pub refkeys: Cloud<RefKey>,
+    pub creators: Cloud<Creator>,
 }
 
 /// Information about an episode / part or article, as published in an issue.
@@ -265,6 +374,7 @@ pub struct FullEpisode {
     pub refs: RefKeySet,
     pub creators: CreatorSet,
     pub published: PartsPublished,
+    pub orig_lang: Option<Language>,
     pub orig_mag: Option<OtherMag>,
 }
 
@@ -276,12 +386,14 @@ impl FullEpisode {
         let refs = RefKeySet::for_episode(&episode, db).await?;
         let creators = CreatorSet::for_episode(&episode, db).await?;
         let published = PartsPublished::for_episode(&episode, db).await?;
+        let orig_lang = episode.load_orig_lang(db).await?;
         let orig_mag = episode.load_orig_mag(db).await?;
         Ok(FullEpisode {
             episode,
             refs,
             creators,
             published,
+            orig_lang,
             orig_mag,
         })
     }
@@ -295,12 +407,14 @@ impl FullEpisode {
         let creators = CreatorSet::for_episode(&episode, db).await?;
         let published =
             PartsPublished::for_episode_except(&episode, issue, db).await?;
+        let orig_lang = episode.load_orig_lang(db).await?;
         let orig_mag = episode.load_orig_mag(db).await?;
         Ok(FullEpisode {
             episode,
             refs,
             creators,
             published,
+            orig_lang,
             orig_mag,
         })
     }
@@ -401,7 +515,13 @@ fn text_to_fa_html_e() {
     )
 }
 
-async fn issue(year: i16, issue: u8, db: PgPool) -> Result<impl Reply> {
+async fn issue(
+    year: i16,
+    issue: u8,
+    db: PgPool,
+    user: Option<User>,
+    lang: Lang,
+) -> Result<impl Reply> {
     let mut db = db.get().await?;
     let issue: Issue = i::issues
         .filter(i::year.eq(year))
@@ -418,12 +538,19 @@ async fn issue(year: i16, issue: u8, db: PgPool) -> Result<impl Reply> {
         .load::<IssueRef>(&mut db)
         .await?;
 
+    let mine = match &user {
+        Some(user) => Some(MyIssue::load(user, issue.id, &mut db).await?),
+        None => None,
+    };
+    let nav = IssueNav::load(&issue, &mut db).await?;
     let details = IssueDetails::load_full(issue, &mut db).await?;
     let years = YearLinks::load(year, &mut db).await?.link_current();
-    Ok(Builder::new().html(|o| issue_html(o, &years, &details, &pubyear))?)
+    Ok(Builder::new().html(|o| {
+        issue_html(o, lang, &years, &nav, &details, &pubyear, mine.as_ref())
+    })?)
 }
 
-async fn list_year(year: i16, db: PgPool) -> Result<impl Reply> {
+async fn list_year(year: i16, db: PgPool, lang: Lang) -> Result<impl Reply> {
     let mut db = db.get().await?;
     let issues_in = i::issues
         .filter(i::year.eq(year))
@@ -438,7 +565,7 @@ async fn list_year(year: i16, db: PgPool) -> Result<impl Reply> {
         issues.push(IssueDetails::load_full(issue, &mut db).await?);
     }
     let years = YearLinks::load(year, &mut db).await?;
-    Ok(Builder::new().html(|o| year_html(o, year, &years, &issues))?)
+    Ok(Builder::new().html(|o| year_html(o, lang, year, &years, &issues))?)
 }
 
 pub struct IssueDetails {
@@ -528,8 +655,18 @@ impl IssueDetails {
             contents,
         })
     }
-    pub fn description(&self) -> String {
-        let mut result = format!("Innehållet i Fantomen {}.", self.issue);
+    pub fn description(&self, lang: Lang) -> String {
+        let issue = &self.issue;
+        let mut name = format!("{}/{}", issue.number_str, issue.year);
+        let details = [
+            issue.pages.map(|p| lang.text("{} sidor", &[&p])),
+            issue.price.map(|p| lang.text("pris {}", &[&p])),
+        ];
+        let details = details.into_iter().flatten().collect::<Vec<_>>();
+        if !details.is_empty() {
+            name = format!("{name} ({})", details.join(", "));
+        }
+        let mut result = lang.text("Innehållet i Fantomen {}.", &[&name]);
         for c in &self.contents {
             if let PublishedContent::EpisodePart { title, .. } = &c.content {
                 result.push(' ');
@@ -539,6 +676,83 @@ impl IssueDetails {
         }
         result
     }
+
+    /// Page metadata, with schema.org data for the issue and contents.
+    pub fn meta(&self, lang: Lang) -> PageMeta {
+        let issue = &self.issue;
+        let url = format!("/{}/{}", issue.year, issue.number);
+        let mut data = json!({
+            "@context": "https://schema.org",
+            "@type": ["PublicationIssue", "ComicIssue"],
+            "@id": meta::absolute(&url),
+            "url": meta::absolute(&url),
+            "name": format!("Fantomen {}/{}", issue.number_str, issue.year),
+            "description": self.description(lang),
+            "issueNumber": issue.number_str,
+            "datePublished": issue
+                .date
+                .map_or_else(|| issue.year.to_string(), |d| d.to_string()),
+            "inLanguage": "sv",
+            "image": meta::absolute(&issue.cover_url()),
+            "isPartOf": {"@type": "Periodical", "name": "Fantomen"},
+        });
+        if let Some(pages) = issue.pages {
+            data["numberOfPages"] = pages.into();
+        }
+        if let Some(price) = issue.price {
+            data["offers"] = json!({
+                "@type": "Offer",
+                "price": format!("{:.2}", price.as_kr()),
+                "priceCurrency": "SEK",
+            });
+        }
+        if !self.cover_by.is_empty() {
+            data["artist"] = self.cover_by.iter().map(meta::person).collect();
+        }
+        let parts = self
+            .contents
+            .iter()
+            .map(|item| {
+                let mut part = match &item.content {
+                    PublishedContent::EpisodePart {
+                        title, episode, ..
+                    } => {
+                        let mut part = json!({
+                            "@type": "ComicStory",
+                            "name": match &episode.episode.name {
+                                Some(name) => format!("{}: {name}", title.title),
+                                None => title.title.clone(),
+                            },
+                            "url": meta::absolute(&format!("/titles/{}", title.slug)),
+                        });
+                        meta::add_creators(&mut part, &episode.creators);
+                        part
+                    }
+                    PublishedContent::Text(article) => {
+                        let mut part = json!({
+                            "@type": "Article",
+                            "name": article.article.title,
+                        });
+                        meta::add_creators(&mut part, &article.creators);
+                        part
+                    }
+                };
+                if let Some(seqno) = item.seqno {
+                    part["position"] = seqno.into();
+                }
+                part
+            })
+            .collect::<Vec<_>>();
+        if !parts.is_empty() {
+            data["hasPart"] = parts.into();
+        }
+        PageMeta {
+            kind: "book",
+            url: Some(url),
+            image: Some(issue.cover_url()),
+            json_ld: Some(data),
+        }
+    }
 }
 
 async fn cover_by(
@@ -593,6 +807,39 @@ impl YearLinks {
     }
 }
 
+/// Links to the previous and next issue, across years.
+///
+/// Issues are ordered by their `magic` number, so a double issue is
+/// placed by its first number.
+pub struct IssueNav {
+    pub prev: Option<IssueRef>,
+    pub next: Option<IssueRef>,
+}
+
+impl IssueNav {
+    async fn load(
+        issue: &Issue,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Self, DbError> {
+        let issue_ref = (i::year, (i::number, i::number_str));
+        let prev = i::issues
+            .select(issue_ref)
+            .filter(i::magic.lt(issue.magic))
+            .order(i::magic.desc())
+            .first(db)
+            .await
+            .optional()?;
+        let next = i::issues
+            .select(issue_ref)
+            .filter(i::magic.gt(issue.magic))
+            .order(i::magic)
+            .first(db)
+            .await
+            .optional()?;
+        Ok(IssueNav { prev, next })
+    }
+}
+
 impl ToHtml for YearLinks {
     fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
         let shown = self.shown;
@@ -0,0 +1,198 @@
+//! Catalogue of episodes by original language and magazine.
+use super::locale::lang;
+use super::{PartsPublished, PgFilter, PgPool, Result, ViewError, goh, wrap};
+use crate::i18n::Lang;
+use crate::models::{Episode, Language, OrigKind, OtherMag, Title};
+use crate::schema::episodes::dsl as e;
+use crate::schema::languages::dsl as l;
+use crate::schema::other_mags::{self, dsl as om};
+use crate::schema::titles::dsl as t;
+use crate::templates::{RenderRucte, origin_html, origins_html};
+use diesel::dsl::{count_star, max, min, sql};
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use slug::slugify;
+use warp::filters::BoxedFilter;
+use warp::http::response::Builder;
+use warp::reply::Response;
+use warp::{self, Filter, Reply};
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
+    use warp::path::{end, param, path};
+    let index = end().and(goh()).and(s.clone()).and(lang()).then(origins);
+    let mag = path("mag")
+        .and(param())
+        .and(end())
+        .and(goh())
+        .and(s.clone())
+        .and(lang())
+        .then(by_mag);
+    let language = param()
+        .and(end())
+        .and(goh())
+        .and(s)
+        .and(lang())
+        .then(by_lang);
+    path("orig")
+        .and(index.or(mag).unify().or(language).unify())
+        .map(wrap)
+        .boxed()
+}
+
+async fn origins(db: PgPool, lang: Lang) -> Result<Response> {
+    let mut db = db.get().await?;
+    let langs = l::languages
+        .inner_join(e::episodes)
+        .group_by((l::code, l::name))
+        .select((
+            Language::as_select(),
+            count_star(),
+            sql::<BigInt>(
+                "count(*) filter (where orig_to_date is not null \
+                 and not orig_sundays)",
+            ),
+            sql::<BigInt>(
+                "count(*) filter (where orig_to_date is not null \
+                 and orig_sundays)",
+            ),
+        ))
+        .order(l::name)
+        .load::<(Language, i64, i64, i64)>(&mut db)
+        .await?;
+    let mags = om::other_mags
+        .inner_join(e::episodes)
+        .group_by(om::name)
+        .select((om::name, count_star(), min(om::year), max(om::year)))
+        .order(om::name)
+        .load::<(String, i64, Option<i16>, Option<i16>)>(&mut db)
+        .await?
+        .into_iter()
+        .map(|(name, n, first, last)| MagSummary {
+            slug: slugify(&name),
+            name,
+            n,
+            years: first.zip(last),
+        })
+        .collect::<Vec<_>>();
+    Ok(Builder::new().html(|o| origins_html(o, lang, &langs, &mags))?)
+}
+
+async fn by_lang(code: String, db: PgPool, lang: Lang) -> Result<Response> {
+    let mut db = db.get().await?;
+    let language = Language::get(&code, &mut db)
+        .await?
+        .ok_or(ViewError::NotFound)?;
+    let episodes = e::episodes
+        .inner_join(t::titles)
+        .left_join(om::other_mags)
+        .select((
+            Episode::as_select(),
+            Title::as_select(),
+            other_mags::all_columns.nullable(),
+        ))
+        .filter(e::orig_lang.eq(&language.code))
+        .order((e::orig_date.asc().nulls_last(), t::title, e::name))
+        .load::<(Episode, Title, Option<OtherMag>)>(&mut db)
+        .await?;
+    let mut items = OrigItem::load_all(&episodes, &mut db).await?;
+    let mut groups: Vec<(&str, Vec<OrigItem>)> = Vec::new();
+    for kind in ["Dagstrippar", "Söndagssidor", "Övriga episoder"] {
+        let (these, rest) = items
+            .into_iter()
+            .partition::<Vec<_>, _>(|item| item_kind(item.episode) == kind);
+        items = rest;
+        if !these.is_empty() {
+            groups.push((kind, these));
+        }
+    }
+    let name = lang.t(&language.name);
+    let title = lang.text("{} original", &[&name]);
+    let name = match lang {
+        Lang::Sv => name.to_lowercase(),
+        Lang::En => name.to_string(),
+    };
+    let desc = lang.text(
+        "Serier i svenska Fantomentidningen med original på {}.",
+        &[&name],
+    );
+    Ok(Builder::new()
+        .html(|o| origin_html(o, lang, &title, &desc, &groups))?)
+}
+
+async fn by_mag(slug: String, db: PgPool, lang: Lang) -> Result<Response> {
+    let mut db = db.get().await?;
+    let name = om::other_mags
+        .select(om::name)
+        .distinct()
+        .load::<String>(&mut db)
+        .await?
+        .into_iter()
+        .find(|name| slugify(name) == slug)
+        .ok_or(ViewError::NotFound)?;
+    let episodes = e::episodes
+        .inner_join(t::titles)
+        .inner_join(om::other_mags)
+        .select((
+            Episode::as_select(),
+            Title::as_select(),
+            other_mags::all_columns.nullable(),
+        ))
+        .filter(om::name.eq(&name))
+        .order((om::year, om::issue, t::title))
+        .load::<(Episode, Title, Option<OtherMag>)>(&mut db)
+        .await?;
+    let items = OrigItem::load_all(&episodes, &mut db).await?;
+    let desc = lang.text(
+        "Serier i svenska Fantomentidningen som tidigare publicerats i {}.",
+        &[&name],
+    );
+    let groups = [("Episoder", items)];
+    Ok(Builder::new()
+        .html(|o| origin_html(o, lang, &name, &desc, &groups))?)
+}
+
+fn item_kind(episode: &Episode) -> &'static str {
+    match episode.orig_dates().map(|d| d.kind()) {
+        Some(kind @ (OrigKind::Daily | OrigKind::Sundays)) => kind.label(),
+        _ => "Övriga episoder",
+    }
+}
+
+/// Summary of episodes previously published in a magazine.
+pub struct MagSummary {
+    pub name: String,
+    pub slug: String,
+    pub n: i64,
+    pub years: Option<(i16, i16)>,
+}
+
+/// An episode in the catalogue of original publications.
+pub struct OrigItem<'a> {
+    pub episode: &'a Episode,
+    pub title: &'a Title,
+    pub orig_mag: Option<&'a OtherMag>,
+    pub published: PartsPublished,
+}
+
+impl<'a> OrigItem<'a> {
+    /// Items for episodes loaded with their titles and original magazines.
+    ///
+    /// The publications of all episodes are loaded in a single query.
+    async fn load_all(
+        episodes: &'a [(Episode, Title, Option<OtherMag>)],
+        db: &mut AsyncPgConnection,
+    ) -> Result<Vec<OrigItem<'a>>> {
+        let ids = episodes.iter().map(|(e, ..)| e.id).collect::<Vec<_>>();
+        let mut published = PartsPublished::for_episodes(&ids, db).await?;
+        Ok(episodes
+            .iter()
+            .map(|(episode, title, orig_mag)| OrigItem {
+                episode,
+                title,
+                orig_mag: orig_mag.as_ref(),
+                published: published.remove(&episode.id).unwrap_or_default(),
+            })
+            .collect())
+    }
+}
@@ -5,6 +5,8 @@ use std::io::{self, Write};
 pub struct Paginator {
     n_pages: usize,
     page: usize,
+    /// Other query parameters to keep in page links, such as filters.
+    query: String,
 }
 
 const PAGE_SIZE: usize = 30;
@@ -13,6 +15,7 @@ impl Paginator {
     pub fn if_needed<T>(
         mut items: Vec<T>,
         page: Option<usize>,
+        query: &str,
     ) -> Result<(Vec<T>, Option<Paginator>), ()> {
         if items.len() / 3 > PAGE_SIZE {
             let n_pages = (items.len() - 1) / PAGE_SIZE + 1;
@@ -22,7 +25,19 @@ impl Paginator {
             }
             items.drain(0..PAGE_SIZE * (page - 1));
             items.truncate(PAGE_SIZE);
-            Ok((items, Some(Paginator { n_pages, page })))
+            let query = if query.is_empty() {
+                String::new()
+            } else {
+                format!("{query}&")
+            };
+            Ok((
+                items,
+                Some(Paginator {
+                    n_pages,
+                    page,
+                    query,
+                }),
+            ))
         } else {
             Ok((items, None))
         }
@@ -31,14 +46,15 @@ impl Paginator {
 
 impl ToHtml for Paginator {
     fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
-        fn one(out: &mut dyn Write, p: usize, pp: usize) -> io::Result<()> {
+        let one = |out: &mut dyn Write, p: usize, pp: usize| {
             if p == pp {
-                write!(out, "<b>{p}</b>")?;
+                write!(out, "<b>{p}</b>")
             } else {
-                write!(out, "<a href='?p={p}'>{p}</a>")?;
+                write!(out, "<a href='?")?;
+                self.query.to_html(out)?;
+                write!(out, "p={p}'>{p}</a>")
             }
-            Ok(())
-        }
+        };
         let from = if self.page > 7 { self.page - 5 } else { 1 };
         let to = if self.page + 7 < self.n_pages {
             self.page + 5
@@ -61,3 +77,15 @@ impl ToHtml for Paginator {
         Ok(())
     }
 }
+
+#[test]
+fn links_keep_query() {
+    let items = (0..100).collect::<Vec<_>>();
+    let (items, pages) =
+        Paginator::if_needed(items, Some(2), "reprinted=true").unwrap();
+    assert_eq!(items.first(), Some(&30));
+    let mut out = Vec::new();
+    pages.unwrap().to_html(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("<a href='?reprinted=true&amp;p=1'>1</a>, <b>2"));
+}
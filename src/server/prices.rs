@@ -0,0 +1,287 @@
+use super::locale::lang;
+use super::{PgFilter, PgPool, Result, ViewResult, goh, wrap};
+
+use crate::i18n::{Lang, ToHtmlIn};
+use crate::models::{IssueRef, Price, kpi_year};
+use crate::schema::issues::dsl as i;
+use crate::templates::{RenderRucte, prices_html};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use warp::filters::BoxedFilter;
+use warp::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use warp::http::response::Builder;
+use warp::reply::{Response, json};
+use warp::{self, Filter, Reply};
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
+    use warp::path::{end, path};
+    let html = path("prices")
+        .and(end())
+        .and(goh())
+        .and(s.clone())
+        .and(lang())
+        .then(price_page);
+    let csv = path("prices.csv")
+        .and(end())
+        .and(goh())
+        .and(s.clone())
+        .then(price_csv);
+    let json = path("prices.json")
+        .and(end())
+        .and(goh())
+        .and(s)
+        .then(price_json);
+    html.or(csv).unify().or(json).unify().map(wrap).boxed()
+}
+
+async fn price_page(db: PgPool, lang: Lang) -> Result<Response> {
+    let mut db = db.get().await?;
+    let prices = PricePoint::load_all(&mut db).await?;
+    let years = YearPrices::summarize(&prices);
+    let chart = PriceChart(&prices);
+    Ok(Builder::new()
+        .html(|o| prices_html(o, lang, kpi_year(), &chart, &years))?)
+}
+
+async fn price_csv(db: PgPool) -> Result<Response> {
+    let mut db = db.get().await?;
+    let prices = PricePoint::load_all(&mut db).await?;
+    let mut csv = String::from(
+        "year,nr,pages,price,real_price,price_per_page,real_price_per_page\n",
+    );
+    for p in prices.iter().map(PriceRow::from) {
+        let opt = |v: Option<f64>| v.map(|v| format!("{v:.2}"));
+        writeln!(
+            csv,
+            "{},{},{},{:.2},{},{},{}",
+            p.year,
+            p.nr,
+            p.pages.map(|p| p.to_string()).unwrap_or_default(),
+            p.price,
+            opt(p.real_price).unwrap_or_default(),
+            opt(p.price_per_page).unwrap_or_default(),
+            opt(p.real_price_per_page).unwrap_or_default(),
+        )
+        .ise()?;
+    }
+    Builder::new()
+        .header(CONTENT_TYPE, "text/csv; charset=utf-8")
+        .header(CONTENT_DISPOSITION, "inline; filename=\"prices.csv\"")
+        .body(csv.into())
+        .ise()
+}
+
+async fn price_json(db: PgPool) -> Result<Response> {
+    let mut db = db.get().await?;
+    let prices = PricePoint::load_all(&mut db).await?;
+    let rows = prices.iter().map(PriceRow::from).collect::<Vec<_>>();
+    Ok(json(&rows).into_response())
+}
+
+/// The cover price of a single issue.
+pub struct PricePoint {
+    pub issue: IssueRef,
+    pub pages: Option<i16>,
+    pub price: Price,
+    /// The price in the money value of the last year with known KPI.
+    pub real: Option<Price>,
+}
+
+impl PricePoint {
+    async fn load_all(
+        db: &mut AsyncPgConnection,
+    ) -> Result<Vec<PricePoint>, diesel::result::Error> {
+        let to_year = kpi_year();
+        Ok(i::issues
+            .select((
+                (i::year, (i::number, i::number_str)),
+                i::pages,
+                i::price,
+            ))
+            .filter(i::price.is_not_null())
+            .order(i::magic)
+            .load::<(IssueRef, Option<i16>, Option<Price>)>(db)
+            .await?
+            .into_iter()
+            .filter_map(|(issue, pages, price)| {
+                let price = price?;
+                let real = price.adjusted(issue.year, to_year);
+                Some(PricePoint {
+                    issue,
+                    pages,
+                    price,
+                    real,
+                })
+            })
+            .collect())
+    }
+    pub fn per_page(&self) -> Option<Price> {
+        self.price.per_page(self.pages?)
+    }
+    pub fn real_per_page(&self) -> Option<Price> {
+        self.real?.per_page(self.pages?)
+    }
+}
+
+/// A row in the csv or json export of prices.
+#[derive(Serialize)]
+struct PriceRow {
+    year: i16,
+    nr: String,
+    pages: Option<i16>,
+    price: f64,
+    real_price: Option<f64>,
+    price_per_page: Option<f64>,
+    real_price_per_page: Option<f64>,
+}
+
+impl From<&PricePoint> for PriceRow {
+    fn from(p: &PricePoint) -> Self {
+        PriceRow {
+            year: p.issue.year,
+            nr: p.issue.number.to_string(),
+            pages: p.pages,
+            price: p.price.as_kr(),
+            real_price: p.real.map(Price::as_kr),
+            price_per_page: p.per_page().map(Price::as_kr),
+            real_price_per_page: p.real_per_page().map(Price::as_kr),
+        }
+    }
+}
+
+/// Summary of the prices of one year.
+pub struct YearPrices {
+    pub year: i16,
+    /// The distinct prices of the year, in order of appearance.
+    pub prices: Vec<(Price, Option<Price>)>,
+    /// Average real price per page, for issues with known pages.
+    pub real_per_page: Option<Price>,
+}
+
+impl YearPrices {
+    fn summarize(prices: &[PricePoint]) -> Vec<YearPrices> {
+        let mut years = BTreeMap::<i16, Vec<&PricePoint>>::new();
+        for p in prices {
+            years.entry(p.issue.year).or_default().push(p);
+        }
+        years
+            .into_iter()
+            .map(|(year, points)| {
+                let mut prices = Vec::<(Price, Option<Price>)>::new();
+                for p in &points {
+                    if prices.last().is_none_or(|l| l.0 != p.price) {
+                        prices.push((p.price, p.real));
+                    }
+                }
+                let per_page = points
+                    .iter()
+                    .filter_map(|p| p.real_per_page())
+                    .map(Price::as_ore)
+                    .collect::<Vec<_>>();
+                let real_per_page = (!per_page.is_empty()).then(|| {
+                    let n = i32::try_from(per_page.len()).unwrap_or(i32::MAX);
+                    Price::from_ore(per_page.iter().sum::<i32>() / n)
+                });
+                YearPrices {
+                    year,
+                    prices,
+                    real_per_page,
+                }
+            })
+            .collect()
+    }
+}
+
+/// An svg line chart of nominal and real prices.
+pub struct PriceChart<'a>(&'a [PricePoint]);
+
+impl ToHtmlIn for PriceChart<'_> {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
+        let (Some(first), Some(last)) = (self.0.first(), self.0.last())
+        else {
+            return Ok(());
+        };
+        let (w, h, margin) = (800., 300., 40.);
+        let (y0, y1) = (first.issue.year, last.issue.year + 1);
+        let max = self
+            .0
+            .iter()
+            .flat_map(|p| [Some(p.price), p.real])
+            .flatten()
+            .max()
+            .map_or(1., Price::as_kr);
+        let step = [1., 2., 5., 10., 20., 50., 100., 200.]
+            .into_iter()
+            .find(|s| max / s <= 8.)
+            .unwrap_or(500.);
+        let top = (max / step).ceil() * step;
+        let x = |year: f64| {
+            margin
+                + (year - f64::from(y0)) * (w - margin - 10.)
+                    / f64::from(y1 - y0)
+        };
+        let y = |kr: f64| h - margin - kr * (h - margin - 10.) / top;
+        // Issues per year are not known here, so spread them over
+        // the year according to the highest number seen that year.
+        let mut per_year = BTreeMap::<i16, i16>::new();
+        for p in self.0 {
+            let nr = &p.issue.number;
+            let e = per_year.entry(p.issue.year).or_default();
+            *e = (*e).max(nr.last());
+        }
+        let t = |p: &PricePoint| {
+            let nr = &p.issue.number;
+            let n = per_year.get(&p.issue.year).copied().unwrap_or(1);
+            f64::from(p.issue.year) + f64::from(nr.first() - 1) / f64::from(n)
+        };
+
+        write!(
+            out,
+            "<svg class='pricechart' viewBox='0 0 {w} {h}' role='img' \
+             aria-label='{}'>",
+            lang.t("Pris per nummer"),
+        )?;
+        let mut tick = 0.;
+        while tick <= top {
+            write!(
+                out,
+                "<line class='grid' x1='{x0}' x2='{x1}' y1='{y:.1}' y2='{y:.1}'/>\
+                 <text class='ylabel' x='{xt}' y='{y:.1}'>{tick}</text>",
+                x0 = margin,
+                x1 = w - 10.,
+                xt = margin - 4.,
+                y = y(tick),
+            )?;
+            tick += step;
+        }
+        for year in (y0..y1).filter(|y| y % 10 == 0) {
+            write!(
+                out,
+                "<line class='grid' x1='{x:.1}' x2='{x:.1}' y1='10' y2='{y}'/>\
+                 <text class='xlabel' x='{x:.1}' y='{yt}'>{year}</text>",
+                x = x(f64::from(year)),
+                y = h - margin,
+                yt = h - margin + 16.,
+            )?;
+        }
+        let line = |out: &mut dyn Write,
+                    class: &str,
+                    value: &dyn Fn(&PricePoint) -> Option<Price>|
+         -> io::Result<()> {
+            write!(out, "<polyline class='{class}' points='")?;
+            for p in self.0 {
+                if let Some(v) = value(p) {
+                    write!(out, "{:.1},{:.1} ", x(t(p)), y(v.as_kr()))?;
+                }
+            }
+            write!(out, "'/>")
+        };
+        line(out, "real", &|p| p.real)?;
+        line(out, "nominal", &|p| Some(p.price))?;
+        write!(out, "</svg>")
+    }
+}
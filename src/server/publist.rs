@@ -1,21 +1,21 @@
 use super::DbError;
-use crate::models::{
-    Creator, CreatorSet, Episode, Issue, IssueRef, PartInIssue, Title,
-};
+use crate::i18n::{Lang, ToHtmlIn};
+use crate::models::{Creator, Episode, Issue, IssueRef, PartInIssue, Title};
 use crate::schema::creator_aliases::dsl as ca;
 use crate::schema::episode_parts::dsl as ep;
 use crate::schema::episodes::dsl as e;
 use crate::schema::episodes_by::dsl as eb;
 use crate::schema::issues::dsl as i;
 use crate::schema::publications::dsl as p;
+use crate::schema::roles::dsl as ro;
 use crate::schema::titles::dsl as t;
-use crate::templates::ToHtml;
 use diesel::dsl::min;
 use diesel::prelude::*;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use std::collections::BTreeMap;
 use std::io::{self, Write};
 
+#[derive(Default)]
 pub struct PartsPublished {
     issues: Vec<PartInIssue>,
     others: bool,
@@ -49,6 +49,32 @@ impl PartsPublished {
         })
     }
 
+    /// Publications for many episodes at once, by episode id.
+    pub async fn for_episodes(
+        episodes: &[i32],
+        db: &mut AsyncPgConnection,
+    ) -> Result<BTreeMap<i32, PartsPublished>, DbError> {
+        let data = i::issues
+            .inner_join(p::publications.inner_join(ep::episode_parts))
+            .select((
+                ep::episode_id,
+                (
+                    (i::year, (i::number, i::number_str)),
+                    (ep::part_no, ep::part_name),
+                    p::best_plac,
+                ),
+            ))
+            .filter(ep::episode_id.eq_any(episodes))
+            .order((i::year, i::number))
+            .load::<(i32, PartInIssue)>(db)
+            .await?;
+        let mut result = BTreeMap::<i32, PartsPublished>::new();
+        for (episode, part) in data {
+            result.entry(episode).or_default().issues.push(part);
+        }
+        Ok(result)
+    }
+
     pub async fn for_episode_except(
         episode: &Episode,
         issue: &Issue,
@@ -83,35 +109,35 @@ impl PartsPublished {
 
 pub struct SmallPartsPublished<'a>(&'a PartsPublished);
 
-impl ToHtml for PartsPublished {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for PartsPublished {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         if let Some((last, pubs)) = self.issues.split_last() {
             out.write_all(b"<p class='info pub'>")?;
             if self.others {
-                out.write_all("Även publicerad i ".as_bytes())?;
+                write!(out, "{} ", lang.t("Även publicerad i"))?;
             } else {
-                out.write_all(b"Publicerad i ")?;
+                write!(out, "{} ", lang.t("Publicerad i"))?;
             }
             for p in pubs {
-                p.to_html(out)?;
+                p.to_html_in(lang, out)?;
                 out.write_all(b", ")?;
             }
-            last.to_html(out)?;
+            last.to_html_in(lang, out)?;
             out.write_all(b".</p>")?;
         }
         Ok(())
     }
 }
 
-impl ToHtml for SmallPartsPublished<'_> {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for SmallPartsPublished<'_> {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         if let Some((last, pubs)) = self.0.issues.split_last() {
             out.write_all(b"<small class='pub'>")?;
             for p in pubs {
-                p.to_html(out)?;
+                p.to_html_in(lang, out)?;
                 out.write_all(b", ")?;
             }
-            last.to_html(out)?;
+            last.to_html_in(lang, out)?;
             out.write_all(b".</small>")?;
         }
         Ok(())
@@ -119,7 +145,8 @@ impl ToHtml for SmallPartsPublished<'_> {
 }
 
 pub struct OtherContribs {
-    pub roles: String,
+    /// Nouns for the roles, to be translated.
+    roles: Vec<String>,
     pub episodes: BTreeMap<Title, Vec<(Option<String>, PartsPublished)>>,
 }
 
@@ -132,8 +159,9 @@ impl OtherContribs {
         let other_episodes = eb::episodes_by
             .select(eb::episode_id)
             .inner_join(ca::creator_aliases)
+            .inner_join(ro::roles)
             .filter(ca::creator_id.eq(creator.id))
-            .filter(eb::role.ne_all(CreatorSet::MAIN_ROLES));
+            .filter(ro::main.eq(false));
 
         let other_episodes = e::episodes
             .inner_join(t::titles)
@@ -158,28 +186,28 @@ impl OtherContribs {
 
         let o_roles = eb::episodes_by
             .inner_join(ca::creator_aliases)
+            .inner_join(ro::roles)
             .filter(ca::creator_id.eq(creator.id))
-            .filter(eb::role.ne_all(CreatorSet::MAIN_ROLES))
-            .select(eb::role)
+            .filter(ro::main.eq(false))
+            .select((ro::ord, ro::noun))
             .distinct()
-            .load::<String>(db)
+            .order(ro::ord)
+            .load::<(i16, String)>(db)
             .await?
             .into_iter()
-            .map(|r| match r.as_ref() {
-                "color" => "färgläggare",
-                "redax" => "redaktion",
-                "xlat" => "översättare",
-                "textning" => "textsättare",
-                _ => "något annat",
-            })
-            .collect::<Vec<_>>()
-            .join(", ");
+            .map(|(_, noun)| noun)
+            .collect();
         Ok(OtherContribs {
             roles: o_roles,
             episodes: oe,
         })
     }
 
+    pub fn roles(&self, lang: Lang) -> String {
+        let roles = self.roles.iter().map(|r| lang.t(r));
+        roles.collect::<Vec<_>>().join(", ")
+    }
+
     pub fn is(&self) -> bool {
         !self.episodes.is_empty()
     }
@@ -1,8 +1,13 @@
+use super::locale::lang;
 use super::{
     DbError, FullArticle, FullEpisode, PgFilter, PgPool, Result, ViewError,
     goh, redirect, wrap,
 };
-use crate::models::{Article, Episode, IdRefKey, IssueRef, RefKey, Title};
+use crate::i18n::Lang;
+use crate::models::{
+    Article, Episode, IdRefKey, IssueRef, RefKey, RefKeyInfo,
+    RefKeyRelations, Title,
+};
 use crate::schema::article_refkeys::dsl as ar;
 use crate::schema::articles::dsl as a;
 use crate::schema::episode_parts::dsl as ep;
@@ -10,12 +15,15 @@ use crate::schema::episode_refkeys::dsl as er;
 use crate::schema::episodes::dsl as e;
 use crate::schema::issues::dsl as i;
 use crate::schema::publications::dsl as p;
+use crate::schema::refkey_aliases::dsl as ra;
+use crate::schema::refkey_info::dsl as ri;
 use crate::schema::refkeys::dsl as r;
 use crate::schema::titles::dsl as t;
 use crate::templates::{RenderRucte, refkey_html, refkeys_html};
 use diesel::dsl::{count_star, max, min, sql};
+use diesel::expression::SqlLiteral;
 use diesel::prelude::*;
-use diesel::sql_types::Integer;
+use diesel::sql_types::{Bool, Integer};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use tracing::debug;
 use warp::filters::BoxedFilter;
@@ -30,6 +38,7 @@ pub fn fa_route(s: PgFilter) -> BoxedFilter<(ByteResponse,)> {
         .and(end())
         .and(goh())
         .and(s)
+        .and(lang())
         .then(one_fa)
         .map(wrap)
         .boxed()
@@ -37,8 +46,13 @@ pub fn fa_route(s: PgFilter) -> BoxedFilter<(ByteResponse,)> {
 
 pub fn what_routes(s: PgFilter) -> BoxedFilter<(ByteResponse,)> {
     use warp::path::{end, param};
-    let list = end().and(goh()).and(s.clone()).then(list_refs);
-    let one = param().and(end()).and(goh()).and(s).then(one_ref);
+    let list = end().and(goh()).and(s.clone()).and(lang()).then(list_refs);
+    let one = param()
+        .and(end())
+        .and(goh())
+        .and(s)
+        .and(lang())
+        .then(one_ref);
     list.or(one).unify().map(wrap).boxed()
 }
 
@@ -55,16 +69,43 @@ pub async fn get_all_fa(
         .collect())
 }
 
-async fn list_refs(db: PgPool) -> Result<ByteResponse> {
+/// A refkey in the list of all refkeys.
+pub struct ListedRef {
+    pub refkey: RefKey,
+    /// The Swedish noun for the kind of refkey, if known.
+    pub kind: Option<&'static str>,
+    pub n_episodes: i64,
+    pub first: Option<IssueRef>,
+    pub last: Option<IssueRef>,
+}
+
+/// Filter episode refkeys on the listed refkey or any of its descendants.
+///
+/// The same expansion as [`RefKeyRelations::with_descendants`], as a
+/// recursive query so all refkeys can be counted in a single query.
+fn refkey_or_descendant() -> SqlLiteral<Bool> {
+    sql("episode_refkeys.refkey_id in (\
+         with recursive d(id) as (\
+          select refkeys.id \
+          union select rp.refkey_id from refkey_parents rp \
+          join d on rp.parent_id = d.id) \
+         select id from d)")
+}
+
+async fn list_refs(db: PgPool, lang: Lang) -> Result<ByteResponse> {
     let mut db = db.get().await?;
     let all = r::refkeys
         .filter(r::kind.eq(RefKey::KEY_ID))
         .select((
             IdRefKey::as_select(),
+            ri::refkey_info
+                .select(ri::kind)
+                .filter(ri::refkey_id.eq(r::id))
+                .single_value(),
             er::episode_refkeys
                 .select(er::episode_id)
                 .distinct()
-                .filter(er::refkey_id.eq(r::id))
+                .filter(refkey_or_descendant())
                 .count()
                 .single_value(),
             i::issues
@@ -74,7 +115,7 @@ async fn list_refs(db: PgPool) -> Result<ByteResponse> {
                     ),
                 ))
                 .select(min(i::magic))
-                .filter(er::refkey_id.eq(r::id))
+                .filter(refkey_or_descendant())
                 .single_value(),
             i::issues
                 .left_join(p::publications.left_join(
@@ -83,37 +124,51 @@ async fn list_refs(db: PgPool) -> Result<ByteResponse> {
                     ),
                 ))
                 .select(max(i::magic))
-                .filter(er::refkey_id.eq(r::id))
+                .filter(refkey_or_descendant())
                 .single_value(),
         ))
         .order(r::title)
-        .load::<(IdRefKey, Option<i64>, Option<i16>, Option<i16>)>(&mut db)
+        .load::<(
+            IdRefKey,
+            Option<String>,
+            Option<i64>,
+            Option<i16>,
+            Option<i16>,
+        )>(&mut db)
         .await?
         .into_iter()
-        .map(|(refkey, c, first, last)| {
-            (
-                refkey.refkey,
-                c.unwrap_or(0),
-                first.map(IssueRef::from_magic),
-                last.map(IssueRef::from_magic),
-            )
+        .map(|(refkey, kind, c, first, last)| ListedRef {
+            refkey: refkey.refkey,
+            kind: kind.as_deref().and_then(RefKeyInfo::noun),
+            n_episodes: c.unwrap_or(0),
+            first: first.map(IssueRef::from_magic),
+            last: last.map(IssueRef::from_magic),
         })
         .collect::<Vec<_>>();
-    Ok(Response::builder().html(|o| refkeys_html(o, &all))?)
+    Ok(Response::builder().html(|o| refkeys_html(o, lang, &all))?)
 }
 
-async fn one_fa(slug: String, db: PgPool) -> Result<ByteResponse> {
-    one_ref_impl(db, slug, RefKey::FA_ID).await
+async fn one_fa(
+    slug: String,
+    db: PgPool,
+    lang: Lang,
+) -> Result<ByteResponse> {
+    one_ref_impl(db, slug, RefKey::FA_ID, lang).await
 }
 
-async fn one_ref(slug: String, db: PgPool) -> Result<ByteResponse> {
-    one_ref_impl(db, slug, RefKey::KEY_ID).await
+async fn one_ref(
+    slug: String,
+    db: PgPool,
+    lang: Lang,
+) -> Result<ByteResponse> {
+    one_ref_impl(db, slug, RefKey::KEY_ID, lang).await
 }
 
 async fn one_ref_impl(
     db: PgPool,
     slug: String,
     kind: i16,
+    lang: Lang,
 ) -> Result<ByteResponse> {
     let mut db = db.get().await?;
     let refkey = r::refkeys
@@ -123,27 +178,19 @@ async fn one_ref_impl(
         .await
         .optional()?;
     let Some(refkey) = refkey else {
-        if kind == RefKey::FA_ID {
-            // Some special cases
-            if slug == "17.1" {
-                return redirect("/fa/17j");
-            } else if slug == "22.1" {
-                return redirect("/fa/22k");
-            } else if slug == "22.2" {
-                return redirect("/fa/22j");
-            }
-        }
-        if kind == RefKey::KEY_ID {
-            if slug == "christophe_derrant" {
-                return redirect("/what/christophe-d-errant");
-            } else if slug == "olangofolket" {
-                return redirect("/what/olango-folket");
-            } else if slug == "/what/piratpete" {
-                return redirect("/what/pirat-pete");
-            }
-        }
         let target =
             slug.to_lowercase().replace('_', "-").replace(".html", "");
+        let alias = r::refkeys
+            .inner_join(ra::refkey_aliases)
+            .select((r::kind, r::title, r::slug))
+            .filter(ra::kind.eq(kind))
+            .filter(ra::slug.eq_any([&slug, &target]))
+            .first::<RefKey>(&mut db)
+            .await
+            .optional()?;
+        if let Some(alias) = alias {
+            return redirect(&alias.url());
+        }
         if target != slug {
             debug!("Trying refkey redirect {:?} -> {:?}", slug, target);
             let n = r::refkeys
@@ -162,11 +209,27 @@ async fn one_ref_impl(
         }
         return Err(ViewError::NotFound);
     };
+    let info = RefKeyInfo::load(refkey.id, &mut db).await?;
+    let relations = RefKeyRelations::load(refkey.id, &mut db).await?;
+    let first = i::issues
+        .inner_join(
+            p::publications.inner_join(
+                ep::episode_parts
+                    .inner_join(e::episodes.inner_join(er::episode_refkeys)),
+            ),
+        )
+        .select(min(i::magic))
+        .filter(er::refkey_id.eq(refkey.id))
+        .first::<Option<i16>>(&mut db)
+        .await?
+        .map(IssueRef::from_magic);
+    // Things about a child refkey are also about this refkey.
+    let ids = RefKeyRelations::with_descendants(refkey.id, &mut db).await?;
 
     let raw_articles = a::articles
         .select(Article::as_select())
         .left_join(ar::article_refkeys.left_join(r::refkeys))
-        .filter(ar::refkey_id.eq(refkey.id))
+        .filter(ar::refkey_id.eq_any(&ids))
         .inner_join(p::publications.inner_join(i::issues))
         .order(min(i::magic))
         .group_by(a::articles::all_columns())
@@ -190,7 +253,7 @@ async fn one_ref_impl(
             e::id.eq_any(
                 er::episode_refkeys
                     .select(er::episode_id)
-                    .filter(er::refkey_id.eq(refkey.id)),
+                    .filter(er::refkey_id.eq_any(&ids)),
             ),
         )
         .select((Title::as_select(), Episode::as_select()))
@@ -209,6 +272,25 @@ async fn one_ref_impl(
         episodes.push((t, e));
     }
 
-    Ok(Response::builder()
-        .html(|o| refkey_html(o, &refkey.refkey, &articles, &episodes))?)
+    let page = RefKeyPage {
+        refkey: refkey.refkey,
+        info,
+        first,
+        relations,
+        articles,
+        episodes,
+    };
+    Ok(Response::builder().html(|o| refkey_html(o, lang, &page))?)
+}
+
+/// Everything shown on the page for a refkey.
+pub struct RefKeyPage {
+    pub refkey: RefKey,
+    pub info: RefKeyInfo,
+    /// The first issue with an episode about the refkey.
+    pub first: Option<IssueRef>,
+    pub relations: RefKeyRelations,
+    /// Articles about the refkey, with the issues they are in.
+    pub articles: Vec<(FullArticle, Vec<IssueRef>)>,
+    pub episodes: Vec<(Title, FullEpisode)>,
 }
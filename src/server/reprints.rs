@@ -0,0 +1,266 @@
+//! Episodes published more than once.
+use super::locale::lang;
+use super::{DbError, PgFilter, PgPool, Result, goh, wrap};
+use crate::i18n::Lang;
+use crate::models::{IssueRef, Title};
+use crate::schema::episode_parts::dsl as ep;
+use crate::schema::episodes::dsl as e;
+use crate::schema::issues::dsl as i;
+use crate::schema::publications::dsl as p;
+use crate::schema::titles::dsl as t;
+use crate::templates::{RenderRucte, reprints_html};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Bool;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::{BTreeMap, BTreeSet};
+use warp::filters::BoxedFilter;
+use warp::http::response::Builder;
+use warp::reply::Response;
+use warp::{self, Filter, Reply};
+
+pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
+    use warp::path::{end, path};
+    path("reprints")
+        .and(end())
+        .and(goh())
+        .and(s)
+        .and(lang())
+        .then(reprints)
+        .map(wrap)
+        .boxed()
+}
+
+async fn reprints(db: PgPool, lang: Lang) -> Result<Response> {
+    let mut db = db.get().await?;
+    let episodes = Reprinted::load_all(&mut db).await?;
+    Ok(Builder::new().html(|o| reprints_html(o, lang, &episodes))?)
+}
+
+/// Ids of episodes published more than once, optionally for one title.
+///
+/// An episode is published more than once if it is published in more
+/// than one issue, and either the same part is published more than
+/// once or the whole episode is published in one of the issues.
+pub async fn reprinted_ids(
+    title: Option<i32>,
+    db: &mut AsyncPgConnection,
+) -> Result<BTreeSet<i32>, DbError> {
+    let mut query = ep::episode_parts
+        .inner_join(p::publications)
+        .select(ep::episode_id)
+        .group_by(ep::episode_id)
+        .having(sql::<Bool>(
+            "count(distinct publications.issue_id) > 1 and (\
+             bool_or(part_no is null and part_name is null) or \
+             count(*) > count(distinct episode_parts.id))",
+        ))
+        .into_boxed();
+    if let Some(title) = title {
+        query =
+            query.filter(ep::episode_id.eq_any(
+                e::episodes.select(e::id).filter(e::title_id.eq(title)),
+            ));
+    }
+    Ok(query.load::<i32>(db).await?.into_iter().collect())
+}
+
+/// An episode that is published more than once.
+pub struct Reprinted {
+    pub title: Title,
+    pub name: Option<String>,
+    pub runs: Vec<PubRun>,
+}
+
+impl Reprinted {
+    async fn load_all(
+        db: &mut AsyncPgConnection,
+    ) -> Result<Vec<Reprinted>, DbError> {
+        let ids = reprinted_ids(None, db).await?;
+        let episodes = e::episodes
+            .inner_join(t::titles)
+            .select((e::id, e::name, Title::as_select()))
+            .filter(e::id.eq_any(&ids))
+            .load::<(i32, Option<String>, Title)>(db)
+            .await?;
+        let mut pubs = BTreeMap::<i32, Vec<Pub>>::new();
+        for (episode, part_no, part_name, seqno, issue) in ep::episode_parts
+            .inner_join(p::publications.inner_join(i::issues))
+            .select((
+                ep::episode_id,
+                ep::part_no,
+                ep::part_name,
+                p::seqno,
+                (i::year, (i::number, i::number_str)),
+            ))
+            .filter(ep::episode_id.eq_any(&ids))
+            .order((i::magic, ep::part_no))
+            .load::<(i32, Option<i16>, Option<String>, Option<i16>, IssueRef)>(
+                db,
+            )
+            .await?
+        {
+            let part = (part_no.is_some() || part_name.is_some())
+                .then_some((part_no, part_name));
+            pubs.entry(episode).or_default().push(Pub {
+                part,
+                prevpub: seqno.is_none(),
+                issue,
+            });
+        }
+        let mut result = episodes
+            .into_iter()
+            .map(|(id, name, title)| Reprinted {
+                title,
+                name,
+                runs: PubRun::split(pubs.remove(&id).unwrap_or_default()),
+            })
+            .collect::<Vec<_>>();
+        result.sort_by(|a, b| {
+            a.title
+                .cmp(&b.title)
+                .then_with(|| first_year(a).cmp(&first_year(b)))
+        });
+        Ok(result)
+    }
+}
+
+fn first_year(r: &Reprinted) -> Option<i16> {
+    r.runs.iter().map(|r| r.first.year).min()
+}
+
+/// A publication of part or all of an episode in one issue.
+struct Pub {
+    /// Part number and/or name, None for the whole episode.
+    part: Option<(Option<i16>, Option<String>)>,
+    /// True for a publication known only by a note about previous
+    /// publication, so the contents are unknown.
+    prevpub: bool,
+    issue: IssueRef,
+}
+
+/// One publication of an episode, possibly split over several issues.
+pub struct PubRun {
+    pub first: IssueRef,
+    pub last: IssueRef,
+    /// Number of distinct parts published, or None for the whole.
+    pub parts: Option<usize>,
+    /// Total number of known parts of the episode.
+    pub of_parts: usize,
+    /// True if known only from a note about previous publication.
+    pub prevpub: bool,
+    /// Years since the previous publication started.
+    pub years_after: Option<i16>,
+}
+
+impl PubRun {
+    /// Split publications, in order, into separate runs.
+    ///
+    /// A new run starts when a part is published again, or when the
+    /// whole episode is published after any part.
+    fn split(pubs: Vec<Pub>) -> Vec<PubRun> {
+        let of_parts = pubs
+            .iter()
+            .filter_map(|p| p.part.as_ref())
+            .collect::<BTreeSet<_>>()
+            .len();
+        let mut groups: Vec<Vec<Pub>> = Vec::new();
+        for pb in pubs {
+            let new_run = groups.last().is_none_or(|run| {
+                pb.part.is_none()
+                    || run
+                        .iter()
+                        .any(|r| r.part.is_none() || r.part == pb.part)
+            });
+            if new_run {
+                groups.push(vec![pb]);
+            } else if let Some(run) = groups.last_mut() {
+                run.push(pb);
+            }
+        }
+        let mut runs = Vec::<PubRun>::with_capacity(groups.len());
+        for group in groups {
+            let ([first, ..], [.., last]) = (&group[..], &group[..]) else {
+                continue;
+            };
+            let parts = if group.iter().any(|p| p.part.is_none()) {
+                None
+            } else {
+                Some(
+                    group
+                        .iter()
+                        .filter_map(|p| p.part.as_ref())
+                        .collect::<BTreeSet<_>>()
+                        .len(),
+                )
+            };
+            runs.push(PubRun {
+                first: first.issue.clone(),
+                last: last.issue.clone(),
+                parts,
+                of_parts,
+                prevpub: group.iter().all(|p| p.prevpub),
+                years_after: runs
+                    .last()
+                    .map(|r| first.issue.year - r.first.year),
+            });
+        }
+        runs
+    }
+
+    pub fn is_partial(&self) -> bool {
+        self.parts.is_some_and(|n| n < self.of_parts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Pub, PubRun};
+    use crate::models::IssueRef;
+
+    fn pb(part: Option<i16>, year: i16, nr: &str) -> Pub {
+        Pub {
+            part: part.map(|n| (Some(n), None)),
+            prevpub: false,
+            issue: IssueRef {
+                year,
+                number: nr.parse().unwrap(),
+            },
+        }
+    }
+
+    #[test]
+    fn serial_is_one_run() {
+        let runs = PubRun::split(vec![
+            pb(Some(1), 1970, "4"),
+            pb(Some(2), 1970, "5"),
+        ]);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].parts, Some(2));
+        assert!(!runs[0].is_partial());
+    }
+
+    #[test]
+    fn partial_then_whole() {
+        let runs = PubRun::split(vec![
+            pb(Some(1), 1970, "4-5"),
+            pb(None, 1995, "2"),
+        ]);
+        assert_eq!(runs.len(), 2);
+        assert!(!runs[0].is_partial()); // Only one part known.
+        assert_eq!(runs[1].parts, None);
+        assert_eq!(runs[1].years_after, Some(25));
+    }
+
+    #[test]
+    fn partial_reprint() {
+        let runs = PubRun::split(vec![
+            pb(Some(1), 1970, "4"),
+            pb(Some(2), 1970, "5"),
+            pb(Some(1), 1980, "7"),
+        ]);
+        assert_eq!(runs.len(), 2);
+        assert!(runs[1].is_partial());
+        assert_eq!(runs[1].years_after, Some(10));
+    }
+}
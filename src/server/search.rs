@@ -1,4 +1,5 @@
 use super::{FullArticle, FullEpisode, PgPool, Result};
+use crate::i18n::Lang;
 use crate::models::{
     Article, Creator, Episode, IdRefKey, IssueRef, RefKey, Title,
 };
@@ -9,7 +10,7 @@ use crate::schema::creator_aliases::dsl as ca;
 use crate::schema::creators::dsl as c;
 use crate::schema::episode_parts::dsl as ep;
 use crate::schema::episode_refkeys::dsl as er;
-use crate::schema::episodes::dsl as e;
+use crate::schema::episodes::{self, dsl as e};
 use crate::schema::episodes_by::dsl as eb;
 use crate::schema::issues::dsl as i;
 use crate::schema::publications::dsl as p;
@@ -18,6 +19,7 @@ use crate::schema::titles::dsl as t;
 use crate::templates::{RenderRucte, search_html};
 use diesel::PgTextExpressionMethods;
 use diesel::dsl::{max, sql};
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel::sql_types::Text;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
@@ -30,13 +32,14 @@ use warp::{self, Reply};
 pub async fn search(
     query: Vec<(String, String)>,
     db: PgPool,
+    lang: Lang,
 ) -> Result<impl Reply> {
     let mut db = db.get().await?;
     let query = SearchQuery::load(query, &mut db).await?;
     let (titles, creators, refkeys, episodes) =
         query.do_search(&mut db).await?;
     Ok(Response::builder().html(|o| {
-        search_html(o, &query, &titles, &creators, &refkeys, &episodes)
+        search_html(o, lang, &query, &titles, &creators, &refkeys, &episodes)
     })?)
 }
 
@@ -131,7 +134,7 @@ impl SearchQuery {
             k: vec![],
         }
     }
-    async fn load(
+    pub async fn load(
         query: Vec<(String, String)>,
         db: &mut AsyncPgConnection,
     ) -> Result<Self, diesel::result::Error> {
@@ -154,6 +157,34 @@ impl SearchQuery {
             && self.p.is_empty()
             && self.k.is_empty()
     }
+    /// Episodes matching all the title, creator and refkey filters.
+    pub fn filtered_episodes(&self) -> episodes::BoxedQuery<'_, Pg> {
+        let mut episodes = e::episodes.into_boxed();
+        for title in &self.t {
+            episodes = episodes.filter(e::title_id.eq(title.id));
+        }
+        for creator in &self.p {
+            episodes = episodes.filter(
+                e::id.eq_any(
+                    eb::episodes_by
+                        .select(eb::episode_id)
+                        .inner_join(ca::creator_aliases)
+                        .filter(ca::creator_id.eq(creator.id)),
+                ),
+            );
+        }
+        for key in &self.k {
+            episodes = episodes.filter(
+                e::id.eq_any(
+                    er::episode_refkeys
+                        .select(er::episode_id)
+                        .filter(er::refkey_id.eq(key.id)),
+                ),
+            );
+        }
+        episodes
+    }
+
     async fn do_search(
         &self,
         db: &mut AsyncPgConnection,
@@ -230,7 +261,6 @@ impl SearchQuery {
                         .filter(e::title_id.eq(title.id)),
                 ),
             );
-            episodes = episodes.filter(e::title_id.eq(title.id));
             articles = articles.filter(
                 a::id.eq_any(
                     ar::article_refkeys
@@ -262,14 +292,6 @@ impl SearchQuery {
                         .filter(ca::creator_id.eq(creator.id)),
                 ),
             );
-            episodes = episodes.filter(
-                e::id.eq_any(
-                    eb::episodes_by
-                        .select(eb::episode_id)
-                        .inner_join(ca::creator_aliases)
-                        .filter(ca::creator_id.eq(creator.id)),
-                ),
-            );
             articles = articles.filter({
                 let by = ab::articles_by
                     .select(ab::article_id)
@@ -301,13 +323,6 @@ impl SearchQuery {
                         .filter(er::refkey_id.eq(key.id)),
                 ),
             );
-            episodes = episodes.filter(
-                e::id.eq_any(
-                    er::episode_refkeys
-                        .select(er::episode_id)
-                        .filter(er::refkey_id.eq(key.id)),
-                ),
-            );
             articles = articles.filter(
                 a::id.eq_any(
                     ar::article_refkeys
@@ -317,6 +332,11 @@ impl SearchQuery {
             );
         }
 
+        if !self.t.is_empty() || !self.p.is_empty() || !self.k.is_empty() {
+            episodes = episodes
+                .filter(e::id.eq_any(self.filtered_episodes().select(e::id)));
+        }
+
         let creators = if self.q.is_empty() {
             vec![]
         } else {
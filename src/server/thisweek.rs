@@ -0,0 +1,76 @@
+//! Issues released this week in earlier years, for the frontpage.
+use super::DbError;
+use crate::models::{IssueRef, Nr};
+use crate::schema::issues::dsl as i;
+use chrono::{Datelike, Local, NaiveDate};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::BTreeMap;
+
+/// Issues released the same week of the year as now, some years ago.
+///
+/// The release date is used when known.  Otherwise it is estimated
+/// from the number, assuming the issues of a year are evenly spread.
+pub struct ThisWeek(Vec<(i32, IssueRef)>);
+
+impl ThisWeek {
+    pub async fn load(db: &mut AsyncPgConnection) -> Result<Self, DbError> {
+        let today = Local::now().date_naive();
+        let issues = i::issues
+            .select(((i::year, (i::number, i::number_str)), i::date))
+            .filter(
+                i::year.lt(i16::try_from(today.year()).unwrap_or(i16::MAX)),
+            )
+            .order(i::magic)
+            .load::<(IssueRef, Option<NaiveDate>)>(db)
+            .await?;
+        let mut per_year = BTreeMap::<i16, i16>::new();
+        for (issue, _) in &issues {
+            let n = per_year.entry(issue.year).or_default();
+            *n = (*n).max(issue.number.last());
+        }
+        let week = today.iso_week().week();
+        let mut result = issues
+            .into_iter()
+            .filter_map(|(issue, date)| {
+                let date = date.or_else(|| {
+                    estimate(&issue, *per_year.get(&issue.year)?)
+                })?;
+                (date.iso_week().week() == week)
+                    .then(|| (today.year() - i32::from(issue.year), issue))
+            })
+            .collect::<Vec<_>>();
+        result.sort_by_key(|(ago, _)| *ago);
+        Ok(ThisWeek(result))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The issues, with how many years ago they were released.
+    pub fn issues(&self) -> &[(i32, IssueRef)] {
+        &self.0
+    }
+}
+
+/// Estimate a release date from the issue number.
+fn estimate(issue: &IssueRef, per_year: i16) -> Option<NaiveDate> {
+    let per_year = u32::try_from(per_year).ok().filter(|n| *n > 0)?;
+    let number = u32::try_from(Nr::first(&issue.number)).ok()?;
+    let ordinal = 1 + number.saturating_sub(1) * 365 / per_year;
+    NaiveDate::from_yo_opt(issue.year.into(), ordinal)
+}
+
+#[test]
+fn estimate_spreads_issues() {
+    let issue = |nr: &str| IssueRef {
+        year: 1970,
+        number: nr.parse().unwrap(),
+    };
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+    assert_eq!(estimate(&issue("1"), 26), date(1970, 1, 1));
+    assert_eq!(estimate(&issue("14"), 26), date(1970, 7, 2));
+    assert_eq!(estimate(&issue("25-26"), 26), date(1970, 12, 3));
+    assert_eq!(estimate(&issue("1"), 0), None);
+}
@@ -0,0 +1,196 @@
+//! A timeline of activity per year for a creator or a title.
+use crate::i18n::{Lang, ToHtmlIn};
+use crate::models::Role;
+use crate::schema::covers_by::dsl as cb;
+use crate::schema::creator_aliases::dsl as ca;
+use crate::schema::episode_parts::dsl as ep;
+use crate::schema::episodes::dsl as e;
+use crate::schema::episodes_by::dsl as eb;
+use crate::schema::issues::dsl as i;
+use crate::schema::publications::dsl as p;
+use crate::templates::ToHtml;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// Number of episodes per year for each role, and optionally covers.
+///
+/// Rendered as an svg grid with a row per role and a column per year,
+/// where each year links to the year page.
+pub struct Timeline {
+    /// Label and count per year for each row, in display order.
+    rows: Vec<(String, BTreeMap<i16, usize>)>,
+}
+
+impl Timeline {
+    /// Episodes and covers by a creator.
+    pub async fn for_creator(
+        creator_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Timeline, Error> {
+        let episodes = eb::episodes_by
+            .inner_join(ca::creator_aliases)
+            .inner_join(
+                e::episodes.inner_join(
+                    ep::episode_parts
+                        .inner_join(p::publications.inner_join(i::issues)),
+                ),
+            )
+            .filter(ca::creator_id.eq(creator_id))
+            .select((i::year, eb::role, eb::episode_id))
+            .distinct()
+            .load(db)
+            .await?;
+        let mut timeline = Timeline::build(episodes, db).await?;
+        let covers = cb::covers_by
+            .inner_join(ca::creator_aliases)
+            .inner_join(i::issues)
+            .filter(ca::creator_id.eq(creator_id))
+            .select((i::year, cb::issue_id))
+            .distinct()
+            .load::<(i16, i32)>(db)
+            .await?;
+        if !covers.is_empty() {
+            timeline.rows.push(("omslag".into(), per_year(&covers)));
+        }
+        Ok(timeline)
+    }
+
+    /// Episodes of a title, by the roles credited.
+    pub async fn for_title(
+        title_id: i32,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Timeline, Error> {
+        let episodes = eb::episodes_by
+            .inner_join(
+                e::episodes.inner_join(
+                    ep::episode_parts
+                        .inner_join(p::publications.inner_join(i::issues)),
+                ),
+            )
+            .filter(e::title_id.eq(title_id))
+            .select((i::year, eb::role, eb::episode_id))
+            .distinct()
+            .load(db)
+            .await?;
+        Timeline::build(episodes, db).await
+    }
+
+    async fn build(
+        episodes: Vec<(i16, String, i32)>,
+        db: &mut AsyncPgConnection,
+    ) -> Result<Timeline, Error> {
+        let mut per_role = BTreeMap::<String, Vec<(i16, i32)>>::new();
+        for (year, role, episode) in episodes {
+            per_role.entry(role).or_default().push((year, episode));
+        }
+        let rows = Role::all(db)
+            .await?
+            .into_iter()
+            .filter_map(|role| {
+                per_role
+                    .remove(&role.code)
+                    .map(|c| (role.noun, per_year(&c)))
+            })
+            .collect();
+        Ok(Timeline { rows })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The first and last year with any activity.
+    fn years(&self) -> Option<(i16, i16)> {
+        let years = self.rows.iter().flat_map(|(_, c)| c.keys());
+        let first = years.clone().min()?;
+        let last = years.max()?;
+        Some((*first, *last))
+    }
+}
+
+/// The number of (year, id) pairs for each year.
+fn per_year(items: &[(i16, i32)]) -> BTreeMap<i16, usize> {
+    let mut counts = BTreeMap::new();
+    for (year, _) in items {
+        *counts.entry(*year).or_default() += 1;
+    }
+    counts
+}
+
+impl ToHtmlIn for Timeline {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
+        let Some((y0, y1)) = self.years() else {
+            return Ok(());
+        };
+        let (w, label, row, margin) = (800., 110., 18., 20.);
+        let cols = f64::from(y1 - y0 + 1);
+        let cell = (w - label - margin) / cols;
+        #[allow(clippy::cast_precision_loss)]
+        let h = self.rows.len() as f64 * row + margin;
+        let x = |year: i16| label + f64::from(year - y0) * cell;
+
+        write!(
+            out,
+            "<svg class='timeline' viewBox='0 0 {w} {h}' role='img' \
+             aria-label='{}'>",
+            lang.t("Tidslinje"),
+        )?;
+        let decades = (y0..=y1).filter(|y| y % 10 == 0).collect::<Vec<_>>();
+        let labels = if decades.is_empty() {
+            vec![y0]
+        } else {
+            decades
+        };
+        for year in labels {
+            write!(
+                out,
+                "<a href='/{year}'><text class='xlabel' x='{x:.1}' \
+                 y='{y}'>{year}</text></a>",
+                x = x(year),
+                y = h - 6.,
+            )?;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        for (n, (noun, counts)) in self.rows.iter().enumerate() {
+            let y = n as f64 * row;
+            write!(
+                out,
+                "<text class='ylabel' x='{x}' y='{y:.1}'>",
+                x = label - 6.,
+                y = y + row * 0.7,
+            )?;
+            lang.t(noun).to_html(out)?;
+            write!(out, "</text>")?;
+            let max = counts.values().copied().max().unwrap_or(1).max(1);
+            for (year, count) in counts {
+                write!(
+                    out,
+                    "<a href='/{year}'><rect class='w{w}' x='{x:.1}' \
+                     y='{y:.1}' width='{cw:.1}' height='{rh}'><title>",
+                    w = (count * 4).div_ceil(max),
+                    x = x(*year),
+                    y = y + 1.,
+                    cw = (cell - 1.).max(0.5),
+                    rh = row - 2.,
+                )?;
+                lang.t(noun).to_html(out)?;
+                write!(out, " {year}: {count}</title></rect></a>")?;
+            }
+        }
+        write!(out, "</svg>")
+    }
+}
+
+#[test]
+fn years_span_all_rows() {
+    let timeline = Timeline {
+        rows: vec![
+            ("tecknare".into(), per_year(&[(1972, 2), (1980, 1)])),
+            ("omslag".into(), per_year(&[(1968, 1)])),
+        ],
+    };
+    assert_eq!(timeline.years(), Some((1968, 1980)));
+}
@@ -1,8 +1,12 @@
+use super::chronology::title_chronology;
+use super::locale::lang;
+use super::reprints::reprinted_ids;
 use super::{
     FullArticle, FullEpisode, Paginator, PgFilter, PgPool, RenderRucte,
-    Result, ViewError, goh, redirect, wrap,
+    Result, Timeline, ViewError, goh, redirect, wrap,
 };
-use crate::models::{Article, Episode, IssueRef, RefKey, Title};
+use crate::i18n::Lang;
+use crate::models::{Article, Episode, IssueRef, RefKey, Title, TitleInfo};
 use crate::schema::article_refkeys::dsl as ar;
 use crate::schema::articles::dsl as a;
 use crate::schema::episode_parts::dsl as ep;
@@ -22,18 +26,35 @@ use warp::{self, Filter, Reply, reply::Response};
 
 pub fn routes(s: PgFilter) -> BoxedFilter<(impl Reply,)> {
     use warp::filters::query::query;
-    use warp::path::{end, param};
-    let list = goh().and(end()).and(s.clone()).then(list_titles);
+    use warp::path::{end, param, path};
+    let list = goh()
+        .and(end())
+        .and(s.clone())
+        .and(lang())
+        .then(list_titles);
     let one = goh()
-        .and(s)
+        .and(s.clone())
         .and(param())
         .and(end())
         .and(query())
+        .and(lang())
         .then(one_title);
-    list.or(one).unify().map(wrap).boxed()
+    let chronology = goh()
+        .and(s)
+        .and(param())
+        .and(path("chronology"))
+        .and(end())
+        .and(lang())
+        .then(title_chronology);
+    list.or(chronology)
+        .unify()
+        .or(one)
+        .unify()
+        .map(wrap)
+        .boxed()
 }
 
-async fn list_titles(db: PgPool) -> Result<Response> {
+async fn list_titles(db: PgPool, lang: Lang) -> Result<Response> {
     let mut db = db.get().await?;
     let all = t::titles
         .inner_join(
@@ -58,18 +79,22 @@ async fn list_titles(db: PgPool) -> Result<Response> {
             (title, c, first.unwrap(), last.unwrap())
         })
         .collect::<Vec<_>>();
-    Ok(Builder::new().html(|o| titles_html(o, &all))?)
+    Ok(Builder::new().html(|o| titles_html(o, lang, &all))?)
 }
 
 #[derive(Deserialize)]
 pub struct PageParam {
     p: Option<usize>,
+    /// Show only episodes published more than once.
+    #[serde(default)]
+    reprinted: bool,
 }
 
 async fn one_title(
     db: PgPool,
     slug: String,
     page: PageParam,
+    lang: Lang,
 ) -> Result<Response> {
     let mut db = db.get().await?;
     let (slug, strip) = if let Some(strip) = slug.strip_prefix("weekdays-") {
@@ -85,6 +110,8 @@ async fn one_title(
         .await
         .optional()?
         .ok_or(ViewError::NotFound)?;
+    let info = TitleInfo::load(title.id, &mut db).await?;
+    let timeline = Timeline::for_title(title.id, &mut db).await?;
 
     let articles_raw = a::articles
         .select(a::articles::all_columns())
@@ -134,7 +161,18 @@ async fn one_title(
         }
     };
 
-    let (episodes_raw, pages) = Paginator::if_needed(episodes, page.p)
+    let episodes = if page.reprinted {
+        let ids = reprinted_ids(Some(title.id), &mut db).await?;
+        episodes
+            .into_iter()
+            .filter(|e| ids.contains(&e.id))
+            .collect()
+    } else {
+        episodes
+    };
+
+    let query = if page.reprinted { "reprinted=true" } else { "" };
+    let (episodes_raw, pages) = Paginator::if_needed(episodes, page.p, query)
         .map_err(|()| ViewError::NotFound)?;
 
     let mut episodes = Vec::with_capacity(episodes_raw.len());
@@ -142,9 +180,29 @@ async fn one_title(
         episodes.push(FullEpisode::load_details(episode, &mut db).await?);
     }
 
-    Ok(Builder::new().html(|o| {
-        title_html(o, &title, pages.as_ref(), &articles, &episodes)
-    })?)
+    let page = TitlePage {
+        title,
+        info,
+        timeline,
+        reprinted: page.reprinted,
+        pages,
+        articles,
+        episodes,
+    };
+    Ok(Builder::new().html(|o| title_html(o, lang, &page))?)
+}
+
+/// Everything shown on the page for a title.
+pub struct TitlePage {
+    pub title: Title,
+    pub info: TitleInfo,
+    pub timeline: Timeline,
+    /// True if only episodes published more than once are shown.
+    pub reprinted: bool,
+    pub pages: Option<Paginator>,
+    /// Articles about the title, with the issues they are in.
+    pub articles: Vec<(FullArticle, Vec<IssueRef>)>,
+    pub episodes: Vec<FullEpisode>,
 }
 
 pub async fn oldslug(slug: String, db: PgPool) -> Result<impl Reply> {
@@ -1,4 +1,5 @@
 use super::{DbError, PgPool, Result, ViewError, YearLinks};
+use crate::i18n::{Lang, ToHtmlIn};
 use crate::models::{Creator, Issue, Part};
 use crate::schema::articles::dsl as a;
 use crate::schema::episode_parts::dsl as ep;
@@ -13,7 +14,11 @@ use std::io::{self, Write};
 use warp::http::response::Builder;
 use warp::{self, Reply};
 
-pub async fn year_summary(year: i16, db: PgPool) -> Result<impl Reply> {
+pub async fn year_summary(
+    year: i16,
+    db: PgPool,
+    lang: Lang,
+) -> Result<impl Reply> {
     let mut db = db.get().await?;
 
     let (ord_min, ord_max): (Option<i32>, Option<i32>) = i::issues
@@ -37,7 +42,7 @@ pub async fn year_summary(year: i16, db: PgPool) -> Result<impl Reply> {
     }
     let years = YearLinks::load(year, &mut db).await?;
     Ok(Builder::new()
-        .html(|o| year_summary_html(o, year, ord, &years, &issues))?)
+        .html(|o| year_summary_html(o, lang, year, ord, &years, &issues))?)
 }
 
 async fn load_summary(
@@ -90,10 +95,10 @@ impl ContentSummary {
     }
 }
 
-impl ToHtml for ContentSummary {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for ContentSummary {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         match self {
-            ContentSummary::Comic(c, _plac) => c.to_html(out),
+            ContentSummary::Comic(c, _plac) => c.to_html_in(lang, out),
             ContentSummary::Text(a) => a.to_html(out),
         }
     }
@@ -108,8 +113,8 @@ pub struct ComicSummary {
 }
 
 // <strong><a href="/titles/slug">title</a>[episode]</strong> [part]
-impl ToHtml for ComicSummary {
-    fn to_html(&self, out: &mut dyn Write) -> io::Result<()> {
+impl ToHtmlIn for ComicSummary {
+    fn to_html_in(&self, lang: Lang, out: &mut dyn Write) -> io::Result<()> {
         write!(out, "<strong><a href='/titles/{}'>", self.slug)?;
         self.title.to_html(out)?;
         out.write_all(b"</a>")?;
@@ -120,7 +125,7 @@ impl ToHtml for ComicSummary {
         out.write_all(b"</strong>")?;
         if self.part.is_part() {
             out.write_all(b" ")?;
-            self.part.to_html(out)?;
+            self.part.to_html_in(lang, out)?;
         }
         Ok(())
     }
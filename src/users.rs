@@ -0,0 +1,161 @@
+use crate::DbOpt;
+use crate::models::User;
+use crate::models::collection::{export_owned, import_owned};
+use crate::schema::owned_issues::dsl as oi;
+use crate::schema::read_episodes::dsl as re;
+use crate::schema::users::dsl as u;
+use anyhow::{Context, Result, anyhow, bail};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::fs::read_to_string;
+use std::io::{BufRead, Write, stderr, stdin};
+use std::path::PathBuf;
+
+#[derive(clap::Parser)]
+pub struct Args {
+    #[clap(flatten)]
+    db: DbOpt,
+
+    #[clap(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(clap::Subcommand)]
+enum Cmd {
+    /// Create a user.  The password is read from stdin.
+    Add {
+        username: String,
+        /// Let the user edit issue contents.
+        #[arg(long)]
+        admin: bool,
+    },
+    /// Grant or revoke the right to edit issue contents.
+    Admin {
+        username: String,
+        /// Revoke admin rights rather than granting them.
+        #[arg(long)]
+        revoke: bool,
+    },
+    /// Change the password of a user, ending all sessions.
+    ///
+    /// The password is read from stdin.
+    Passwd { username: String },
+    /// Remove a user, with all owned issues and read episodes.
+    Remove { username: String },
+    /// List all users.
+    List,
+    /// Write the issues owned by a user as csv to stdout.
+    ///
+    /// The columns are year, nr and condition.
+    Export { username: String },
+    /// Read owned issues for a user from a csv file.
+    ///
+    /// The columns are year, nr and an optional condition.  Issues
+    /// already owned get the condition updated.  Rows that do not
+    /// match any known issue are reported.
+    Import { username: String, file: PathBuf },
+}
+
+impl Args {
+    pub async fn run(self) -> Result<()> {
+        let mut db = self.db.get_db().await?;
+        match self.cmd {
+            Cmd::Add { username, admin } => {
+                if User::by_name(&username, &mut db).await?.is_some() {
+                    bail!("User {username:?} already exists");
+                }
+                let user =
+                    User::create(&username, &read_password()?, &mut db)
+                        .await?;
+                if admin {
+                    user.set_admin(true, &mut db).await?;
+                }
+                println!("Created user {username:?}.");
+            }
+            Cmd::Admin { username, revoke } => {
+                get_user(&username, &mut db)
+                    .await?
+                    .set_admin(!revoke, &mut db)
+                    .await?;
+                println!("Updated admin rights for {username:?}.");
+            }
+            Cmd::Passwd { username } => {
+                get_user(&username, &mut db)
+                    .await?
+                    .set_password(&read_password()?, &mut db)
+                    .await?;
+                println!("Updated password for {username:?}.");
+            }
+            Cmd::Remove { username } => {
+                let user = get_user(&username, &mut db).await?;
+                diesel::delete(u::users.filter(u::id.eq(user.id)))
+                    .execute(&mut db)
+                    .await?;
+                println!("Removed user {username:?}.");
+            }
+            Cmd::List => {
+                let users = u::users
+                    .select((
+                        u::username,
+                        u::admin,
+                        oi::owned_issues
+                            .filter(oi::user_id.eq(u::id))
+                            .count()
+                            .single_value(),
+                        re::read_episodes
+                            .filter(re::user_id.eq(u::id))
+                            .count()
+                            .single_value(),
+                    ))
+                    .order(u::username)
+                    .load::<(String, bool, Option<i64>, Option<i64>)>(&mut db)
+                    .await?;
+                for (name, admin, owned, read) in users {
+                    println!(
+                        "{name}{}: {} issues owned, {} episodes read",
+                        if admin { " (admin)" } else { "" },
+                        owned.unwrap_or(0),
+                        read.unwrap_or(0),
+                    );
+                }
+            }
+            Cmd::Export { username } => {
+                let user = get_user(&username, &mut db).await?;
+                print!("{}", export_owned(&user, &mut db).await?);
+            }
+            Cmd::Import { username, file } => {
+                let user = get_user(&username, &mut db).await?;
+                let csv = read_to_string(&file)
+                    .with_context(|| format!("Failed to read {file:?}"))?;
+                let report = import_owned(&user, &csv, &mut db).await?;
+                for bad in &report.bad {
+                    eprintln!("{bad}");
+                }
+                println!(
+                    "Imported {} issues for {username:?}, {} rows failed.",
+                    report.imported,
+                    report.bad.len(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn get_user(name: &str, db: &mut AsyncPgConnection) -> Result<User> {
+    User::by_name(name, db)
+        .await?
+        .ok_or_else(|| anyhow!("No such user {name:?}"))
+}
+
+fn read_password() -> Result<String> {
+    eprint!("Password: ");
+    stderr().flush()?;
+    let mut line = String::new();
+    stdin().lock().read_line(&mut line)?;
+    let password = line.trim_end_matches(['\r', '\n']);
+    if password.len() < 8 {
+        bail!("The password should be at least 8 characters");
+    }
+    Ok(password.to_string())
+}
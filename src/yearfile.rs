@@ -0,0 +1,182 @@
+//! Editing the xml source of single issues in a `{year}.data` file.
+//!
+//! The file is edited as text, so formatting and comments outside of
+//! the edited issue element are preserved.
+use anyhow::{Context, Result, bail};
+use roxmltree::{Document, Node};
+use std::fs::{read_to_string, write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+pub struct YearFile {
+    path: PathBuf,
+    text: String,
+}
+
+impl YearFile {
+    pub fn load(basedir: &Path, year: i16) -> Result<YearFile> {
+        let path = basedir.join(format!("{year}.data"));
+        let text = read_to_string(&path)
+            .with_context(|| format!("Failed to read {path:?}"))?;
+        Ok(YearFile { path, text })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A temporary path next to the data file, for [`YearFile::save_as`].
+    pub fn temp_path(&self) -> PathBuf {
+        self.path.with_extension("data.new")
+    }
+
+    /// Write the edited text to `path`, leaving the data file as is.
+    ///
+    /// The new file can then be renamed to replace the data file.
+    pub fn save_as(&self, path: &Path) -> Result<()> {
+        write(path, &self.text)
+            .with_context(|| format!("Failed to write {path:?}"))
+    }
+
+    /// The xml source of the issue with a given number, if any.
+    pub fn issue_xml(&self, nr: &str) -> Result<Option<&str>> {
+        Ok(self.issue_range(nr)?.map(|r| &self.text[r]))
+    }
+
+    /// Replace the issue element with number `nr`, or add it.
+    ///
+    /// A new issue is added before the first issue with a higher
+    /// number, or last in the file.
+    pub fn set_issue(&mut self, nr: &str, xml: &str) -> Result<()> {
+        check_issue(nr, xml)?;
+        let xml = xml.trim();
+        if let Some(range) = self.issue_range(nr)? {
+            self.text.replace_range(range, xml);
+            return Ok(());
+        }
+        let first = first_number(nr);
+        let (at, end) = {
+            let doc = Document::parse(&self.text)?;
+            let root = doc.root_element();
+            let at = root
+                .children()
+                .filter(|n| n.has_tag_name("issue"))
+                .find(|n| n.attribute("nr").map(first_number) > Some(first))
+                .map(|n| n.range().start);
+            (at, root_end_tag(&self.text, root)?)
+        };
+        match at {
+            Some(at) => self.text.insert_str(at, &format!("{xml}\n  ")),
+            None => self.text.insert_str(end, &format!("  {xml}\n")),
+        }
+        Ok(())
+    }
+
+    fn issue_range(&self, nr: &str) -> Result<Option<Range<usize>>> {
+        let doc = Document::parse(&self.text)
+            .with_context(|| format!("Failed to parse {:?}", self.path))?;
+        Ok(doc
+            .root_element()
+            .children()
+            .find(|n| {
+                n.has_tag_name("issue") && n.attribute("nr") == Some(nr)
+            })
+            .map(|n| n.range()))
+    }
+}
+
+/// Check that `xml` is a single well-formed issue element for `nr`.
+pub fn check_issue(nr: &str, xml: &str) -> Result<()> {
+    let doc = Document::parse(xml.trim())?;
+    let root = doc.root_element();
+    if !root.has_tag_name("issue") {
+        bail!(
+            "Expected an issue element, got {:?}",
+            root.tag_name().name()
+        );
+    }
+    if root.attribute("nr") != Some(nr) {
+        bail!("Expected issue nr {nr:?}, got {:?}", root.attribute("nr"));
+    }
+    Ok(())
+}
+
+/// Escape text for use in xml content or attribute values.
+pub fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn first_number(nr: &str) -> Option<i16> {
+    nr.split('-').next()?.parse().ok()
+}
+
+/// The position of the end tag of the root element.
+fn root_end_tag(text: &str, root: Node) -> Result<usize> {
+    let end = root.range().end;
+    match text[..end].rfind("</") {
+        Some(pos) => Ok(pos),
+        None => bail!("Root element has no end tag"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::YearFile;
+
+    fn file(text: &str) -> YearFile {
+        YearFile {
+            path: "test.data".into(),
+            text: text.into(),
+        }
+    }
+
+    const DATA: &str = "<year>\n  <!-- keep -->\n  <issue nr=\"1\">\n    \
+                        <text><title>A</title></text>\n  </issue>\n  \
+                        <issue nr=\"4-5\" pages=\"52\"/>\n</year>\n";
+
+    #[test]
+    fn get_issue() {
+        let f = file(DATA);
+        assert_eq!(
+            f.issue_xml("4-5").unwrap(),
+            Some("<issue nr=\"4-5\" pages=\"52\"/>"),
+        );
+        assert_eq!(f.issue_xml("4").unwrap(), None);
+    }
+
+    #[test]
+    fn replace_issue() {
+        let mut f = file(DATA);
+        f.set_issue("1", "<issue nr=\"1\" pages=\"36\"/>\n")
+            .unwrap();
+        assert_eq!(
+            f.text,
+            "<year>\n  <!-- keep -->\n  <issue nr=\"1\" pages=\"36\"/>\n  \
+             <issue nr=\"4-5\" pages=\"52\"/>\n</year>\n",
+        );
+    }
+
+    #[test]
+    fn add_issue() {
+        let mut f = file(DATA);
+        f.set_issue("2", "<issue nr=\"2\"/>").unwrap();
+        f.set_issue("7", "<issue nr=\"7\"/>").unwrap();
+        assert_eq!(
+            f.text,
+            "<year>\n  <!-- keep -->\n  <issue nr=\"1\">\n    \
+             <text><title>A</title></text>\n  </issue>\n  \
+             <issue nr=\"2\"/>\n  <issue nr=\"4-5\" pages=\"52\"/>\n  \
+             <issue nr=\"7\"/>\n</year>\n",
+        );
+    }
+
+    #[test]
+    fn wrong_nr() {
+        let mut f = file(DATA);
+        assert!(f.set_issue("2", "<issue nr=\"3\"/>").is_err());
+        assert!(f.set_issue("2", "<issue nr=\"2\">").is_err());
+    }
+}